@@ -9,7 +9,11 @@ use good_web_game::graphics::spritebatch::SpriteBatch;
 use good_web_game::graphics::spritebatch::SpriteIdx;
 use good_web_game::graphics::Rect;
 use gwg::graphics::DrawParam;
-use gwg::graphics::{self,};
+use gwg::graphics::{
+	self,
+};
+
+use super::asset_name::AssetName;
 
 fn norm_angle(angle: f64) -> f64 {
 	angle.rem_euclid(std::f64::consts::TAU) / std::f64::consts::TAU
@@ -33,10 +37,42 @@ pub struct AssetParams {
 	pub height: u32,
 }
 
+impl AssetParams {
+	/// Reads the parameters of the named asset from the config
+	pub fn from_config(config: &AssetConfig, asset_name: &str) -> Option<Self> {
+		let asset = config.find_asset(asset_name)?;
+
+		Some(Self {
+			z_local_frames: asset.z_local_frames,
+			z_frames: asset.z_frames,
+			x_frames: asset.x_frames,
+			width: asset.width,
+			height: asset.height.unwrap_or(asset.width),
+		})
+	}
+
+	/// Checks whether these parameters describe a drawable asset
+	pub fn check(&self) -> Result<(), String> {
+		if self.z_local_frames == 0 || self.z_frames == 0 || self.x_frames == 0 {
+			Err(format!(
+				"has no frames ({}x{}x{})",
+				self.z_local_frames, self.z_frames, self.x_frames
+			))
+		} else if self.width == 0 || self.height == 0 {
+			Err(format!("has no size ({}x{})", self.width, self.height))
+		} else {
+			Ok(())
+		}
+	}
+}
+
 #[derive(Debug)]
 pub struct AssetBatch {
 	batch: SpriteBatch,
 	params: AssetParams,
+	/// The key of the asset in the config, if loaded from it
+	#[cfg(all(feature = "dev", not(target_family = "wasm")))]
+	asset_key: Option<String>,
 }
 
 impl AssetBatch {
@@ -44,6 +80,8 @@ impl AssetBatch {
 		Self {
 			batch,
 			params,
+			#[cfg(all(feature = "dev", not(target_family = "wasm")))]
+			asset_key: None,
 		}
 	}
 
@@ -61,21 +99,52 @@ impl AssetBatch {
 		ctx: &mut gwg::Context,
 		quad_ctx: &mut gwg::miniquad::Context,
 		config: &AssetConfig,
-		asset_name: &str,
+		asset_name: AssetName,
+	) -> gwg::GameResult<Self> {
+		Self::from_config_key(ctx, quad_ctx, config, asset_name.key())
+	}
+
+	/// Loads the asset of the given `key` in the config, e.g. of a hull, see
+	/// `asset_name::hull_key`
+	pub fn from_config_key(
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::Context,
+		config: &AssetConfig,
+		key: &str,
 	) -> gwg::GameResult<Self> {
-		let asset = config.find_asset(asset_name).unwrap();
-		let asset_filename = config.get_asset_output(asset_name).unwrap();
+		let params = AssetParams::from_config(config, key).unwrap();
+		let asset_filename = config.get_asset_output(key).unwrap();
 		let asset_filepath = PathBuf::from("rendered").join(asset_filename);
 
-		let params = AssetParams {
-			z_local_frames: asset.z_local_frames,
-			z_frames: asset.z_frames,
-			x_frames: asset.x_frames,
-			width: asset.width,
-			height: asset.height.unwrap_or(asset.width),
-		};
+		#[allow(unused_mut)]
+		let mut batch = Self::from_image_file(ctx, quad_ctx, asset_filepath, params)?;
+		#[cfg(all(feature = "dev", not(target_family = "wasm")))]
+		{
+			batch.asset_key = Some(key.to_owned());
+		}
+		Ok(batch)
+	}
 
-		Self::from_image_file(ctx, quad_ctx, asset_filepath, params)
+	/// Re-reads the parameters of this asset from the given config
+	///
+	/// Only the parameters are updated, the image stays the same. Does nothing,
+	/// if this batch was not loaded from a config or its asset is missing.
+	///
+	/// Returns whether the parameters changed.
+	#[cfg(all(feature = "dev", not(target_family = "wasm")))]
+	pub fn reload_params(&mut self, config: &AssetConfig) -> bool {
+		let params = self
+			.asset_key
+			.as_deref()
+			.and_then(|key| AssetParams::from_config(config, key));
+
+		match params {
+			Some(params) if params != self.params => {
+				self.params = params;
+				true
+			},
+			_ => false,
+		}
 	}
 
 	pub fn add_frame(
@@ -0,0 +1,142 @@
+use enum_map::Enum;
+use logic::state::SailKind;
+use logic::state::ShipHull;
+
+
+/// The key of the sprite of the given hull in the asset config
+///
+/// The hulls follow the naming convention `ship-NN`, with `NN` being the
+/// position of the hull in the upgrade order, so a new hull just needs the
+/// sprite of the next number.
+pub fn hull_key(hull: ShipHull) -> String {
+	format!("ship-{:02}", hull.into_usize())
+}
+
+/// The names of the assets in the asset config
+///
+/// Each variant maps to the key of its entry in `render_assets.toml`.
+/// The hulls are not listed, they follow a naming convention, see [hull_key].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(strum::EnumIter, strum::IntoStaticStr)]
+pub enum AssetName {
+	#[strum(serialize = "sail-00-0")]
+	SailBermuda0,
+	#[strum(serialize = "sail-00-1")]
+	SailBermuda1,
+	#[strum(serialize = "sail-00-2")]
+	SailBermuda2,
+	#[strum(serialize = "sail-00-3")]
+	SailBermuda3,
+	#[strum(serialize = "sail-00-4")]
+	SailBermuda4,
+
+	#[strum(serialize = "sail-01-0")]
+	SailSchooner0,
+	#[strum(serialize = "sail-01-1")]
+	SailSchooner1,
+	#[strum(serialize = "sail-01-2")]
+	SailSchooner2,
+	#[strum(serialize = "sail-01-3")]
+	SailSchooner3,
+	#[strum(serialize = "sail-01-4")]
+	SailSchooner4,
+	#[strum(serialize = "sail-01-5")]
+	SailSchooner5,
+	#[strum(serialize = "sail-01-6")]
+	SailSchooner6,
+	#[strum(serialize = "sail-01-7")]
+	SailSchooner7,
+
+	#[strum(serialize = "sail-02-0")]
+	SailCog0,
+	#[strum(serialize = "sail-02-1")]
+	SailCog1,
+	#[strum(serialize = "sail-02-2")]
+	SailCog2,
+	#[strum(serialize = "sail-02-3")]
+	SailCog3,
+
+	#[strum(serialize = "fish-00")]
+	Fish0,
+	#[strum(serialize = "fish-01")]
+	Fish1,
+	#[strum(serialize = "fish-02")]
+	Fish2,
+	#[strum(serialize = "fish-03")]
+	Fish3,
+	#[strum(serialize = "fish-04")]
+	Fish4,
+	#[strum(serialize = "fish-05")]
+	Fish5,
+	#[strum(serialize = "fish-06")]
+	Fish6,
+	#[strum(serialize = "fish-07")]
+	Fish7,
+
+	#[strum(serialize = "starfish-00")]
+	Starfish0,
+	#[strum(serialize = "starfish-01")]
+	Starfish1,
+	#[strum(serialize = "starfish-02")]
+	Starfish2,
+	#[strum(serialize = "starfish-03")]
+	Starfish3,
+	#[strum(serialize = "starfish-04")]
+	Starfish4,
+
+	#[strum(serialize = "shoe-00")]
+	Shoe0,
+	#[strum(serialize = "shoe-01")]
+	Shoe1,
+
+	#[strum(serialize = "grass-00")]
+	Grass0,
+	#[strum(serialize = "grass-01")]
+	Grass1,
+
+	#[strum(serialize = "harbour-00")]
+	Harbor,
+}
+
+impl AssetName {
+	/// The sprites of the given sail, one per reefing level
+	pub fn sail(kind: SailKind) -> &'static [Self] {
+		match kind {
+			SailKind::Cog => {
+				&[
+					Self::SailCog0,
+					Self::SailCog1,
+					Self::SailCog2,
+					Self::SailCog3,
+				]
+			},
+			// TODO: give the gaff sail its own sprites, once the asset repo has them
+			SailKind::Bermuda | SailKind::Gaff => {
+				&[
+					Self::SailBermuda0,
+					Self::SailBermuda1,
+					Self::SailBermuda2,
+					Self::SailBermuda3,
+					Self::SailBermuda4,
+				]
+			},
+			SailKind::Schooner => {
+				&[
+					Self::SailSchooner0,
+					Self::SailSchooner1,
+					Self::SailSchooner2,
+					Self::SailSchooner3,
+					Self::SailSchooner4,
+					Self::SailSchooner5,
+					Self::SailSchooner6,
+					Self::SailSchooner7,
+				]
+			},
+		}
+	}
+
+	/// The key of this asset in the asset config
+	pub fn key(self) -> &'static str {
+		self.into()
+	}
+}
@@ -0,0 +1,47 @@
+//! Extraction of the packaged assets into a cache directory on disk
+//!
+
+use std::path::Path;
+use std::path::PathBuf;
+
+
+/// The name of the cache directory of this game
+const CACHE_DIR_NAME: &str = "plenty-of-fish-in-the-sea";
+
+/// The file marking a completely extracted cache
+const COMPLETE_MARKER: &str = ".complete";
+
+/// The base directory for caches of the current user
+fn user_cache_dir() -> Option<PathBuf> {
+	std::env::var_os("XDG_CACHE_HOME")
+		.map(PathBuf::from)
+		.or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+		.or_else(|| std::env::var_os("LOCALAPPDATA").map(PathBuf::from))
+}
+
+/// Returns the directory with the extracted `archive`, extracting it, if needed
+///
+/// Each `version` gets its own directory, so an outdated cache is never used.
+/// Returns `None`, if the cache can not be set up, in which case the embedded
+/// archive should be used instead.
+pub fn extracted_assets(archive: &[u8], version: &str) -> Option<PathBuf> {
+	let dir = user_cache_dir()?.join(CACHE_DIR_NAME).join(version);
+	let marker = dir.join(COMPLETE_MARKER);
+
+	if marker.exists() {
+		return Some(dir);
+	}
+
+	println!("[cache] extracting assets to {}", dir.display());
+	let extraction = std::fs::create_dir_all(&dir)
+		.and_then(|()| tar::Archive::new(archive).unpack(&dir))
+		.and_then(|()| std::fs::write(&marker, ""));
+
+	match extraction {
+		Ok(()) => Some(dir),
+		Err(err) => {
+			println!("[cache] failed to extract the assets: {err}");
+			None
+		},
+	}
+}
@@ -1,20 +1,32 @@
+use std::path::Path;
+
 use asset_config::AssetConfig;
+use cfg_if::cfg_if;
 use enum_map::EnumMap;
 use good_web_game as gwg;
 use good_web_game::graphics::spritebatch::SpriteBatch;
 use good_web_game::GameResult;
 use gwg::graphics::Color;
+use gwg::graphics::DrawParam;
 use gwg::graphics::Image;
+use logic::resource::ResourceTable;
 use logic::state::SailKind;
 use logic::state::ShipHull;
 use logic::units::TileType;
 use nalgebra::Point2;
 
 use self::asset_batch::AssetBatch;
+use self::asset_batch::AssetParams;
 
 
 pub mod asset_batch;
+pub mod asset_name;
 pub mod audio;
+#[cfg(not(target_family = "wasm"))]
+pub mod cache;
+
+#[cfg(test)]
+mod test;
 
 
 /// The location of the asset configuration file
@@ -23,6 +35,17 @@ const ASSET_CONFIG_STR: &str = include_str!(concat!(
 	"/../asset-repo/render_assets.toml"
 ));
 
+/// The path of the asset configuration file on the development machine
+#[cfg(all(feature = "dev", not(target_family = "wasm")))]
+const ASSET_CONFIG_PATH: &str = concat!(
+	env!("CARGO_MANIFEST_DIR"),
+	"/../asset-repo/render_assets.toml"
+);
+
+/// The stats of the resource types, for balancing
+const RESOURCE_TABLE_STR: &str =
+	include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/resources.toml"));
+
 /// UI assets bundle
 pub struct UiImages {
 	/// Image to indicate the direction of the wind
@@ -230,9 +253,175 @@ pub struct BuildingBatches {
 	pub harbor: AssetBatch,
 }
 
-/// Load the asset configuration file
-pub fn load_asset_config() -> AssetConfig {
-	toml::from_str(ASSET_CONFIG_STR).unwrap()
+#[cfg(all(feature = "dev", not(target_family = "wasm")))]
+impl ShipSprites {
+	/// Iterates over all asset batches of this ship
+	pub fn asset_batches_mut(&mut self) -> impl Iterator<Item = &mut AssetBatch> {
+		self.body
+			.values_mut()
+			.chain(self.sail.values_mut().flatten())
+	}
+}
+
+#[cfg(all(feature = "dev", not(target_family = "wasm")))]
+impl ResourceBatches {
+	/// Iterates over all asset batches of the resources
+	pub fn asset_batches_mut(&mut self) -> impl Iterator<Item = &mut AssetBatch> {
+		self.fishes
+			.iter_mut()
+			.chain(self.starfishes.iter_mut())
+			.chain(self.shoe.iter_mut())
+			.chain(self.grass.iter_mut())
+	}
+}
+
+cfg_if! {
+	if #[cfg(all(feature = "dev", not(target_family = "wasm")))] {
+		/// Load the asset configuration file
+		///
+		/// In development, the file is read at runtime, falling back to the
+		/// embedded one, if it can not be read.
+		pub fn load_asset_config() -> AssetConfig {
+			match read_asset_config() {
+				Ok(config) => config,
+				Err(err) => {
+					println!("[assets] failed to load {ASSET_CONFIG_PATH}: {err}");
+					toml::from_str(ASSET_CONFIG_STR).unwrap()
+				},
+			}
+		}
+
+		fn read_asset_config() -> Result<AssetConfig, String> {
+			let content = std::fs::read_to_string(ASSET_CONFIG_PATH).map_err(|e| e.to_string())?;
+			toml::from_str(&content).map_err(|e| e.to_string())
+		}
+
+		fn asset_config_modified() -> Option<std::time::SystemTime> {
+			std::fs::metadata(ASSET_CONFIG_PATH)
+				.and_then(|m| m.modified())
+				.ok()
+		}
+
+		/// Watches the asset configuration file for changes
+		#[derive(Debug, Clone)]
+		pub struct AssetConfigWatcher {
+			/// The modification time of the file when last loaded
+			modified: Option<std::time::SystemTime>,
+		}
+
+		impl AssetConfigWatcher {
+			pub fn new() -> Self {
+				Self {
+					modified: asset_config_modified(),
+				}
+			}
+
+			/// Returns the reloaded config, if the file changed since the last call
+			///
+			/// A broken config is reported and skipped, until the file changes again.
+			pub fn poll(&mut self) -> Option<AssetConfig> {
+				let modified = asset_config_modified();
+				if modified == self.modified {
+					return None;
+				}
+				self.modified = modified;
+
+				match read_asset_config() {
+					Ok(config) => Some(config),
+					Err(err) => {
+						println!("[assets] failed to reload {ASSET_CONFIG_PATH}: {err}");
+						None
+					},
+				}
+			}
+		}
+	} else {
+		/// Load the asset configuration file
+		pub fn load_asset_config() -> AssetConfig {
+			toml::from_str(ASSET_CONFIG_STR).unwrap()
+		}
+	}
+}
+
+/// Load the stats of the resource types
+///
+/// They are read from the file at `path`, if given, otherwise the packaged
+/// ones are used. The resource types missing in the file keep their built-in
+/// stats.
+pub fn load_resource_table(path: Option<&Path>) -> Result<ResourceTable, String> {
+	match path {
+		Some(path) => {
+			let content = std::fs::read_to_string(path)
+				.map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+			toml::from_str(&content).map_err(|e| format!("{}: {e}", path.display()))
+		},
+		None => toml::from_str(RESOURCE_TABLE_STR).map_err(|e| e.to_string()),
+	}
+}
+
+/// Validation of the asset configuration
+pub trait ValidateAssets {
+	/// Checks that all `required` assets are present and drawable
+	///
+	/// Reports the problems of all assets at once.
+	fn validate(&self, required: &[&str]) -> Result<(), Vec<String>>;
+}
+
+impl ValidateAssets for AssetConfig {
+	fn validate(&self, required: &[&str]) -> Result<(), Vec<String>> {
+		validate_assets(required, |name| AssetParams::from_config(self, name))
+	}
+}
+
+/// Checks the parameters of all `required` assets as given by `lookup`
+fn validate_assets(
+	required: &[&str],
+	lookup: impl Fn(&str) -> Option<AssetParams>,
+) -> Result<(), Vec<String>> {
+	let problems = Vec::from_iter(required.iter().filter_map(|&name| {
+		match lookup(name) {
+			None => Some(format!("{name}: missing in the asset config")),
+			Some(params) => params.check().err().map(|p| format!("{name}: {p}")),
+		}
+	}));
+
+	if problems.is_empty() {
+		Ok(())
+	} else {
+		Err(problems)
+	}
+}
+
+/// Safe drawing of sprite batches
+pub trait DrawIfNonEmpty {
+	/// Draws this sprite batch, unless it is empty.
+	///
+	/// For some ridiculous reason, empty sprite batches cause sever glitches (UB-like) on windows.
+	/// Thus, sprite batches should never be drawn directly, but only via this method.
+	///
+	/// Returns whether anything was drawn.
+	fn draw_if_nonempty(
+		&self,
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::Context,
+		param: impl Into<DrawParam>,
+	) -> GameResult<bool>;
+}
+
+impl DrawIfNonEmpty for SpriteBatch {
+	fn draw_if_nonempty(
+		&self,
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::Context,
+		param: impl Into<DrawParam>,
+	) -> GameResult<bool> {
+		if self.get_sprites().is_empty() {
+			Ok(false)
+		} else {
+			gwg::graphics::draw(ctx, quad_ctx, self, param)?;
+			Ok(true)
+		}
+	}
 }
 
 /// Dispatch the draw calls of all given sprite batches and clears them
@@ -242,10 +431,7 @@ pub fn draw_and_clear<'a>(
 	batches: impl IntoIterator<Item = &'a mut SpriteBatch>,
 ) -> GameResult<()> {
 	for batch in batches {
-		// For some ridiculous reason, empty sprite batches cause sever glitches (UB-like) on windows.
-		// Thus we will only draw those that aren't empty.
-		if !batch.get_sprites().is_empty() {
-			gwg::graphics::draw(ctx, quad_ctx, batch, (Point2::new(0.0, 0.0),))?;
+		if batch.draw_if_nonempty(ctx, quad_ctx, (Point2::new(0.0, 0.0),))? {
 			batch.clear();
 		}
 	}
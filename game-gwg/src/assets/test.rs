@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use logic::resource::ResourceTable;
+use logic::state::ShipHull;
+use strum::IntoEnumIterator;
+
+use super::asset_batch::AssetParams;
+use super::asset_name::hull_key;
+use super::load_resource_table;
+use super::validate_assets;
+
+const PARAMS: AssetParams = AssetParams {
+	z_local_frames: 1,
+	z_frames: 16,
+	x_frames: 4,
+	width: 64,
+	height: 64,
+};
+
+#[test]
+fn complete_config_is_valid() {
+	// Arrange
+	let config = HashMap::from([("ship-00", PARAMS), ("ship-01", PARAMS)]);
+
+	// Act
+	let result = validate_assets(&["ship-00", "ship-01"], |name| config.get(name).copied());
+
+	// Assert
+	assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn report_all_missing_assets() {
+	// Arrange
+	let config = HashMap::from([("ship-00", PARAMS)]);
+
+	// Act
+	let result = validate_assets(&["ship-00", "ship-01", "sail-02-3"], |name| {
+		config.get(name).copied()
+	});
+
+	// Assert
+	let problems = result.unwrap_err();
+	assert_eq!(problems.len(), 2);
+	assert!(problems[0].starts_with("ship-01"));
+	assert!(problems[1].starts_with("sail-02-3"));
+}
+
+#[test]
+fn report_assets_without_frames() {
+	// Arrange
+	let broken = AssetParams {
+		z_frames: 0,
+		..PARAMS
+	};
+	let config = HashMap::from([("fish-00", broken)]);
+
+	// Act
+	let result = validate_assets(&["fish-00"], |name| config.get(name).copied());
+
+	// Assert
+	let problems = result.unwrap_err();
+	assert_eq!(problems.len(), 1);
+	assert!(problems[0].starts_with("fish-00"));
+}
+
+#[test]
+fn hull_keys_follow_the_upgrade_order() {
+	// Act
+	let keys = Vec::from_iter(ShipHull::iter().map(hull_key));
+
+	// Assert
+	assert_eq!(keys, ["ship-00", "ship-01"]);
+}
+
+#[test]
+fn packaged_resource_table_matches_the_built_in_one() {
+	// Act
+	let table = load_resource_table(None);
+
+	// Assert
+	assert_eq!(table, Ok(ResourceTable::default()));
+}
+
+#[test]
+fn missing_resource_table_file_is_an_error() {
+	// Act
+	let table = load_resource_table(Some(Path::new("does/not/exist.toml")));
+
+	// Assert
+	let err = table.unwrap_err();
+	assert!(err.contains("does/not/exist.toml"), "{err}");
+}
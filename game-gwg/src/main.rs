@@ -10,6 +10,8 @@ use gwg::graphics::Rect;
 use gwg::graphics::Text;
 use gwg::GameResult;
 use lazy_static::lazy_static;
+use logic::state::Player;
+use logic::state::SailKind;
 use logic::DebuggingConf;
 use structopt::StructOpt;
 
@@ -51,6 +53,11 @@ struct Opts {
 	#[structopt(long)]
 	money_cheat: Option<u64>,
 
+	/// Draw a graph of the last frame times
+	#[cfg(feature = "dev")]
+	#[structopt(long)]
+	frame_graph: bool,
+
 	/// Disables all sounds and music.
 	#[structopt(short, long)]
 	muted: bool,
@@ -59,17 +66,108 @@ struct Opts {
 	#[structopt(short = "s", long, default_value = "32")]
 	map_size: u16,
 
+	/// Animate fish farther away than the given distance in meter less often,
+	/// improving the performance on big maps
+	#[structopt(long)]
+	fish_lod: Option<f32>,
+
 	/// Start the game in window modus
 	#[structopt(short, long)]
 	windowed: bool,
 
+	/// Scales the texts and elements of the HUD, e.g. `1.5` on high-DPI screens
+	///
+	/// The scale is clamped to the range from `0.5` to `4.0`, and kept for the
+	/// next runs.
+	#[structopt(long)]
+	ui_scale: Option<f32>,
+
+	/// Draw the HUD texts on solid backgrounds, making them easier to read
+	#[structopt(long)]
+	high_contrast: bool,
+
+	/// Sets the background behind the world, either `sky` or `rainbow`
+	#[structopt(long, default_value = "sky")]
+	background: Background,
+
+	/// Do not darken the screen edges, which otherwise intensifies during storms
+	#[structopt(long)]
+	no_vignette: bool,
+
 	/// Start the game directly, skipping the main menu
 	#[structopt(long)]
 	start: bool,
 
+	/// Play the tutorial voyage, which is otherwise only offered on the first run
+	#[structopt(long)]
+	tutorial: bool,
+
+	/// Race through the given number of checkpoints against the clock
+	#[structopt(long)]
+	race: Option<usize>,
+
+	/// Take turns at the helm with the given number of players on this device
+	#[structopt(long)]
+	hotseat: Option<usize>,
+
 	/// Use a fixed game world seed
 	#[structopt(long)]
 	seed: Option<String>,
+
+	/// Generate a wintry world, with ice floes slowing down the ship
+	#[structopt(long)]
+	icy: bool,
+
+	/// Overrides the friction of the ship moving forward, the default is 0.1
+	#[structopt(long)]
+	ground_friction: Option<f32>,
+
+	/// Overrides the friction of the ship drifting sideways, the default is 0.8
+	#[structopt(long)]
+	cross_friction: Option<f32>,
+
+	/// Let the auto-reef assist set the sails for the best speed
+	///
+	/// Overrides the choice in the main menu, which is kept otherwise.
+	#[structopt(long)]
+	auto_reef: bool,
+
+	/// Sets the difficulty preset of a new game, either `easy` or `hard`
+	#[structopt(short, long, default_value = "hard")]
+	difficulty: Difficulty,
+
+	/// Record the trajectory of the ship into the given file, saved when leaving the game
+	#[cfg(not(target_family = "wasm"))]
+	#[structopt(long)]
+	record: Option<std::path::PathBuf>,
+
+	/// Race against a ghost ship following the trajectory recorded in the given file
+	#[cfg(not(target_family = "wasm"))]
+	#[structopt(long)]
+	ghost: Option<std::path::PathBuf>,
+
+	/// Export the best run into the given ghost file for sharing, i.e. the fastest finished race,
+	/// or the voyage earning the most money outside of races
+	#[cfg(not(target_family = "wasm"))]
+	#[structopt(long)]
+	export_ghost: Option<std::path::PathBuf>,
+
+	/// Write the position, speed, wind, money, and cargo as a JSON line each second into the
+	/// given file, or to the stdout for `-`
+	#[cfg(not(target_family = "wasm"))]
+	#[structopt(long)]
+	telemetry: Option<std::path::PathBuf>,
+
+	/// Read the stats of the resource types from the given file instead of the packaged
+	/// `resources.toml`, for balancing
+	#[cfg(not(target_family = "wasm"))]
+	#[structopt(long)]
+	resource_table: Option<std::path::PathBuf>,
+
+	/// Extract the assets into the user cache directory and load them from there
+	#[cfg(not(target_family = "wasm"))]
+	#[structopt(long)]
+	asset_cache: bool,
 }
 impl Opts {
 	fn to_debugging_conf(&self) -> logic::DebuggingConf {
@@ -89,26 +187,94 @@ impl Opts {
 	}
 }
 
+/// The difficulty presets for a new game
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+enum Difficulty {
+	/// Start with a better sail and some money
+	Easy,
+	/// Start with the basic ship and no money
+	Hard,
+}
+impl Difficulty {
+	/// The other difficulty preset, for toggling in the menu
+	fn toggle(self) -> Self {
+		match self {
+			Self::Easy => Self::Hard,
+			Self::Hard => Self::Easy,
+		}
+	}
+
+	/// Applies this preset to the freshly generated `player`
+	fn apply(self, player: &mut Player) {
+		match self {
+			Self::Easy => {
+				player.vehicle.sail.kind = SailKind::Bermuda;
+				player.money = 500;
+			},
+			Self::Hard => {
+				// The defaults are hard enough
+			},
+		}
+	}
+}
+
+/// The backgrounds drawn behind the world
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+enum Background {
+	/// A sky gradient turning grey during storms
+	Sky,
+	/// An animated rainbow color
+	Rainbow,
+}
+
 lazy_static! {
 	static ref OPTIONS: Opts = Opts::from_args();
 }
 
+/// The packaged assets
+const ASSETS_TAR: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/assets.tar"));
+
 fn main() -> gwg::GameResult {
 	println!("--- [main] entered");
 
 	let opts = &*OPTIONS;
 
-	gwg::start(
-		gwg::conf::Conf::default()
-			.window_title("Plenty of fish in the sea".into())
-			.window_resizable(true)
-			.fullscreen(!opts.windowed)
-			.cache(Some(include_bytes!(concat!(
-				env!("OUT_DIR"),
-				"/assets.tar"
-			)))),
-		|context, quad_ctx| Box::new(scenes::create_stack(context, quad_ctx)),
-	)
+	let conf = gwg::conf::Conf::default()
+		.window_title("Plenty of fish in the sea".into())
+		.window_resizable(true)
+		.fullscreen(!opts.windowed);
+
+	let conf = {
+		cfg_if! {
+			if #[cfg(not(target_family = "wasm"))] {
+				// The cache is keyed by the build, so a new build never sees stale assets
+				let version = format!(
+					"{}-{}",
+					built_info::PKG_VERSION,
+					built_info::GIT_COMMIT_HASH.unwrap_or("unknown")
+				);
+				let cached = opts
+					.asset_cache
+					.then(|| assets::cache::extracted_assets(ASSETS_TAR, &version))
+					.flatten();
+
+				match cached {
+					Some(dir) => conf.physical_root_dir(Some(dir)),
+					None => conf.cache(Some(ASSETS_TAR)),
+				}
+			} else {
+				conf.cache(Some(ASSETS_TAR))
+			}
+		}
+	};
+
+	gwg::start(conf, |context, quad_ctx| {
+		Box::new(scenes::create_stack(context, quad_ctx))
+	})
 }
 
 /// Draw the built version information
@@ -1,10 +1,18 @@
+use good_web_game::graphics::Rect;
+
 #[cfg(test)]
 mod test;
 
+/// The leeway for rounding errors, when checking whether a point lies on a segment
+const SEGMENT_LEEWAY: f32 = 1e-3;
+
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Default)]
 pub struct Line(pub nalgebra::Point2<f32>, pub nalgebra::Point2<f32>);
 
 impl Line {
+	/// Intersects the infinite lines through both pairs of points
+	///
+	/// Returns `None` if the lines are parallel.
 	pub fn intersect(&self, other: &Self) -> Option<nalgebra::Point2<f32>> {
 		let p1 = self.0;
 		let p2 = self.1;
@@ -26,4 +34,76 @@ impl Line {
 
 		(x.is_finite() && y.is_finite()).then(|| nalgebra::Point2::new(x, y))
 	}
+
+	/// Intersects the segments between both pairs of points
+	///
+	/// Returns `None` if the segments do not touch, or if they are parallel,
+	/// even if they are collinear and overlap.
+	pub fn intersect_segments(&self, other: &Self) -> Option<nalgebra::Point2<f32>> {
+		let dir = self.1 - self.0;
+		let other_dir = other.1 - other.0;
+
+		let cross = dir.x * other_dir.y - dir.y * other_dir.x;
+		if cross.abs() <= f32::EPSILON * dir.norm() * other_dir.norm() {
+			return None;
+		}
+
+		let point = self.intersect(other)?;
+		(self.bounds_contain(point) && other.bounds_contain(point)).then_some(point)
+	}
+
+	/// Whether the point lies within the bounding box of the segment
+	fn bounds_contain(&self, point: nalgebra::Point2<f32>) -> bool {
+		let min = self
+			.0
+			.coords
+			.inf(&self.1.coords)
+			.add_scalar(-SEGMENT_LEEWAY);
+		let max = self.0.coords.sup(&self.1.coords).add_scalar(SEGMENT_LEEWAY);
+
+		(min.x..=max.x).contains(&point.x) && (min.y..=max.y).contains(&point.y)
+	}
+}
+
+/// Gives the point where the ray from `origin` in the direction `dir` leaves the rectangle
+///
+/// The `origin` has to be within the rectangle. Unlike intersecting lines,
+/// this never gives a point behind the origin. Returns `None` for a zero `dir`.
+pub fn ray_exit(
+	rect: Rect,
+	origin: nalgebra::Point2<f32>,
+	dir: nalgebra::Vector2<f32>,
+) -> Option<nalgebra::Point2<f32>> {
+	if dir == nalgebra::Vector2::zeros() {
+		return None;
+	}
+
+	// A segment long enough to leave the rectangle from anywhere within it
+	let ray = Line(origin, origin + dir.normalize() * (rect.w + rect.h));
+
+	// The ray leaves through one of the two edges facing its direction,
+	// so the other two need no check
+	let (left, right) = (rect.x, rect.x + rect.w);
+	let (top, bottom) = (rect.y, rect.y + rect.h);
+	let edge_y = if dir.y > 0.0 { bottom } else { top };
+	let edge_x = if dir.x > 0.0 { right } else { left };
+	let edges = [
+		Line(
+			nalgebra::Point2::new(left, edge_y),
+			nalgebra::Point2::new(right, edge_y),
+		),
+		Line(
+			nalgebra::Point2::new(edge_x, top),
+			nalgebra::Point2::new(edge_x, bottom),
+		),
+	];
+
+	edges
+		.iter()
+		.filter_map(|edge| ray.intersect_segments(edge))
+		.min_by(|a, b| {
+			let dst_a = nalgebra::distance_squared(&origin, a);
+			let dst_b = nalgebra::distance_squared(&origin, b);
+			dst_a.partial_cmp(&dst_b).unwrap()
+		})
 }
@@ -1,5 +1,7 @@
+use good_web_game::graphics::Rect;
 use nalgebra as ng;
 
+use super::ray_exit;
 use super::Line;
 
 const fn p(x: f32, y: f32) -> ng::Point2<f32> {
@@ -80,3 +82,129 @@ fn intersect_lines_right() {
 	assert!(actual.is_some());
 	assert!(logic::glm::distance(&actual.unwrap().coords, &expected.coords) < f32::EPSILON);
 }
+
+#[test]
+fn intersect_segments_crossing() {
+	// Arrange
+	let a = Line(p(0.0, 0.0), p(800.0, 600.0));
+	let b = Line(p(0.0, 600.0), p(800.0, 0.0));
+
+	// Act
+	let actual = a.intersect_segments(&b);
+
+	// Assert
+	let expected = p(400.0, 300.0);
+	assert!(actual.is_some());
+	assert!(logic::glm::distance(&actual.unwrap().coords, &expected.coords) < 1e-3);
+}
+
+#[test]
+fn intersect_segments_parallel() {
+	// Arrange
+	let a = Line(p(0.0, 0.0), p(800.0, 0.0));
+	let b = Line(p(0.0, 600.0), p(800.0, 600.0));
+
+	// Act & Assert
+	assert_eq!(a.intersect_segments(&b), None);
+}
+
+#[test]
+fn intersect_segments_collinear() {
+	// Arrange
+	let a = Line(p(0.0, 0.0), p(400.0, 300.0));
+	let b = Line(p(200.0, 150.0), p(800.0, 600.0));
+
+	// Act & Assert
+	assert_eq!(a.intersect_segments(&b), None);
+}
+
+#[test]
+fn intersect_segments_t_junction() {
+	// Arrange
+	let a = Line(p(0.0, 600.0), p(800.0, 600.0));
+	let b = Line(p(400.0, 300.0), p(400.0, 600.0));
+
+	// Act
+	let actual = a.intersect_segments(&b);
+
+	// Assert
+	let expected = p(400.0, 600.0);
+	assert!(actual.is_some());
+	assert!(logic::glm::distance(&actual.unwrap().coords, &expected.coords) < f32::EPSILON);
+}
+
+#[test]
+fn intersect_segments_apart() {
+	// Arrange
+	let a = Line(p(0.0, 600.0), p(800.0, 600.0));
+	let b = Line(p(400.0, 300.0), p(400.0, 500.0));
+
+	// Act
+	let lines = a.intersect(&b);
+	let segments = a.intersect_segments(&b);
+
+	// Assert
+	assert!(lines.is_some());
+	assert_eq!(segments, None);
+}
+
+/// A screen of 800x600 pixels
+const SCREEN: Rect = Rect {
+	x: 0.0,
+	y: 0.0,
+	w: 800.0,
+	h: 600.0,
+};
+
+#[test]
+fn ray_exits_in_its_direction() {
+	// Arrange
+	let center = p(400.0, 300.0);
+
+	// Act
+	let actual = ray_exit(SCREEN, center, ng::Vector2::new(1.0, 0.5));
+
+	// Assert
+	let expected = p(800.0, 500.0);
+	assert!(actual.is_some());
+	assert!(logic::glm::distance(&actual.unwrap().coords, &expected.coords) < 1e-3);
+}
+
+#[test]
+fn ray_exits_behind_the_camera() {
+	// Arrange
+	let center = p(400.0, 300.0);
+	// A harbor far behind, i.e. to the left, of a camera looking right
+	let harbor = p(-5000.0, 300.0);
+
+	// Act
+	let actual = ray_exit(SCREEN, center, harbor - center);
+
+	// Assert
+	let expected = p(0.0, 300.0);
+	assert!(actual.is_some());
+	assert!(logic::glm::distance(&actual.unwrap().coords, &expected.coords) < 1e-3);
+}
+
+#[test]
+fn ray_exits_through_a_corner() {
+	// Arrange
+	let center = p(400.0, 300.0);
+
+	// Act
+	let actual = ray_exit(SCREEN, center, ng::Vector2::new(-4.0, -3.0));
+
+	// Assert
+	let expected = p(0.0, 0.0);
+	assert!(actual.is_some());
+	assert!(logic::glm::distance(&actual.unwrap().coords, &expected.coords) < 1e-3);
+}
+
+#[test]
+fn ray_without_direction_exits_nowhere() {
+	// Act & Assert
+	assert_eq!(
+		ray_exit(SCREEN, p(400.0, 300.0), ng::Vector2::zeros()),
+		None
+	);
+}
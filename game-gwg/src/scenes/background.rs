@@ -0,0 +1,133 @@
+//! The atmospheric background of the game view
+//!
+//! The sky gradient is drawn first, so it shines through wherever the world
+//! is not drawn, while the vignette darkens the screen edges above the world.
+//!
+
+use good_web_game as gwg;
+use gwg::graphics;
+use gwg::graphics::Color;
+use gwg::graphics::DrawMode;
+use gwg::graphics::MeshBuilder;
+use gwg::graphics::Rect;
+use gwg::GameResult;
+use logic::units::Wind;
+use logic::MAX_WIND_SPEED;
+use nalgebra::Point2;
+
+use super::in_game::Mix;
+use crate::Background;
+
+
+/// The number of horizontal bands making up the sky gradient
+const SKY_BANDS: u16 = 16;
+const SKY_TOP_COLOR: Color = Color::new(0.35, 0.6, 0.9, 1.0);
+const SKY_HORIZON_COLOR: Color = Color::new(0.75, 0.88, 0.95, 1.0);
+/// The color the sky turns into during a storm
+const SKY_STORM_COLOR: Color = Color::new(0.3, 0.33, 0.38, 1.0);
+
+/// The number of rings making up the vignette
+const VIGNETTE_RINGS: u16 = 12;
+/// The width of the vignette relative to the smaller screen dimension
+const VIGNETTE_WIDTH: f32 = 0.2;
+/// The opacity of the vignette at the screen edges in calm weather
+const VIGNETTE_CALM_ALPHA: f32 = 0.15;
+/// The opacity of the vignette at the screen edges in the strongest storm
+const VIGNETTE_STORM_ALPHA: f32 = 0.6;
+const VIGNETTE_COLOR: Color = Color::new(0.02, 0.03, 0.08, 1.0);
+
+/// The wind speed in m/s, from which on the weather turns stormy
+const STORM_MIN_SPEED: f32 = 8.0;
+
+
+/// How stormy the weather is, in range [0,1]
+///
+/// It is zero up to a fresh breeze, and one at the strongest possible wind.
+pub fn storm_intensity(wind: Wind) -> f32 {
+	((wind.magnitude() - STORM_MIN_SPEED) / (MAX_WIND_SPEED - STORM_MIN_SPEED)).clamp(0.0, 1.0)
+}
+
+/// Clears the screen with the background, as configured by `style`
+pub fn draw_sky(
+	ctx: &mut gwg::Context,
+	quad_ctx: &mut gwg::miniquad::Context,
+	style: Background,
+	wind: Wind,
+) -> GameResult<()> {
+	match style {
+		Background::Sky => {
+			let storm = storm_intensity(wind);
+			let top = SKY_TOP_COLOR.mix(&SKY_STORM_COLOR, storm);
+			let horizon = SKY_HORIZON_COLOR.mix(&SKY_STORM_COLOR, storm);
+			graphics::clear(ctx, quad_ctx, top);
+
+			// From the top of the screen down to the horizon at its bottom
+			let screen = graphics::screen_coordinates(ctx);
+			let band_height = screen.h / f32::from(SKY_BANDS);
+			let mut mb = MeshBuilder::new();
+			for i in 0..SKY_BANDS {
+				let t = (f32::from(i) + 0.5) / f32::from(SKY_BANDS);
+				mb.rectangle(
+					DrawMode::fill(),
+					Rect::new(
+						screen.x,
+						screen.y + f32::from(i) * band_height,
+						screen.w,
+						// Overlap a bit to avoid gaps between the bands
+						band_height + 1.,
+					),
+					top.mix(&horizon, t),
+				)?;
+			}
+			let mesh = mb.build(ctx, quad_ctx)?;
+			graphics::draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+		},
+		Background::Rainbow => {
+			let elapsed = gwg::timer::time_since_start(ctx).as_secs_f32();
+			let red = elapsed.sin() * 0.5 + 0.5;
+			let green = (1.3 + elapsed + 0.3).sin() * 0.5 + 0.5;
+			let blue = (1.13 * elapsed + 0.7).sin() * 0.5 + 0.5;
+			graphics::clear(ctx, quad_ctx, [red, green, blue, 1.0].into());
+		},
+	}
+
+	Ok(())
+}
+
+/// Darkens the screen edges, the more the stormier the weather is
+pub fn draw_vignette(
+	ctx: &mut gwg::Context,
+	quad_ctx: &mut gwg::miniquad::Context,
+	wind: Wind,
+) -> GameResult<()> {
+	let storm = storm_intensity(wind);
+	let edge_alpha = VIGNETTE_CALM_ALPHA + (VIGNETTE_STORM_ALPHA - VIGNETTE_CALM_ALPHA) * storm;
+
+	let screen = graphics::screen_coordinates(ctx);
+	let ring_width = screen.w.min(screen.h) * VIGNETTE_WIDTH / f32::from(VIGNETTE_RINGS);
+
+	// The rings get fainter towards the screen center
+	let mut mb = MeshBuilder::new();
+	for i in 0..VIGNETTE_RINGS {
+		let inset = (f32::from(i) + 0.5) * ring_width;
+		let fade = 1.0 - f32::from(i) / f32::from(VIGNETTE_RINGS);
+		let color = Color {
+			a: edge_alpha * fade * fade,
+			..VIGNETTE_COLOR
+		};
+		mb.rectangle(
+			DrawMode::stroke(ring_width),
+			Rect::new(
+				screen.x + inset,
+				screen.y + inset,
+				screen.w - 2. * inset,
+				screen.h - 2. * inset,
+			),
+			color,
+		)?;
+	}
+	let mesh = mb.build(ctx, quad_ctx)?;
+	graphics::draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+
+	Ok(())
+}
@@ -0,0 +1,78 @@
+//! The favorite fishing spots of the player
+//!
+
+#[cfg(test)]
+mod test;
+
+use std::collections::BTreeMap;
+
+use logic::terrain::Terrain;
+use logic::units::Location;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::profile;
+
+
+/// The distance in meter within which bookmarking removes an existing bookmark instead
+const BOOKMARK_RADIUS: f32 = 20.0;
+
+
+/// The bookmarked spots, persisted in the profile of the player
+///
+/// Since the spots only make sense on the map they were found on, they are
+/// kept per world seed.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Bookmarks {
+	/// The spots by world seed, as decimal string, because TOML keys are strings
+	worlds: BTreeMap<String, Vec<Location>>,
+}
+
+impl Bookmarks {
+	/// The spots bookmarked on the world of the given `seed`, oldest first
+	pub fn spots(&self, seed: u64) -> &[Location] {
+		self.worlds
+			.get(&seed.to_string())
+			.map(Vec::as_slice)
+			.unwrap_or_default()
+	}
+
+	/// Bookmarks the spot at `loc`, or removes the bookmark nearby
+	///
+	/// Returns whether a bookmark was added. The change is not stored in the
+	/// profile, see [Self::save].
+	pub fn toggle(&mut self, seed: u64, terrain: &Terrain, loc: Location) -> bool {
+		let spots = self.worlds.entry(seed.to_string()).or_default();
+		let nearby = spots
+			.iter()
+			.position(|&spot| terrain.torus_distance(spot, loc).magnitude() < BOOKMARK_RADIUS);
+
+		let added = match nearby {
+			Some(idx) => {
+				spots.remove(idx);
+				false
+			},
+			None => {
+				spots.push(loc);
+				true
+			},
+		};
+		if spots.is_empty() {
+			self.worlds.remove(&seed.to_string());
+		}
+
+		added
+	}
+
+	/// Loads the bookmarks from the profile, or starts without any
+	pub fn load() -> Self {
+		profile::load("bookmarks")
+	}
+
+	/// Stores the bookmarks in the profile
+	pub fn save(&self) {
+		profile::save("bookmarks", self)
+	}
+}
@@ -0,0 +1,69 @@
+use logic::terrain::Terrain;
+use logic::units::Location;
+
+use super::Bookmarks;
+
+#[test]
+fn toggle_adds_a_spot() {
+	// Arrange
+	let terrain = Terrain::new(64);
+	let mut bookmarks = Bookmarks::default();
+
+	// Act
+	let added = bookmarks.toggle(42, &terrain, Location::new(10.0, 10.0));
+
+	// Assert
+	assert!(added);
+	assert_eq!(bookmarks.spots(42), &[Location::new(10.0, 10.0)]);
+}
+
+#[test]
+fn toggle_nearby_removes_the_spot() {
+	// Arrange
+	let terrain = Terrain::new(64);
+	let mut bookmarks = Bookmarks::default();
+	bookmarks.toggle(42, &terrain, Location::new(10.0, 10.0));
+
+	// Act
+	let added = bookmarks.toggle(42, &terrain, Location::new(15.0, 12.0));
+
+	// Assert
+	assert!(!added);
+	assert!(bookmarks.spots(42).is_empty());
+	assert_eq!(bookmarks, Bookmarks::default());
+}
+
+#[test]
+fn toggle_far_away_adds_another_spot() {
+	// Arrange
+	let terrain = Terrain::new(64);
+	let mut bookmarks = Bookmarks::default();
+	bookmarks.toggle(42, &terrain, Location::new(10.0, 10.0));
+
+	// Act
+	let added = bookmarks.toggle(42, &terrain, Location::new(100.0, 10.0));
+
+	// Assert
+	assert!(added);
+	assert_eq!(
+		bookmarks.spots(42),
+		&[Location::new(10.0, 10.0), Location::new(100.0, 10.0)]
+	);
+}
+
+#[test]
+fn spots_are_kept_per_world() {
+	// Arrange
+	let terrain = Terrain::new(64);
+	let mut bookmarks = Bookmarks::default();
+
+	// Act
+	bookmarks.toggle(1, &terrain, Location::new(10.0, 10.0));
+	let added = bookmarks.toggle(2, &terrain, Location::new(10.0, 10.0));
+
+	// Assert
+	assert!(added);
+	assert_eq!(bookmarks.spots(1), &[Location::new(10.0, 10.0)]);
+	assert_eq!(bookmarks.spots(2), &[Location::new(10.0, 10.0)]);
+	assert!(bookmarks.spots(3).is_empty());
+}
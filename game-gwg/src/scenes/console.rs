@@ -0,0 +1,205 @@
+//! The in-game cheat console of development builds
+//!
+//! Commands are typed in, one per line, for instance `money 5000`,
+//! `teleport 100 200`, `sail schooner`, `hull bigger`, or `spawn fish0 10`.
+//!
+//! The debugging configuration is edited with `engine on`, `turning off`,
+//! `wind 1.5` (radians), or `wind free`. It is part of the world, thus it is
+//! kept in the saves of it.
+//!
+
+use std::f32::consts::TAU;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use good_web_game::miniquad::KeyCode;
+use logic::resource::ResourcePack;
+use logic::resource::ResourcePackContent;
+use logic::state::SailKind;
+use logic::state::ShipHull;
+use logic::units::Location;
+use logic::World;
+use strum::IntoEnumIterator;
+
+
+#[cfg(test)]
+mod test;
+
+
+/// The distance in meters from the ship, at which spawned resources are placed
+const SPAWN_DISTANCE: f32 = 5.0;
+
+
+/// A parsed console command
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Command {
+	/// Sets the money of the player
+	Money(u64),
+	/// Moves the ship to the given location in meters
+	Teleport(f32, f32),
+	/// Replaces the sail of the ship
+	Sail(SailKind),
+	/// Replaces the hull of the ship
+	Hull(ShipHull),
+	/// Places the given amount of resources around the ship
+	Spawn(ResourcePackContent, usize),
+	/// Turns the ship engine cheat on or off
+	Engine(bool),
+	/// Turns the turning wind on or off
+	TurningWind(bool),
+	/// Fixes the wind to the given direction in radians, or releases it
+	FixedWind(Option<f32>),
+}
+
+impl Command {
+	/// Applies this command to the `world`, giving a feedback message
+	pub fn apply(self, world: &mut World) -> String {
+		let World {
+			init,
+			state,
+		} = world;
+		let vehicle = &mut state.player.vehicle;
+
+		match self {
+			Self::Money(money) => {
+				state.player.money = money;
+				format!("Money: {money}")
+			},
+			Self::Teleport(x, y) => {
+				let pos = init.terrain.map_loc_on_torus(Location::new(x, y));
+				vehicle.teleport(pos, vehicle.heading);
+				vehicle.velocity = Default::default();
+				format!("Teleported to {:.0}, {:.0}", pos.0.x, pos.0.y)
+			},
+			Self::Sail(kind) => {
+				vehicle.sail.kind = kind;
+				format!("Sail: {kind:?}")
+			},
+			Self::Hull(hull) => {
+				vehicle.hull = hull;
+				format!("Hull: {hull:?}")
+			},
+			Self::Spawn(kind, count) => {
+				let center = vehicle.pos;
+				let mut rng = state.rng_for_tick(init);
+				for i in 0..count {
+					let angle = TAU * i as f32 / count as f32;
+					let offset =
+						logic::glm::rotate_vec2(&logic::glm::vec2(SPAWN_DISTANCE, 0.0), angle);
+					let loc = init.terrain.map_loc_on_torus(Location(center.0 + offset));
+
+					let mut pack =
+						ResourcePack::new(&init.terrain_setting.resources, loc, kind, &mut rng);
+					pack.update(state.timestamp);
+					state.resources.push(pack);
+				}
+				format!("Spawned {count} {kind:?}")
+			},
+			Self::Engine(on) => {
+				init.dbg.ship_engine = on;
+				format!("Engine: {on}")
+			},
+			Self::TurningWind(on) => {
+				init.dbg.wind_turning = on;
+				format!("Turning wind: {on}")
+			},
+			Self::FixedWind(direction) => {
+				init.dbg.fixed_wind_direction = direction;
+				format!("Fixed wind: {direction:?}")
+			},
+		}
+	}
+}
+
+impl FromStr for Command {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut words = s.split_whitespace();
+		let name = words.next().ok_or_else(|| "Empty command".to_owned())?;
+		let args: Vec<&str> = words.collect();
+
+		let cmd = match (name.to_lowercase().as_str(), args.as_slice()) {
+			("money", [money]) => Self::Money(parse_number(money)?),
+			("teleport", [x, y]) => Self::Teleport(parse_finite(x)?, parse_finite(y)?),
+			("sail", [kind]) => Self::Sail(parse_variant(kind)?),
+			("hull", [hull]) => Self::Hull(parse_variant(hull)?),
+			("spawn", [kind]) => Self::Spawn(parse_variant(kind)?, 1),
+			("spawn", [kind, count]) => Self::Spawn(parse_variant(kind)?, parse_number(count)?),
+			("engine", [on]) => Self::Engine(parse_switch(on)?),
+			("turning", [on]) => Self::TurningWind(parse_switch(on)?),
+			("wind", ["free"]) => Self::FixedWind(None),
+			("wind", [direction]) => Self::FixedWind(Some(parse_finite(direction)?)),
+			(
+				"money" | "teleport" | "sail" | "hull" | "spawn" | "engine" | "turning" | "wind",
+				_,
+			) => {
+				return Err(format!("Wrong arguments for {name}"));
+			},
+			_ => return Err(format!("Unknown command: {name}")),
+		};
+
+		Ok(cmd)
+	}
+}
+
+fn parse_number<T: FromStr>(s: &str) -> Result<T, String> {
+	s.parse().map_err(|_| format!("Not a number: {s}"))
+}
+
+/// Parses a float, rejecting `nan` and `inf`, which would break the world
+fn parse_finite(s: &str) -> Result<f32, String> {
+	let number: f32 = parse_number(s)?;
+	if number.is_finite() {
+		Ok(number)
+	} else {
+		Err(format!("Not a finite number: {s}"))
+	}
+}
+
+/// Parses `on` or `off`, ignoring the case
+fn parse_switch(s: &str) -> Result<bool, String> {
+	match s.to_lowercase().as_str() {
+		"on" => Ok(true),
+		"off" => Ok(false),
+		_ => Err(format!("Neither on nor off: {s}")),
+	}
+}
+
+/// Finds the variant whose name matches `s`, ignoring the case
+fn parse_variant<T: IntoEnumIterator + Debug>(s: &str) -> Result<T, String> {
+	T::iter()
+		.find(|v| format!("{v:?}").eq_ignore_ascii_case(s))
+		.ok_or_else(|| format!("Unknown name: {s}"))
+}
+
+
+/// The command line being typed into the console
+#[derive(Debug, Clone, Default)]
+pub struct Console {
+	pub input: String,
+}
+
+impl Console {
+	/// Handles a key press, returning the command line once it is entered
+	///
+	/// Only entering and deleting are handled here, the characters are typed
+	/// in via [Self::text_input].
+	pub fn key_down(&mut self, keycode: KeyCode) -> Option<String> {
+		match keycode {
+			KeyCode::Enter | KeyCode::KpEnter => Some(std::mem::take(&mut self.input)),
+			KeyCode::Backspace => {
+				self.input.pop();
+				None
+			},
+			_ => None,
+		}
+	}
+
+	/// Types in the given character, as given by the keyboard layout
+	pub fn text_input(&mut self, character: char) {
+		if !character.is_control() {
+			self.input.push(character);
+		}
+	}
+}
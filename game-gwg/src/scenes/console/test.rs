@@ -0,0 +1,94 @@
+use good_web_game::miniquad::KeyCode;
+use logic::resource::ResourcePackContent;
+use logic::state::SailKind;
+
+use super::Command;
+use super::Console;
+
+#[test]
+fn parse_commands() {
+	// Arrange
+	let lines = [
+		"money 5000",
+		"teleport 10 -2.5",
+		"sail schooner",
+		"spawn fish0 10",
+		"engine on",
+		"turning OFF",
+		"wind 1.5",
+		"wind free",
+	];
+
+	// Act
+	let actual: Vec<_> = lines.iter().map(|l| l.parse::<Command>()).collect();
+
+	// Assert
+	let expected = vec![
+		Ok(Command::Money(5000)),
+		Ok(Command::Teleport(10.0, -2.5)),
+		Ok(Command::Sail(SailKind::Schooner)),
+		Ok(Command::Spawn(ResourcePackContent::Fish0, 10)),
+		Ok(Command::Engine(true)),
+		Ok(Command::TurningWind(false)),
+		Ok(Command::FixedWind(Some(1.5))),
+		Ok(Command::FixedWind(None)),
+	];
+	assert_eq!(actual, expected);
+}
+
+#[test]
+fn parse_ignores_case_and_spaces() {
+	// Act
+	let actual = "  Spawn   DRIFTWOOD ".parse::<Command>();
+
+	// Assert
+	assert_eq!(
+		actual,
+		Ok(Command::Spawn(ResourcePackContent::Driftwood, 1))
+	);
+}
+
+#[test]
+fn parse_rejects_invalid_commands() {
+	// Arrange
+	let lines = [
+		"",
+		"fly 1 2",
+		"money",
+		"money lots",
+		"sail raft",
+		"teleport 1",
+		"engine maybe",
+		"wind",
+		"teleport nan 0",
+		"teleport 0 inf",
+		"teleport -infinity 0",
+		"wind nan",
+		"wind inf",
+	];
+
+	// Act & Assert
+	for line in lines {
+		assert!(line.parse::<Command>().is_err(), "{line:?}");
+	}
+}
+
+#[test]
+fn console_types_and_enters_a_line() {
+	// Arrange
+	let mut console = Console::default();
+
+	// Act
+	"Mox".chars().for_each(|c| console.text_input(c));
+	let deleted = console.key_down(KeyCode::Backspace);
+	" 4\r".chars().for_each(|c| console.text_input(c));
+	// The characters are not typed by their keys
+	let ignored = console.key_down(KeyCode::M);
+	let entered = console.key_down(KeyCode::Enter);
+
+	// Assert
+	assert_eq!(deleted, None);
+	assert_eq!(ignored, None);
+	assert_eq!(entered.as_deref(), Some("Mo 4"));
+	assert!(console.input.is_empty());
+}
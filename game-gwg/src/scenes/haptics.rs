@@ -0,0 +1,116 @@
+//! The haptic feedback of game controllers
+//!
+//! Neither `good-web-game` nor `miniquad` give access to the force feedback
+//! of game controllers, thus the controllers are opened once more via `gilrs`,
+//! which `good-web-game` uses for their input anyway. On the web, there is no
+//! rumble at all.
+//!
+
+#[cfg(not(target_family = "wasm"))]
+use gilrs::ff::BaseEffect;
+#[cfg(not(target_family = "wasm"))]
+use gilrs::ff::BaseEffectType;
+#[cfg(not(target_family = "wasm"))]
+use gilrs::ff::Effect;
+#[cfg(not(target_family = "wasm"))]
+use gilrs::ff::EffectBuilder;
+#[cfg(not(target_family = "wasm"))]
+use gilrs::ff::Replay;
+#[cfg(not(target_family = "wasm"))]
+use gilrs::ff::Ticks;
+#[cfg(not(target_family = "wasm"))]
+use gilrs::Gilrs;
+
+
+/// The impact speed in m/s, at which the rumble is the strongest
+const RUMBLE_MAX_SPEED: f32 = 5.0;
+
+/// The duration of the rumble of an impact in milliseconds
+#[cfg(not(target_family = "wasm"))]
+const RUMBLE_DURATION_MS: u32 = 200;
+
+
+/// The force feedback of all connected game controllers
+pub struct Haptics {
+	/// The controllers, if they could be opened at all
+	#[cfg(not(target_family = "wasm"))]
+	gilrs: Option<Gilrs>,
+	/// The rumble playing last, it stops once dropped
+	#[cfg(not(target_family = "wasm"))]
+	effect: Option<Effect>,
+}
+
+impl Haptics {
+	pub fn new() -> Self {
+		Self {
+			#[cfg(not(target_family = "wasm"))]
+			gilrs: Gilrs::new()
+				.map_err(|err| println!("[haptics] failed to open the game controllers: {err}"))
+				.ok(),
+			#[cfg(not(target_family = "wasm"))]
+			effect: None,
+		}
+	}
+
+	/// Lets the game controllers rumble for an impact at the given `speed` in m/s
+	///
+	/// The faster the impact, the stronger the rumble.
+	pub fn rumble_impact(&mut self, speed: f32) {
+		let intensity = (speed / RUMBLE_MAX_SPEED).clamp(0.0, 1.0);
+		if intensity > 0.0 {
+			self.rumble(intensity);
+		}
+	}
+
+	/// Lets the game controllers rumble with an `intensity` from `0.0` to `1.0`
+	///
+	/// A new rumble replaces the one playing.
+	#[cfg(not(target_family = "wasm"))]
+	fn rumble(&mut self, intensity: f32) {
+		let gilrs = match &mut self.gilrs {
+			Some(gilrs) => gilrs,
+			None => return,
+		};
+
+		// Keep track of the controllers connected in the meantime
+		while gilrs.next_event().is_some() {}
+
+		let gamepads = Vec::from_iter(
+			gilrs
+				.gamepads()
+				.filter(|(_, gamepad)| gamepad.is_ff_supported())
+				.map(|(id, _)| id),
+		);
+		if gamepads.is_empty() {
+			return;
+		}
+
+		let magnitude = (intensity * f32::from(u16::MAX)) as u16;
+		let effect = EffectBuilder::new()
+			.add_effect(BaseEffect {
+				kind: BaseEffectType::Strong {
+					magnitude,
+				},
+				scheduling: Replay {
+					play_for: Ticks::from_ms(RUMBLE_DURATION_MS),
+					..Default::default()
+				},
+				envelope: Default::default(),
+			})
+			.gamepads(&gamepads)
+			.finish(gilrs)
+			.and_then(|effect| effect.play().map(|()| effect));
+
+		match effect {
+			Ok(effect) => {
+				// Dropping the rumble playing so far stops it
+				drop(self.effect.replace(effect));
+			},
+			Err(err) => println!("[haptics] failed to rumble: {err}"),
+		}
+	}
+
+	/// The web offers no force feedback, see the module docs
+	#[cfg(target_family = "wasm")]
+	fn rumble(&mut self, _intensity: f32) {}
+}
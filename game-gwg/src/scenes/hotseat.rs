@@ -0,0 +1,128 @@
+//! Taking turns at the helm, for several players on the same device
+//!
+//! The players share the very same ship in the very same world, each one
+//! sailing it for a turn of a fixed duration. Each player is credited with
+//! the money earned during the own turns, and whoever earned the most, wins.
+//! The world itself knows nothing of all this, it stays a single-player world.
+//!
+
+#[cfg(test)]
+mod test;
+
+use logic::units::Tick;
+use logic::TICKS_PER_SECOND;
+
+
+/// The duration of a turn in seconds
+pub const TURN_SECONDS: u64 = 90;
+
+/// How many turns each player takes
+pub const TURNS_PER_PLAYER: usize = 2;
+
+
+/// What happened at the end of a turn
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TurnEnd {
+	/// The next player takes the helm, given by index
+	Handover(usize),
+	/// All turns are taken
+	Over,
+}
+
+
+/// The turns of the players and their scores
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hotseat {
+	/// The money earned by each player so far
+	///
+	/// It may be negative, after buying more than selling.
+	scores: Vec<i64>,
+	/// The number of turns taken so far, including the current one
+	turn: usize,
+	/// The duration of a turn in ticks
+	turn_ticks: u64,
+	/// When the current turn started
+	turn_start: Tick,
+	/// The money of the player, when the current turn started
+	start_money: u64,
+}
+
+impl Hotseat {
+	/// The first turn of the first of the given number of players
+	pub fn new(players: usize, now: Tick, money: u64) -> Self {
+		Self::with_turn_ticks(
+			players,
+			TURN_SECONDS * u64::from(TICKS_PER_SECOND),
+			now,
+			money,
+		)
+	}
+
+	/// Like [Hotseat::new], but with turns of the given number of ticks
+	pub fn with_turn_ticks(players: usize, turn_ticks: u64, now: Tick, money: u64) -> Self {
+		assert!(players > 0, "no players to take turns");
+
+		Self {
+			scores: vec![0; players],
+			turn: 1,
+			turn_ticks,
+			turn_start: now,
+			start_money: money,
+		}
+	}
+
+	/// The index of the player at the helm
+	pub fn player(&self) -> usize {
+		(self.turn - 1) % self.scores.len()
+	}
+
+	/// Whether all turns are taken
+	pub fn is_over(&self) -> bool {
+		self.turn > self.scores.len() * TURNS_PER_PLAYER
+	}
+
+	/// The number of ticks left in the current turn
+	pub fn remaining(&self, now: Tick) -> u64 {
+		let elapsed = now.0.saturating_sub(self.turn_start.0);
+		self.turn_ticks.saturating_sub(elapsed)
+	}
+
+	/// The scores of all players, in order
+	pub fn scores(&self) -> &[i64] {
+		&self.scores
+	}
+
+	/// The index of the player with the highest score, or `None` for a tie
+	pub fn leader(&self) -> Option<usize> {
+		let best = *self.scores.iter().max()?;
+		let mut leaders = self.scores.iter().enumerate().filter(|&(_, &score)| score == best);
+		let (leader, _) = leaders.next()?;
+
+		leaders.next().is_none().then_some(leader)
+	}
+
+	/// Ends the current turn, once its time is up
+	///
+	/// The money earned since the start of the turn is credited to the
+	/// player at the helm. After all turns are taken, nothing happens anymore.
+	pub fn update(&mut self, now: Tick, money: u64) -> Option<TurnEnd> {
+		if self.is_over() || self.remaining(now) > 0 {
+			return None;
+		}
+
+		let earned = i64::try_from(money).unwrap_or(i64::MAX)
+			- i64::try_from(self.start_money).unwrap_or(i64::MAX);
+		let player = self.player();
+		self.scores[player] = self.scores[player].saturating_add(earned);
+
+		self.turn += 1;
+		self.turn_start = now;
+		self.start_money = money;
+
+		if self.is_over() {
+			Some(TurnEnd::Over)
+		} else {
+			Some(TurnEnd::Handover(self.player()))
+		}
+	}
+}
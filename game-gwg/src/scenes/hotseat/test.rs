@@ -0,0 +1,70 @@
+use logic::units::Tick;
+
+use super::Hotseat;
+use super::TurnEnd;
+use super::TURNS_PER_PLAYER;
+
+#[test]
+fn turn_ends_only_once_the_time_is_up() {
+	// Arrange
+	let mut hotseat = Hotseat::with_turn_ticks(2, 10, Tick(0), 100);
+
+	// Act
+	let early = hotseat.update(Tick(9), 150);
+	let due = hotseat.update(Tick(10), 150);
+
+	// Assert
+	assert_eq!(early, None);
+	assert_eq!(due, Some(TurnEnd::Handover(1)));
+	assert_eq!(hotseat.player(), 1);
+	assert_eq!(hotseat.remaining(Tick(10)), 10);
+}
+
+#[test]
+fn players_are_credited_with_their_own_earnings() {
+	// Arrange
+	let mut hotseat = Hotseat::with_turn_ticks(2, 10, Tick(0), 100);
+
+	// Act
+	hotseat.update(Tick(10), 250);
+	// The second player spends more than earned
+	hotseat.update(Tick(20), 200);
+
+	// Assert
+	assert_eq!(hotseat.scores(), &[150, -50]);
+	assert_eq!(hotseat.leader(), Some(0));
+}
+
+#[test]
+fn game_is_over_after_all_turns() {
+	// Arrange
+	let players = 3;
+	let mut hotseat = Hotseat::with_turn_ticks(players, 10, Tick(0), 0);
+
+	// Act
+	let ends: Vec<_> = (1..=players * TURNS_PER_PLAYER + 1)
+		.map(|turn| hotseat.update(Tick(turn as u64 * 10), 0))
+		.collect();
+
+	// Assert
+	assert!(ends[..players * TURNS_PER_PLAYER - 1]
+		.iter()
+		.all(|end| matches!(end, Some(TurnEnd::Handover(_)))));
+	assert_eq!(ends[players * TURNS_PER_PLAYER - 1], Some(TurnEnd::Over));
+	assert_eq!(ends[players * TURNS_PER_PLAYER], None);
+	assert!(hotseat.is_over());
+}
+
+#[test]
+fn tie_has_no_leader() {
+	// Arrange
+	let mut hotseat = Hotseat::with_turn_ticks(2, 10, Tick(0), 0);
+
+	// Act
+	hotseat.update(Tick(10), 100);
+	hotseat.update(Tick(20), 200);
+
+	// Assert
+	assert_eq!(hotseat.scores(), &[100, 100]);
+	assert_eq!(hotseat.leader(), None);
+}
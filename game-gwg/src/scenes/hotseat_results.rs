@@ -0,0 +1,173 @@
+//! The scores of the players taking turns, between the turns and at the end
+//!
+
+use good_web_game as gwg;
+use good_web_game::event::GraphicsContext;
+use good_web_game::goodies::scene::Scene;
+use good_web_game::goodies::scene::SceneSwitch;
+use good_web_game::graphics;
+use good_web_game::graphics::Font;
+use good_web_game::graphics::Text;
+use good_web_game::Context;
+use good_web_game::GameResult;
+use miniquad::KeyCode;
+use nalgebra::Point2;
+
+use super::hotseat::Hotseat;
+use super::hotseat::TurnEnd;
+use super::main_menu::BUTTON_COLOR;
+use super::main_menu::TEXT_COLOR;
+use super::GlobalState;
+
+
+
+/// The screen comparing the scores of the players, pushed on top of the game
+///
+/// Between the turns, it gives the next player time to take the helm.
+pub struct HotseatResults {
+	/// The scores of all players, in order
+	scores: Vec<i64>,
+	/// How the last turn ended
+	end: TurnEnd,
+	/// The player in the lead, if any
+	leader: Option<usize>,
+	/// Indicates that the screen shall be closed
+	closing: bool,
+}
+
+impl HotseatResults {
+	pub fn new(hotseat: &Hotseat, end: TurnEnd) -> Self {
+		Self {
+			scores: hotseat.scores().to_vec(),
+			end,
+			leader: hotseat.leader(),
+			closing: false,
+		}
+	}
+
+	/// The heading and the hint at the bottom
+	fn lines(&self) -> (String, String) {
+		match (self.end, self.leader) {
+			(TurnEnd::Handover(next), _) => {
+				(
+					format!("Player {}, take the helm!", next + 1),
+					"Enter to set sail".to_owned(),
+				)
+			},
+			(TurnEnd::Over, Some(leader)) => {
+				(
+					format!("Player {} wins!", leader + 1),
+					"Enter or Esc to continue sailing".to_owned(),
+				)
+			},
+			(TurnEnd::Over, None) => {
+				(
+					"It's a tie!".to_owned(),
+					"Enter or Esc to continue sailing".to_owned(),
+				)
+			},
+		}
+	}
+}
+
+impl Scene<GlobalState> for HotseatResults {
+	fn update(
+		&mut self,
+		_glob: &mut GlobalState,
+		_ctx: &mut Context,
+		_quad_ctx: &mut GraphicsContext,
+	) -> SceneSwitch<GlobalState> {
+		if self.closing {
+			SceneSwitch::Pop
+		} else {
+			SceneSwitch::None
+		}
+	}
+
+	fn draw(
+		&mut self,
+		_glob: &mut GlobalState,
+		ctx: &mut Context,
+		quad_ctx: &mut GraphicsContext,
+	) -> GameResult<()> {
+		let size = graphics::drawable_size(quad_ctx);
+		let (heading, hint) = self.lines();
+
+		graphics::clear(ctx, quad_ctx, [0.0, 0.0, 0.0, 1.0].into());
+
+		let mut heading = Text::new(heading);
+		heading.set_font(Font::default(), (3. * Font::DEFAULT_FONT_SCALE).into());
+		heading.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&heading,
+			(Point2::new(0., size.1 / 4.), TEXT_COLOR),
+		)?;
+
+		// One line per player, the leader highlighted
+		let mut y = size.1 / 2.;
+		for (idx, score) in self.scores.iter().enumerate() {
+			let mut text = Text::new(format!("Player {}: {} €", idx + 1, score));
+			text.set_font(Font::default(), (2. * Font::DEFAULT_FONT_SCALE).into());
+			text.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+			let color = if self.leader == Some(idx) {
+				BUTTON_COLOR
+			} else {
+				TEXT_COLOR
+			};
+			graphics::draw(ctx, quad_ctx, &text, (Point2::new(0., y), color))?;
+			y += text.height(ctx) + Font::DEFAULT_FONT_SCALE;
+		}
+
+		let mut hint = Text::new(hint);
+		hint.set_font(Font::default(), (1. * Font::DEFAULT_FONT_SCALE).into());
+		hint.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&hint,
+			(
+				Point2::new(0., size.1 - 2. * Font::DEFAULT_FONT_SCALE),
+				BUTTON_COLOR,
+			),
+		)?;
+
+		// Finally, issue the draw call and what not, finishing this frame for good
+		graphics::present(ctx, quad_ctx)?;
+
+		Ok(())
+	}
+
+	fn key_down_event(
+		&mut self,
+		_glob: &mut GlobalState,
+		_ctx: &mut Context,
+		_quad_ctx: &mut GraphicsContext,
+		key: KeyCode,
+	) {
+		match (key, self.end) {
+			(KeyCode::Enter | KeyCode::KpEnter, _) => self.closing = true,
+			// The next player has to confirm being ready
+			(KeyCode::Escape, TurnEnd::Over) => self.closing = true,
+			_ => {},
+		}
+	}
+
+	fn name(&self) -> &str {
+		"Hotseat Results"
+	}
+
+	fn resize_event(
+		&mut self,
+		_glob: &mut GlobalState,
+		ctx: &mut gwg::Context,
+		_quad_ctx: &mut gwg::miniquad::GraphicsContext,
+		w: f32,
+		h: f32,
+	) {
+		let coordinates = graphics::Rect::new(0., 0., w, h);
+
+		graphics::set_screen_coordinates(ctx, coordinates).expect("Can't resize the window");
+	}
+}
@@ -1,9 +1,12 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::ops::DerefMut;
 use std::path::Path;
 
+use asset_config::AssetConfig;
 use cfg_if::cfg_if;
-use enum_map::enum_map;
+use enum_map::EnumMap;
 use good_web_game as gwg;
 use gwg::goodies::scene::Scene;
 use gwg::goodies::scene::SceneSwitch;
@@ -16,6 +19,7 @@ use gwg::graphics::Color;
 use gwg::graphics::DrawMode;
 use gwg::graphics::DrawParam;
 use gwg::graphics::Drawable;
+use gwg::graphics::Font;
 use gwg::graphics::Image;
 use gwg::graphics::MeshBuilder;
 use gwg::graphics::PxScale;
@@ -27,24 +31,42 @@ use gwg::miniquad::KeyCode;
 use gwg::timer;
 use gwg::timer::time;
 use gwg::GameResult;
+use logic::generator::generate_race;
 use logic::generator::Generator;
+use logic::generator::Icy;
 use logic::generator::PerlinNoise;
 use logic::generator::Setting;
 use logic::glm::vec1;
 use logic::glm::vec2;
 use logic::glm::Vec2;
+use logic::race::RaceOutcome;
 use logic::resource::ResourcePackContent;
+use logic::resource::ResourceTable;
 use logic::state::Event;
+use logic::state::Magnet;
+use logic::state::ObstacleKind;
 use logic::state::SailKind;
+use logic::state::ShipHull;
+use logic::state::WorldState;
+use logic::terrain::Surface;
 use logic::terrain::TileCoord;
 use logic::terrain::TileDirection;
+#[cfg(not(target_family = "wasm"))]
+use logic::trajectory::Ghost;
+use logic::trajectory::RecordedWorld;
+#[cfg(not(target_family = "wasm"))]
+use logic::trajectory::Score;
+use logic::trajectory::Trajectory;
 use logic::units::BiPolarFraction;
 use logic::units::Distance;
 use logic::units::Elevation;
 use logic::units::Location;
+use logic::units::Tick;
 use logic::units::TileType;
 use logic::Input;
+use logic::StdRng;
 use logic::World;
+use logic::CHECKPOINT_SIZE;
 use logic::TICKS_PER_SECOND;
 use logic::TILE_SIZE;
 use nalgebra::Point2;
@@ -52,20 +74,41 @@ use rand::seq::SliceRandom;
 use rand::Rng;
 use rand::SeedableRng;
 use strum::IntoEnumIterator;
-use wyhash::wyhash;
 
+use super::background;
+use super::bookmarks::Bookmarks;
+use super::hotseat::Hotseat;
+use super::hotseat_results::HotseatResults;
+use super::loading::Loadable;
+use super::logbook::Logbook;
+use super::pause_menu::PauseAction;
+use super::pause_menu::PauseMenu;
+use super::race_results::RaceResults;
+#[cfg(not(target_family = "wasm"))]
+use super::telemetry::Telemetry;
+#[cfg(feature = "dev")]
+use super::tick_budget::SimRate;
+use super::tick_budget::TickBudget;
+use super::tick_budget::MAX_CATCH_UP_TICKS;
+use super::tutorial::Tutorial;
 use super::GlobalState;
 use crate::assets::asset_batch::image_batch;
 use crate::assets::asset_batch::AssetBatch;
+use crate::assets::asset_name::hull_key;
+use crate::assets::asset_name::AssetName;
+use crate::assets::audio::Audios;
 use crate::assets::draw_and_clear;
 use crate::assets::load_asset_config;
+use crate::assets::load_resource_table;
 use crate::assets::BuildingBatches;
 use crate::assets::ResourceBatches;
 use crate::assets::ShipBatches;
 use crate::assets::ShipSprites;
 use crate::assets::TerrainBatches;
 use crate::assets::UiImages;
-use crate::math::Line;
+use crate::assets::ValidateAssets;
+use crate::math;
+use crate::Difficulty;
 
 /// Zoom factor exponentiation base.
 ///
@@ -90,7 +133,7 @@ const ACHIEVEMENT_SPEEDER_SPPED: f32 = 8.0;
 const ACHIEVEMENT_BUSINESSMAN_MONEY: u64 = 10000;
 const ACHIEVEMENT_CHARMER_N_COMPLIMENTS: u32 = 100;
 
-trait Mix {
+pub(super) trait Mix {
 	fn mix(&self, other: &Self, mix_factor: f32) -> Self;
 }
 
@@ -162,6 +205,338 @@ impl Toast {
 	}
 }
 
+const NOTIFICATION_DURATION: f64 = 4.0;
+const NOTIFICATION_FADE_DURATION: f64 = 1.0;
+const NOTIFICATION_MAX_COUNT: usize = 6;
+const NOTIFICATION_COLOR: Color = Color::new(1.0, 1.0, 1.0, 1.0);
+const NOTIFICATION_FAIL_COLOR: Color = Color::new(1.0, 0.4, 0.3, 1.0);
+/// The color of the depth sounder readout in shallow water
+const DEPTH_SHALLOW_COLOR: Color = Color::new(1.0, 0.6, 0.2, 1.0);
+
+/// A transient message shown in the HUD
+struct Notification {
+	text: String,
+	color: Color,
+	/// The time to live in seconds
+	ttl: f64,
+	spawn_time: f64,
+}
+
+impl Notification {
+	fn active(&self) -> bool {
+		time() < self.spawn_time + self.ttl
+	}
+
+	fn color(&self) -> Color {
+		let remaining = self.spawn_time + self.ttl - time();
+		let fade_duration = NOTIFICATION_FADE_DURATION.min(self.ttl);
+		let norm_fade = (remaining / fade_duration).clamp(0.0, 1.0) as f32;
+
+		let mut color = self.color.to_owned();
+		color.a *= norm_fade;
+		color
+	}
+}
+
+/// A queue of transient messages, drawn stacked in a screen corner
+///
+/// The oldest message is displayed on top, and gets dropped first if the
+/// queue overflows.
+#[derive(Default)]
+struct Notifications {
+	queue: VecDeque<Notification>,
+}
+
+impl Notifications {
+	/// Push a message with the default time to live
+	fn push(&mut self, text: impl ToString, color: Color) {
+		self.push_with_ttl(text, color, NOTIFICATION_DURATION);
+	}
+
+	/// Push a message, which will disappear after `ttl` seconds
+	fn push_with_ttl(&mut self, text: impl ToString, color: Color, ttl: f64) {
+		if self.queue.len() >= NOTIFICATION_MAX_COUNT {
+			self.queue.pop_front();
+		}
+
+		self.queue.push_back(Notification {
+			text: text.to_string(),
+			color,
+			ttl,
+			spawn_time: time(),
+		});
+	}
+
+	/// Remove all expired messages
+	fn clean_up(&mut self) {
+		self.queue.retain(|n| n.active());
+	}
+
+	fn iter(&self) -> impl Iterator<Item = &Notification> {
+		self.queue.iter()
+	}
+}
+
+/// How long a particle lives in seconds
+const PARTICLE_DURATION: f64 = 0.8;
+/// The maximum number of particles alive at once, the oldest ones are dropped first
+const PARTICLE_MAX_COUNT: usize = 256;
+/// The downwards acceleration of the particles in m/s²
+const PARTICLE_GRAVITY: f32 = 9.81;
+/// The radius of a particle in meters
+const PARTICLE_SIZE: f32 = 0.1;
+/// The number of particles splashing up when picking up a resource
+const PARTICLE_PICKUP_COUNT: usize = 8;
+/// The number of particles per collision speed in m/s
+const PARTICLE_COLLISION_COUNT_PER_SPEED: f32 = 3.0;
+const PARTICLE_WATER_COLOR: Color = Color::new(0.85, 0.95, 1.0, 1.0);
+const PARTICLE_SAND_COLOR: Color = Color::new(0.9, 0.8, 0.55, 1.0);
+const PARTICLE_WOOD_COLOR: Color = Color::new(0.55, 0.4, 0.25, 1.0);
+
+/// A short-lived droplet or chip, thrown up by an event
+struct Particle {
+	/// The location where it was thrown up
+	origin: Location,
+	/// The horizontal velocity in m/s
+	velocity: Vec2,
+	/// The initial upwards speed in m/s
+	climb: f32,
+	color: Color,
+	spawn_time: f64,
+}
+
+impl Particle {
+	fn age(&self) -> f32 {
+		(time() - self.spawn_time) as f32
+	}
+
+	fn active(&self) -> bool {
+		time() < self.spawn_time + PARTICLE_DURATION
+	}
+
+	/// The current location on the ground and the height above it in meters
+	fn pos(&self) -> (Location, f32) {
+		let age = self.age();
+		let height = self.climb * age - 0.5 * PARTICLE_GRAVITY * age * age;
+		(
+			Location(self.origin.0 + self.velocity * age),
+			height.max(0.0),
+		)
+	}
+
+	fn color(&self) -> Color {
+		let norm_age = (f64::from(self.age()) / PARTICLE_DURATION).clamp(0.0, 1.0) as f32;
+
+		let mut color = self.color;
+		color.a = 1.0 - norm_age;
+		color
+	}
+}
+
+/// A pool of particles, thrown up in bursts at the locations of events
+#[derive(Default)]
+struct Particles {
+	pool: VecDeque<Particle>,
+}
+
+impl Particles {
+	/// Throws up a burst of particles fitting the `event`
+	fn burst(&mut self, event: &Event, rng: &mut impl Rng) {
+		let (count, speed, color) = match *event {
+			Event::Fishy(_) | Event::Starfish(_) | Event::Shoe(_) | Event::Grass(_) => {
+				(PARTICLE_PICKUP_COUNT, 1.0, PARTICLE_WATER_COLOR)
+			},
+			Event::Splash(..) => (2 * PARTICLE_PICKUP_COUNT, 1.5, PARTICLE_WATER_COLOR),
+			Event::TileCollision(speed, _) => {
+				let count = (speed * PARTICLE_COLLISION_COUNT_PER_SPEED) as usize;
+				(count, speed.min(3.0), PARTICLE_SAND_COLOR)
+			},
+			Event::HarborCollision(speed, _) | Event::ObstacleCollision(speed, _) => {
+				let count = (speed * PARTICLE_COLLISION_COUNT_PER_SPEED) as usize;
+				(count, speed.min(3.0), PARTICLE_WOOD_COLOR)
+			},
+			Event::Grounding(_) => (PARTICLE_PICKUP_COUNT, 1.0, PARTICLE_SAND_COLOR),
+			Event::Checkpoint(..) => (2 * PARTICLE_PICKUP_COUNT, 2.0, CHECKPOINT_COLOR),
+		};
+
+		let origin = event.loc();
+		let now = time();
+		for _ in 0..count {
+			if self.pool.len() >= PARTICLE_MAX_COUNT {
+				self.pool.pop_front();
+			}
+
+			let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+			let velocity = vec2(angle.cos(), angle.sin()) * speed * rng.gen_range(0.3..1.0);
+			self.pool.push_back(Particle {
+				origin,
+				velocity,
+				climb: rng.gen_range(1.0..3.0),
+				color,
+				spawn_time: now,
+			});
+		}
+	}
+
+	/// Remove all expired particles
+	fn clean_up(&mut self) {
+		self.pool.retain(|p| p.active());
+	}
+
+	fn iter(&self) -> impl Iterator<Item = &Particle> {
+		self.pool.iter()
+	}
+}
+
+/// The font sizes of the HUD in pixels, each multiplied by the UI scale
+const FONT_SIZE_SMALL: f32 = 18.;
+const FONT_SIZE_NORMAL: f32 = 20.;
+const FONT_SIZE_NOTIFICATION: f32 = 24.;
+const FONT_SIZE_LARGE: f32 = 28.;
+const FONT_SIZE_HUGE: f32 = 32.;
+
+/// The margin in pixels around texts on a high-contrast background
+const HIGH_CONTRAST_PADDING: f32 = 3.;
+/// The opacity of the high-contrast background relative to the halo color
+const HIGH_CONTRAST_ALPHA: f32 = 0.8;
+
+const TRADE_PANEL_WIDTH: f32 = 440.;
+const TRADE_PANEL_PADDING: f32 = 16.;
+const TRADE_PANEL_COLOR: Color = Color::new(0.05, 0.07, 0.15, 0.75);
+const TRADE_TEXT_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.85);
+const TRADE_INACTIVE_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.4);
+const TRADE_HIGHLIGHT_COLOR: Color = Color::new(1.0, 0.85, 0.3, 1.0);
+
+/// How many seconds in advance to warn about a calm
+const CALM_WARNING_TIME: u64 = 10;
+
+/// How many ticks the auto-reef assist leaves the sails to the player, after
+/// reefing by hand
+const AUTO_REEF_PAUSE_TICKS: u32 = 5 * TICKS_PER_SECOND as u32;
+
+/// The least time in seconds between two reports of falling behind
+const BEHIND_REPORT_INTERVAL: f64 = 5.0;
+
+/// How many ticks the ship has to be stuck, before kedging off is offered
+const KEDGE_STUCK_TICKS: u32 = 2 * TICKS_PER_SECOND as u32;
+
+const TUTORIAL_COLOR: Color = Color::new(1.0, 1.0, 0.6, 1.0);
+
+const TARGET_HARBOR_COLOR: Color = Color::new(1.0, 0.85, 0.3, 1.0);
+const TARGET_HARBOR_LINE_WIDTH: f32 = 2.0;
+
+const BOOKMARK_COLOR: Color = Color::new(0.6, 1.0, 0.7, 0.8);
+/// The diameter of the marker of a bookmarked spot in pixel
+const BOOKMARK_MARKER_SIZE: f32 = 16.0;
+
+const CHECKPOINT_COLOR: Color = Color::new(1.0, 0.45, 0.8, 1.0);
+/// The diameter of the edge marker of an off-screen checkpoint in pixel
+const CHECKPOINT_MARKER_SIZE: f32 = 20.0;
+/// The time granted per checkpoint of a race in seconds
+const RACE_SECONDS_PER_CHECKPOINT: u64 = 45;
+
+/// The tint of the ghost ship, mostly transparent
+const GHOST_COLOR: Color = Color::new(0.8, 0.9, 1.0, 0.4);
+
+const ROCK_COLOR: Color = Color::new(0.45, 0.45, 0.48, 1.0);
+const ROCK_OUTLINE_COLOR: Color = Color::new(0.25, 0.25, 0.28, 1.0);
+const WRECK_COLOR: Color = Color::new(0.45, 0.3, 0.18, 1.0);
+const WRECK_OUTLINE_COLOR: Color = Color::new(0.25, 0.16, 0.1, 1.0);
+/// The width of the outline of the obstacles in meters
+const OBSTACLE_OUTLINE_WIDTH: f32 = 0.15;
+
+/// The tint of the frozen water tiles
+const ICE_COLOR: Color = Color::new(0.9, 0.95, 1.0, 0.6);
+
+/// The number of ship positions making up the wake trail
+const WAKE_LENGTH: usize = 30;
+/// Every how many ticks the ship position is sampled for the wake trail
+const WAKE_SAMPLE_TICKS: u64 = 4;
+/// The width of the wake trail right behind the ship in meters
+const WAKE_WIDTH: f32 = 1.2;
+/// The opacity of the wake trail right behind the ship at full intensity
+const WAKE_MAX_ALPHA: f32 = 0.5;
+/// The speed in m/s, from which on the wake trail is drawn at full intensity
+const WAKE_FULL_SPEED: f32 = 6.0;
+/// Segments longer than this in meters are not drawn, e.g. after a teleport
+const WAKE_MAX_SEGMENT: f32 = 5.0;
+const WAKE_COLOR: Color = Color::new(0.9, 0.95, 1.0, 1.0);
+
+/// The speed of panning the camera in photo mode, in pixels per second
+const PHOTO_PAN_SPEED: f32 = 400.;
+
+/// The number of frames shown in the frame time graph
+#[cfg(feature = "dev")]
+const FRAME_GRAPH_LENGTH: usize = 120;
+/// The frame time at the top of the frame time graph, in seconds
+#[cfg(feature = "dev")]
+const FRAME_GRAPH_MAX_TIME: f32 = 1. / 20.;
+#[cfg(feature = "dev")]
+const FRAME_GRAPH_WIDTH: f32 = 240.;
+#[cfg(feature = "dev")]
+const FRAME_GRAPH_HEIGHT: f32 = 80.;
+
+/// For how many ticks the logic events are kept in the event log
+#[cfg(feature = "dev")]
+const EVENT_LOG_TICKS: u64 = 10 * TICKS_PER_SECOND as u64;
+/// The maximum number of the latest events shown in the event log
+#[cfg(feature = "dev")]
+const EVENT_LOG_LINES: usize = 16;
+
+/// The tint of the water the player can not reach
+#[cfg(feature = "dev")]
+const UNREACHABLE_WATER_COLOR: Color = Color::new(1.0, 0.2, 0.1, 0.35);
+
+/// The actions selectable in the trading panel
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(strum::EnumIter)]
+enum TradeAction {
+	SellFish,
+	RepairSail,
+	RepairHull,
+	UpgradeSail,
+	UpgradeHull,
+	UpgradeMagnet,
+}
+
+impl TradeAction {
+	/// The short cut key to trigger this action directly
+	fn key(self) -> KeyCode {
+		match self {
+			Self::SellFish => KeyCode::E,
+			Self::RepairSail => KeyCode::V,
+			Self::RepairHull => KeyCode::H,
+			Self::UpgradeSail => KeyCode::R,
+			Self::UpgradeHull => KeyCode::F,
+			Self::UpgradeMagnet => KeyCode::M,
+		}
+	}
+
+	/// The next entry in the trading panel, wrapping around
+	fn next(self) -> Self {
+		match self {
+			Self::SellFish => Self::RepairSail,
+			Self::RepairSail => Self::RepairHull,
+			Self::RepairHull => Self::UpgradeSail,
+			Self::UpgradeSail => Self::UpgradeHull,
+			Self::UpgradeHull => Self::UpgradeMagnet,
+			Self::UpgradeMagnet => Self::SellFish,
+		}
+	}
+
+	/// The previous entry in the trading panel, wrapping around
+	fn prev(self) -> Self {
+		match self {
+			Self::SellFish => Self::UpgradeMagnet,
+			Self::RepairSail => Self::SellFish,
+			Self::RepairHull => Self::RepairSail,
+			Self::UpgradeSail => Self::RepairHull,
+			Self::UpgradeHull => Self::UpgradeSail,
+			Self::UpgradeMagnet => Self::UpgradeHull,
+		}
+	}
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Hash, Default)]
 pub struct Achievements {
 	admiral: bool,
@@ -179,6 +554,12 @@ pub struct Game {
 	terrain_transition_mask_canvas: Canvas,
 
 	full_screen: bool,
+	/// The factor of all font sizes and offsets of the HUD
+	ui_scale: f32,
+	/// Whether to draw the HUD texts on a solid background instead of a halo
+	high_contrast: bool,
+	/// Whether the camera turns with the ship, keeping its bow pointing up
+	follow_heading: bool,
 	world: World,
 	input: Input,
 	/// The exponent to calculate the zoom factor
@@ -196,6 +577,85 @@ pub struct Game {
 	init: bool,
 
 	toasts: Vec<Toast>,
+	/// HUD messages
+	notifications: Notifications,
+	/// The currently selected entry in the trading panel
+	trade_selection: TradeAction,
+	/// The harbor the player is heading for, as index into the harbors
+	target_harbor: Option<usize>,
+	/// The favorite fishing spots of the player, across all worlds
+	bookmarks: Bookmarks,
+	/// The bookmarked spot the player is heading for, as index into the spots of this world
+	target_spot: Option<usize>,
+	/// The tutorial voyage, if running
+	tutorial: Option<Tutorial>,
+	/// The logbook of notable events, across all voyages
+	logbook: Logbook,
+	/// The beginning of the last calm, that has been warned about
+	warned_calm: Option<Tick>,
+	/// The recent positions and speeds of the ship, oldest first
+	wake: VecDeque<(Location, f32)>,
+	/// The particles thrown up by collisions and pickups
+	particles: Particles,
+	/// Reloads the asset parameters whenever the config changes
+	#[cfg(all(feature = "dev", not(target_family = "wasm")))]
+	asset_config_watcher: crate::assets::AssetConfigWatcher,
+	/// The durations of the last frames in seconds, oldest first
+	#[cfg(feature = "dev")]
+	frame_times: VecDeque<f32>,
+	/// The logic events of the last ticks, oldest first
+	#[cfg(feature = "dev")]
+	event_log: VecDeque<(Tick, Event)>,
+	/// When falling behind was reported last, and the ticks dropped since
+	behind_report: (f64, u32),
+	/// The ticks simulated and dropped in the last frame
+	#[cfg(feature = "dev")]
+	frame_ticks: (u32, u32),
+	/// The rate of the actually simulated ticks
+	#[cfg(feature = "dev")]
+	sim_rate: SimRate,
+	/// Whether to draw the event log
+	#[cfg(feature = "dev")]
+	show_event_log: bool,
+	/// The index of the body of water of each passable tile
+	#[cfg(feature = "dev")]
+	water_regions: HashMap<TileCoord, usize>,
+	/// Whether to tint the water the player can not reach
+	#[cfg(feature = "dev")]
+	show_unreachable_water: bool,
+	/// The cheat console, if open
+	#[cfg(feature = "dev")]
+	console: Option<super::console::Console>,
+	/// The seed the world has been generated from, for restarting on the same map
+	#[cfg(feature = "dev")]
+	seed: u64,
+	/// Whether to throw back cargo in the next tick
+	throw_back: bool,
+	/// For how many ticks the ship has been stuck at the land
+	stuck_ticks: u32,
+	/// Whether the ship is being kedged off, until it is free again
+	kedging: bool,
+	/// Whether the results of the race have been shown already
+	race_reported: bool,
+	/// The turns of the players taking turns at the helm, if any
+	hotseat: Option<Hotseat>,
+	/// For how many more ticks the auto-reef assist leaves the sails to the player
+	auto_reef_pause: u32,
+	/// Whether the sail was tearing in the last tick, to warn only once per gust
+	sail_tearing: bool,
+	/// Whether to open the pause menu in the next update
+	pausing: bool,
+	/// Whether another scene was pushed on top, while the world held still
+	suspended: bool,
+	/// The photo mode, if active
+	photo: Option<PhotoMode>,
+	/// The trajectory of the player being recorded, if requested
+	recording: Option<Trajectory>,
+	/// The previously recorded trajectory followed by the ghost ship
+	ghost: Option<Trajectory>,
+	/// The file receiving the telemetry, if requested
+	#[cfg(not(target_family = "wasm"))]
+	telemetry: Option<Telemetry>,
 
 	fished_compliments: u32,
 	max_speed: f32,
@@ -203,27 +663,197 @@ pub struct Game {
 	achievements: Achievements,
 }
 
-impl Game {
-	pub(super) fn new(
+/// The state of the photo mode, holding the world still for a scenic shot
+///
+/// The HUD is hidden and the camera moves freely, independent of the ship.
+#[derive(Debug, Copy, Clone)]
+struct PhotoMode {
+	/// The center of the free camera
+	camera: Location,
+	/// The zoom before entering the photo mode, restored when leaving it
+	zoom_factor_exp: i32,
+}
+
+/// The phases of loading a new game, in order
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(strum::EnumIter)]
+enum LoadPhase {
+	Sounds,
+	Config,
+	Terrain,
+	Ships,
+	Resources,
+	Buildings,
+	Ui,
+	World,
+}
+
+impl LoadPhase {
+	/// What is being done during this phase
+	fn description(self) -> &'static str {
+		match self {
+			Self::Sounds => "loading sounds",
+			Self::Config => "loading config",
+			Self::Terrain => "loading terrain",
+			Self::Ships => "loading ships",
+			Self::Resources => "loading resources",
+			Self::Buildings => "loading buildings",
+			Self::Ui => "loading ui",
+			Self::World => "generating world",
+		}
+	}
+}
+
+/// Loads a new game incrementally, one phase per step
+///
+/// This allows the loading screen to be redrawn between the phases.
+pub struct GameLoader {
+	/// The phase to be performed by the next step, `None` if all are done
+	phase: Option<LoadPhase>,
+
+	render_config: Option<AssetConfig>,
+	resource_table: Option<ResourceTable>,
+	terrain_batches: Option<TerrainBatches>,
+	ship_batches: Option<ShipBatches>,
+	resource_batches: Option<ResourceBatches>,
+	building_batches: Option<BuildingBatches>,
+	ui: Option<UiImages>,
+
+	/// The seed of the world, once its generation has begun
+	seed: Option<u64>,
+	/// The world being generated in the background
+	#[cfg(not(target_family = "wasm"))]
+	generation: Option<std::thread::JoinHandle<(World, StdRng)>>,
+}
+
+impl GameLoader {
+	pub fn new() -> Self {
+		Self {
+			phase: LoadPhase::iter().next(),
+			render_config: None,
+			resource_table: None,
+			terrain_batches: None,
+			ship_batches: None,
+			resource_batches: None,
+			building_batches: None,
+			ui: None,
+			seed: None,
+			#[cfg(not(target_family = "wasm"))]
+			generation: None,
+		}
+	}
+
+	/// The fraction of the phases done so far, in range [0,1]
+	pub fn progress(&self) -> f32 {
+		let total = LoadPhase::iter().count();
+		let done = self
+			.phase
+			.map(|phase| LoadPhase::iter().take_while(|&p| p != phase).count())
+			.unwrap_or(total);
+
+		done as f32 / total as f32
+	}
+
+	/// What the next step is going to do
+	pub fn description(&self) -> &'static str {
+		self.phase
+			.map(LoadPhase::description)
+			.unwrap_or("ready to go")
+	}
+
+	/// Performs the next loading phase
+	///
+	/// Returns the game, once the final phase is done.
+	pub fn step(
+		&mut self,
 		glob: &mut GlobalState,
 		ctx: &mut gwg::Context,
 		quad_ctx: &mut gwg::miniquad::GraphicsContext,
-	) -> gwg::GameResult<Self> {
-		let opts = &*crate::OPTIONS;
+	) -> gwg::GameResult<Option<Game>> {
+		let phase = self.phase.expect("The game has been loaded already");
 
-		let seed: u64 = opts
-			.seed
-			.as_ref()
-			.map(|s| wyhash(s.as_bytes(), 0))
-			.unwrap_or(gwg::timer::time().floor() as u64);
+		// Only report the beginning of each phase
+		let resumed = {
+			cfg_if! {
+				if #[cfg(not(target_family = "wasm"))] {
+					self.generation.is_some()
+				} else {
+					false
+				}
+			}
+		};
+		if !resumed {
+			println!(
+				"{:.3} [game] {}...",
+				gwg::timer::time_since_start(ctx).as_secs_f64(),
+				phase.description()
+			);
+		}
+		match phase {
+			LoadPhase::Sounds => Self::load_sounds(glob, ctx)?,
+			LoadPhase::Config => {
+				self.render_config = Some(Self::load_config()?);
+				self.resource_table = Some(Self::load_resource_stats()?);
+			},
+			LoadPhase::Terrain => self.terrain_batches = Some(Self::load_terrain(ctx, quad_ctx)?),
+			LoadPhase::Ships => {
+				let config = self.render_config.as_ref().unwrap();
+				self.ship_batches = Some(Self::load_ships(ctx, quad_ctx, config)?);
+			},
+			LoadPhase::Resources => {
+				let config = self.render_config.as_ref().unwrap();
+				self.resource_batches = Some(Self::load_resources(ctx, quad_ctx, config)?);
+			},
+			LoadPhase::Buildings => {
+				let config = self.render_config.as_ref().unwrap();
+				self.building_batches = Some(Self::load_buildings(ctx, quad_ctx, config)?);
+			},
+			LoadPhase::Ui => self.ui = Some(Self::load_ui(ctx, quad_ctx)?),
+			LoadPhase::World => {
+				cfg_if! {
+					if #[cfg(not(target_family = "wasm"))] {
+						// Generate on a worker thread, keeping the loading screen alive
+						let generation = match self.generation.take() {
+							Some(generation) if generation.is_finished() => generation,
+							Some(generation) => {
+								self.generation = Some(generation);
+								return Ok(None);
+							},
+							None => {
+								let seed = *self.seed.insert(Self::seed(glob));
+								let resources = self.resource_table.take().unwrap();
+								let density = Self::resource_density(glob);
+								self.generation = Some(std::thread::spawn(move || {
+									Self::generate_world(seed, resources, density)
+								}));
+								return Ok(None);
+							},
+						};
+						let generated = generation.join().expect("World generation panicked");
+					} else {
+						// There are no threads on the web
+						let seed = *self.seed.insert(Self::seed(glob));
+						let resources = self.resource_table.take().unwrap();
+						let density = Self::resource_density(glob);
+						let generated = Self::generate_world(seed, resources, density);
+					}
+				}
+
+				self.phase = None;
+				return self.finish(glob, ctx, quad_ctx, generated).map(Some);
+			},
+		}
+
+		self.phase = LoadPhase::iter().skip_while(|&p| p != phase).nth(1);
+		Ok(None)
+	}
+
+	fn load_sounds(glob: &mut GlobalState, ctx: &mut gwg::Context) -> gwg::GameResult<()> {
+		let opts = &*crate::OPTIONS;
 
 		let sound_enabled = !opts.muted;
 		let music_enabled = !opts.muted;
 
-		println!(
-			"{:.3} [game] loading sounds...",
-			gwg::timer::time_since_start(ctx).as_secs_f64()
-		);
 		glob.audios
 			.as_mut()
 			.unwrap()
@@ -233,16 +863,47 @@ impl Game {
 			.unwrap()
 			.enable_music(ctx, music_enabled)?;
 
-		println!(
-			"{:.3} [game] loading config...",
-			gwg::timer::time_since_start(ctx).as_secs_f64()
-		);
-		let render_config = load_asset_config();
+		Ok(())
+	}
 
-		println!(
-			"{:.3} [game] loading terrain...",
-			gwg::timer::time_since_start(ctx).as_secs_f64()
+	fn load_config() -> gwg::GameResult<AssetConfig> {
+		let render_config = load_asset_config();
+		let hull_keys = Vec::from_iter(ShipHull::iter().map(hull_key));
+		let required_assets = Vec::from_iter(
+			AssetName::iter()
+				.map(AssetName::key)
+				.chain(hull_keys.iter().map(String::as_str)),
 		);
+		if let Err(problems) = render_config.validate(&required_assets) {
+			for problem in &problems {
+				println!("[game] invalid asset: {problem}");
+			}
+			return Err(gwg::GameError::ResourceLoadError(format!(
+				"invalid asset config:\n{}",
+				problems.join("\n")
+			)));
+		}
+
+		Ok(render_config)
+	}
+
+	fn load_resource_stats() -> gwg::GameResult<ResourceTable> {
+		// Overriding the packaged stats needs a file system
+		#[cfg(not(target_family = "wasm"))]
+		let path = crate::OPTIONS.resource_table.as_deref();
+		#[cfg(target_family = "wasm")]
+		let path = None;
+
+		load_resource_table(path).map_err(|err| {
+			println!("[game] invalid resource table: {err}");
+			gwg::GameError::ResourceLoadError(format!("invalid resource table:\n{err}"))
+		})
+	}
+
+	fn load_terrain(
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::GraphicsContext,
+	) -> gwg::GameResult<TerrainBatches> {
 		let terrain_batches = TerrainBatches {
 			deep: image_batch(ctx, quad_ctx, "img/deepwater0.png")?,
 			shallow: image_batch(ctx, quad_ctx, "img/shallowwater.png")?,
@@ -274,82 +935,92 @@ impl Game {
 			water_anim_2: image_batch(ctx, quad_ctx, "img/wateranim2.png")?,
 		};
 
-		println!(
-			"{:.3} [game] loading ships...",
-			gwg::timer::time_since_start(ctx).as_secs_f64()
-		);
+		Ok(terrain_batches)
+	}
+
+	fn load_ships(
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::GraphicsContext,
+		render_config: &AssetConfig,
+	) -> gwg::GameResult<ShipBatches> {
+		let mut bodies = EnumMap::<ShipHull, Option<AssetBatch>>::default();
+		for hull in ShipHull::iter() {
+			bodies[hull] = Some(AssetBatch::from_config_key(
+				ctx,
+				quad_ctx,
+				render_config,
+				&hull_key(hull),
+			)?);
+		}
+		let mut sails = EnumMap::<SailKind, Vec<AssetBatch>>::default();
+		for (kind, sail) in &mut sails {
+			for &name in AssetName::sail(kind) {
+				sail.push(AssetBatch::from_config(ctx, quad_ctx, render_config, name)?);
+			}
+		}
 		let ship_batches = ShipBatches {
 			basic: ShipSprites {
-				body: enum_map! {
-					logic::state::ShipHull::Small => AssetBatch::from_config(ctx, quad_ctx, &render_config, "ship-00")?,
-					logic::state::ShipHull::Bigger => AssetBatch::from_config(ctx, quad_ctx, &render_config, "ship-01")?,
-				},
-				sail: enum_map! {
-					logic::state::SailKind::Cog => vec![
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-02-0")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-02-1")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-02-2")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-02-3")?,
-				],
-				logic::state::SailKind::Bermuda => vec![
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-00-0")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-00-1")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-00-2")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-00-3")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-00-4")?,
-				],
-				logic::state::SailKind::Schooner => vec![
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-01-0")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-01-1")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-01-2")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-01-3")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-01-4")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-01-5")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-01-6")?,
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, "sail-01-7")?,
-				]
-				},
+				body: bodies.map(|_, body| body.unwrap()),
+				sail: sails,
 			},
 		};
 
-		println!(
-			"{:.3} [game] loading resources...",
-			gwg::timer::time_since_start(ctx).as_secs_f64()
-		);
+		Ok(ship_batches)
+	}
+
+	fn load_resources(
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::GraphicsContext,
+		render_config: &AssetConfig,
+	) -> gwg::GameResult<ResourceBatches> {
 		let mut map_to_ass =
-			|names: Vec<&str>| {
+			|names: Vec<AssetName>| {
 				Vec::from_iter(names.into_iter().map(|name| {
-					AssetBatch::from_config(ctx, quad_ctx, &render_config, name).unwrap()
+					AssetBatch::from_config(ctx, quad_ctx, render_config, name).unwrap()
 				}))
 			};
 		let resource_batches = ResourceBatches {
 			fishes: map_to_ass(vec![
-				"fish-00", "fish-01", "fish-02", "fish-03", "fish-04", "fish-05", "fish-06",
-				"fish-07",
+				AssetName::Fish0,
+				AssetName::Fish1,
+				AssetName::Fish2,
+				AssetName::Fish3,
+				AssetName::Fish4,
+				AssetName::Fish5,
+				AssetName::Fish6,
+				AssetName::Fish7,
 			]),
 			starfishes: map_to_ass(vec![
-				"starfish-00",
-				"starfish-01",
-				"starfish-02",
-				"starfish-03",
-				"starfish-04",
+				AssetName::Starfish0,
+				AssetName::Starfish1,
+				AssetName::Starfish2,
+				AssetName::Starfish3,
+				AssetName::Starfish4,
 			]),
-			shoe: map_to_ass(vec!["shoe-01", "shoe-00"]),
-			grass: map_to_ass(vec!["grass-00", "grass-01"]),
+			shoe: map_to_ass(vec![AssetName::Shoe1, AssetName::Shoe0]),
+			grass: map_to_ass(vec![AssetName::Grass0, AssetName::Grass1]),
 		};
 
-		println!(
-			"{:.3} [game] loading buildings...",
-			gwg::timer::time_since_start(ctx).as_secs_f64()
-		);
+		Ok(resource_batches)
+	}
+
+	fn load_buildings(
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::GraphicsContext,
+		render_config: &AssetConfig,
+	) -> gwg::GameResult<BuildingBatches> {
 		let building_batches = BuildingBatches {
-			harbor: AssetBatch::from_config(ctx, quad_ctx, &render_config, "harbour-00").unwrap(),
+			harbor: AssetBatch::from_config(ctx, quad_ctx, render_config, AssetName::Harbor)
+				.unwrap(),
 		};
 
-		println!(
-			"{:.3} [game] loading ui...",
-			gwg::timer::time_since_start(ctx).as_secs_f64()
-		);
+		Ok(building_batches)
+	}
+
+	fn load_ui(
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::GraphicsContext,
+	) -> gwg::GameResult<UiImages> {
 		let ui = UiImages {
 			wind_direction_indicator: Image::new(ctx, quad_ctx, Path::new("img/wind-arrow.png"))
 				.unwrap(),
@@ -359,43 +1030,90 @@ impl Game {
 			fishy_icon: Image::new(ctx, quad_ctx, Path::new("img/fish-icon.png")).unwrap(),
 		};
 
-		println!(
-			"{:.3} [game] loading other stuff...",
-			gwg::timer::time_since_start(ctx).as_secs_f64()
-		);
-		let terrain_transition_canvas = Canvas::with_window_size(ctx, quad_ctx)?;
-		let terrain_transition_mask_canvas = Canvas::with_window_size(ctx, quad_ctx)?;
+		Ok(ui)
+	}
+
+	/// The seed of the new world
+	fn seed(glob: &GlobalState) -> u64 {
+		glob.seed.seed()
+	}
+
+	/// The resource density of the next world, as chosen in the settings
+	fn resource_density(glob: &GlobalState) -> f32 {
+		let factor = glob.settings.abundance.factor();
+		cfg_if! {
+			if #[cfg(feature = "dev")] {
+				factor * crate::OPTIONS.resource_factor_cheat.unwrap_or(1.0)
+			} else {
+				factor
+			}
+		}
+	}
+
+	/// Generates the world along with the RNG for its further setup
+	///
+	/// This does not touch any graphics, thus it may run on a worker thread.
+	fn generate_world(
+		seed: u64,
+		resources: ResourceTable,
+		resource_density: f32,
+	) -> (World, StdRng) {
+		let opts = &*crate::OPTIONS;
 
-		println!(
-			"{:.3} [game] generating world...",
-			gwg::timer::time_since_start(ctx).as_secs_f64()
-		);
 		// Generate world
 		let noise = PerlinNoise; // logic::generator::WhiteNoise
-		let resource_density = {
-			cfg_if! {
-				if #[cfg(feature = "dev")] {
-					opts.resource_factor_cheat.unwrap_or(1.0)
-				} else {
-					1.0
-				}
-			}
-		};
 		let settings = Setting {
 			edge_length: opts.map_size,
 			resource_density,
+			resources,
+		};
+
+		let mut rng = logic::seeded_rng(seed, 0);
+		let mut world = if opts.icy {
+			Icy(noise).generate(&settings, &mut rng)
+		} else {
+			noise.generate(&settings, &mut rng)
 		};
 
-		let mut rng = logic::StdRng::new(0xcafef00dd15ea5e5, seed.into());
-		let mut world = noise.generate(&settings, &mut rng);
-		// Find a starting position for the player
+		// Tune the ship handling
+		let physics = &mut world.init.physics;
+		if let Some(friction) = opts.ground_friction {
+			physics.ground_friction = friction;
+		}
+		if let Some(friction) = opts.cross_friction {
+			physics.cross_friction = friction;
+		}
+
+		// Trade some animation quality for performance
+		world.init.animation_lod = opts.fish_lod;
+
+		(world, rng)
+	}
+
+	/// Places the player next to the first harbor and equips it for the difficulty
+	///
+	/// This is fully determined by the `world` and the `rng`, thus the same
+	/// generated world always gives the same start.
+	fn setup_player(world: &mut World, rng: &mut StdRng, difficulty: Difficulty) {
+		// Find a starting position for the player, in the water of the first harbor,
+		// so all harbors can be reached
 		let start_point = world.state.harbors[0].loc;
+		let start_tile: TileCoord = start_point.try_into().unwrap();
+		let home_water: HashSet<TileCoord> = world
+			.init
+			.terrain
+			.water_regions()
+			.into_iter()
+			.find(|region| region.contains(&start_tile))
+			.unwrap_or_default()
+			.into_iter()
+			.collect();
 		let mut dist = 2_i32;
 		'find_pos: loop {
 			let forward = ((-dist)..=dist).map(|n| (n, 1));
 			let backward = ((1 - dist)..=(dist - 1)).map(|n| (n, -1));
 			let mut offsets = Vec::from_iter(forward.chain(backward));
-			offsets.shuffle(&mut rng);
+			offsets.shuffle(rng);
 			for (x, s) in offsets {
 				let y = (dist - x.abs()) * s;
 
@@ -403,41 +1121,127 @@ impl Game {
 				let candidate = start_point + Distance(diff);
 				let candidate = world.init.terrain.map_loc_on_torus(candidate);
 
-				if world
-					.init
-					.terrain
-					.get(candidate.try_into().unwrap())
-					.is_passable()
-				{
-					world.state.player.vehicle.pos = candidate;
+				let tile: TileCoord = candidate.try_into().unwrap();
+				if home_water.contains(&tile) {
 					// Orient orthogonal to the distance to the harbor
-					world.state.player.vehicle.heading = f32::atan2(x as f32, -y as f32);
+					world
+						.state
+						.player
+						.vehicle
+						.teleport(candidate, f32::atan2(x as f32, -y as f32));
 					break 'find_pos;
 				}
 			}
 
 			dist += 1;
 		}
+		difficulty.apply(&mut world.state.player);
 		cfg_if! {
 			if #[cfg(feature = "dev")] {
-				if let Some(money) = opts.money_cheat {
+				if let Some(money) = crate::OPTIONS.money_cheat {
 					world.state.player.money = money;
 				}
 			}
 		}
+	}
+
+	/// Sets up a race against the clock, if requested
+	///
+	/// The player has to be placed already, see [Self::setup_player].
+	fn setup_race(world: &mut World) {
+		if let Some(count) = crate::OPTIONS.race {
+			let time_limit =
+				count as u64 * RACE_SECONDS_PER_CHECKPOINT * u64::from(TICKS_PER_SECOND);
+			world.state.race = Some(generate_race(world, count, time_limit));
+		}
+	}
+
+	/// Sets up the turns of several players at the helm, if requested
+	fn setup_hotseat(world: &World) -> Option<Hotseat> {
+		crate::OPTIONS
+			.hotseat
+			.filter(|&players| players > 0)
+			.map(|players| Hotseat::new(players, world.state.timestamp, world.state.player.money))
+	}
+
+	/// Puts the game together with the generated world
+	fn finish(
+		&mut self,
+		glob: &mut GlobalState,
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::GraphicsContext,
+		(mut world, mut rng): (World, StdRng),
+	) -> gwg::GameResult<Game> {
+		let opts = &*crate::OPTIONS;
+
+		let terrain_transition_canvas = Canvas::with_window_size(ctx, quad_ctx)?;
+		let terrain_transition_mask_canvas = Canvas::with_window_size(ctx, quad_ctx)?;
+
+		Self::setup_player(&mut world, &mut rng, glob.difficulty);
+		Self::setup_race(&mut world);
 		world.init.dbg = crate::OPTIONS.to_debugging_conf();
 
+		// Check the first run in any case, so the tutorial is only offered once
+		let first_run = super::tutorial::check_first_run();
+		let tutorial = (opts.tutorial || first_run).then(|| Tutorial::new(&world));
+
+		let mut logbook = Logbook::load();
+		logbook.start_voyage();
+		logbook.save();
+
+		// Replays are stored in files, thus only available on desktop
+		let (recording, ghost) = {
+			cfg_if! {
+				if #[cfg(not(target_family = "wasm"))] {
+					// Exporting the ghost needs the recording as well
+					let recorded_world = Game::recorded_world(&world);
+					let recording = (opts.record.is_some() || opts.export_ghost.is_some())
+						.then(|| Trajectory::new(recorded_world, world.state.timestamp));
+					let ghost = opts
+						.ghost
+						.as_deref()
+						.and_then(|path| super::replay::load_trajectory(path, &recorded_world));
+					(recording, ghost)
+				} else {
+					(None, None)
+				}
+			}
+		};
+
+		#[cfg(not(target_family = "wasm"))]
+		let telemetry = opts.telemetry.as_deref().and_then(|path| {
+			Telemetry::create(path)
+				.map_err(|err| println!("[telemetry] failed to create {}: {err}", path.display()))
+				.ok()
+		});
+
+		// Look up the body of water of each tile, for the diagnostics
+		#[cfg(feature = "dev")]
+		let water_regions = world
+			.init
+			.terrain
+			.water_regions()
+			.into_iter()
+			.enumerate()
+			.flat_map(|(i, region)| region.into_iter().map(move |tc| (tc, i)))
+			.collect();
+
+		let hotseat = Self::setup_hotseat(&world);
+
 		let s = Game {
 			images: Images {
-				terrain_batches,
-				ship_batches,
-				resource_batches,
-				building_batches,
-				ui,
+				terrain_batches: self.terrain_batches.take().unwrap(),
+				ship_batches: self.ship_batches.take().unwrap(),
+				resource_batches: self.resource_batches.take().unwrap(),
+				building_batches: self.building_batches.take().unwrap(),
+				ui: self.ui.take().unwrap(),
 			},
 			terrain_transition_canvas,
 			terrain_transition_mask_canvas,
 			full_screen: !opts.windowed,
+			ui_scale: glob.settings.ui_scale,
+			high_contrast: opts.high_contrast,
+			follow_heading: glob.settings.follow_heading,
 			world,
 			input: Input::default(),
 			zoom_factor_exp: DEFAULT_ZOOM_LEVEL,
@@ -445,6 +1249,51 @@ impl Game {
 			water_wave_2_offset: Default::default(),
 			init: true,
 			toasts: Vec::new(),
+			notifications: Notifications::default(),
+			trade_selection: TradeAction::SellFish,
+			target_harbor: None,
+			bookmarks: Bookmarks::load(),
+			target_spot: None,
+			tutorial,
+			logbook,
+			warned_calm: None,
+			wake: VecDeque::with_capacity(WAKE_LENGTH + 1),
+			particles: Particles::default(),
+			#[cfg(all(feature = "dev", not(target_family = "wasm")))]
+			asset_config_watcher: crate::assets::AssetConfigWatcher::new(),
+			#[cfg(feature = "dev")]
+			frame_times: VecDeque::with_capacity(FRAME_GRAPH_LENGTH),
+			#[cfg(feature = "dev")]
+			event_log: VecDeque::new(),
+			behind_report: (0.0, 0),
+			#[cfg(feature = "dev")]
+			frame_ticks: (0, 0),
+			#[cfg(feature = "dev")]
+			sim_rate: SimRate::default(),
+			#[cfg(feature = "dev")]
+			show_event_log: false,
+			#[cfg(feature = "dev")]
+			water_regions,
+			#[cfg(feature = "dev")]
+			show_unreachable_water: false,
+			#[cfg(feature = "dev")]
+			console: None,
+			#[cfg(feature = "dev")]
+			seed: self.seed.unwrap(),
+			throw_back: false,
+			stuck_ticks: 0,
+			kedging: false,
+			race_reported: false,
+			hotseat,
+			auto_reef_pause: 0,
+			sail_tearing: false,
+			pausing: false,
+			suspended: false,
+			photo: None,
+			recording,
+			ghost,
+			#[cfg(not(target_family = "wasm"))]
+			telemetry,
 			fished_compliments: 0,
 			max_speed: 0.0,
 			max_money: 0,
@@ -458,6 +1307,40 @@ impl Game {
 
 		Ok(s)
 	}
+}
+
+impl Default for GameLoader {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Loadable for GameLoader {
+	type Target = Game;
+
+	fn load(
+		&mut self,
+		glob: &mut GlobalState,
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::GraphicsContext,
+	) -> Option<Self::Target> {
+		self.step(glob, ctx, quad_ctx).unwrap()
+	}
+
+	fn progress(&self) -> Option<(f32, &str)> {
+		Some((GameLoader::progress(self), self.description()))
+	}
+}
+
+impl Game {
+	/// The parameters of the given `world`, recorded along with trajectories
+	fn recorded_world(world: &World) -> RecordedWorld {
+		RecordedWorld {
+			seed: world.init.seed,
+			edge_length: world.init.terrain_setting.edge_length,
+			icy: crate::OPTIONS.icy,
+		}
+	}
 
 	/// A unitless factor for zooming the game view
 	///
@@ -466,6 +1349,35 @@ impl Game {
 		ZOOM_FACTOR_BASE.powi(self.zoom_factor_exp)
 	}
 
+	/// Progress from the previous to the current logical tick in range [0,1]
+	///
+	/// Used to interpolate the rendering between two ticks.
+	fn tick_progress(&self, ctx: &gwg::Context) -> f32 {
+		// While the world holds still, it is shown as of the last tick
+		if self.photo.is_some() {
+			return 1.0;
+		}
+		let remaining = gwg::timer::remaining_update_time(ctx).as_secs_f32();
+		(remaining * f32::from(TICKS_PER_SECOND)).min(1.0)
+	}
+
+	/// The interpolated position of the player ship
+	fn player_render_pos(&self, ctx: &gwg::Context) -> Location {
+		self.world
+			.state
+			.player
+			.vehicle
+			.interpolated_pos(&self.world.init.terrain, self.tick_progress(ctx))
+	}
+
+	/// The center of the camera, following the player ship unless in photo mode
+	fn camera_pos(&self, ctx: &gwg::Context) -> Location {
+		match self.photo {
+			Some(photo) => photo.camera,
+			None => self.player_render_pos(ctx),
+		}
+	}
+
 	/// Conversion factor between world meter and screen pixel.
 	fn pixel_per_meter(&self, ctx: &gwg::Context) -> f32 {
 		// Get the current screen size
@@ -496,6 +1408,34 @@ impl Game {
 	) -> gwg::GameResult<()> {
 		let params = params.into();
 
+		if self.high_contrast {
+			let dims = text.dimensions(ctx);
+			let mut background = halo_color;
+			background.a *= HIGH_CONTRAST_ALPHA;
+			let mesh = MeshBuilder::new()
+				.rectangle(
+					DrawMode::fill(),
+					Rect::new(
+						-HIGH_CONTRAST_PADDING,
+						-HIGH_CONTRAST_PADDING,
+						dims.w + 2. * HIGH_CONTRAST_PADDING,
+						dims.h + 2. * HIGH_CONTRAST_PADDING,
+					),
+					background,
+				)?
+				.build(ctx, quad_ctx)?;
+
+			// The mesh has its own color, thus it must not be tinted
+			let background_params = DrawParam {
+				color: Color::WHITE,
+				..params
+			};
+			graphics::draw(ctx, quad_ctx, &mesh, background_params)?;
+			graphics::draw(ctx, quad_ctx, text, params)?;
+
+			return Ok(());
+		}
+
 		let mut halo_params = params;
 		halo_params.color = halo_color;
 
@@ -523,19 +1463,475 @@ impl Game {
 		Ok(())
 	}
 
+	/// Draws a text without a halo, unless in the high-contrast mode
+	fn draw_plain_text(
+		&self,
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::Context,
+		text: &Text,
+		params: impl Into<DrawParam>,
+	) -> gwg::GameResult<()> {
+		let params = params.into();
+
+		if self.high_contrast {
+			let mut background = Color::BLACK;
+			background.a = params.color.a;
+			self.draw_text_with_halo(ctx, quad_ctx, text, params, background)
+		} else {
+			graphics::draw(ctx, quad_ctx, text, params)
+		}
+	}
+
+	/// The angle by which the world is turned on the screen around its center
+	///
+	/// Zero for the fixed north-up view, otherwise the ship's bow points up.
+	fn camera_angle(&self, ctx: &gwg::Context) -> f32 {
+		if self.follow_heading {
+			let heading = self
+				.world
+				.state
+				.player
+				.vehicle
+				.interpolated_heading(self.tick_progress(ctx));
+			-heading - std::f32::consts::FRAC_PI_2
+		} else {
+			0.0
+		}
+	}
+
+	/// Turns an offset in the world into an offset on the screen, in pixels
+	fn world_to_screen_offset(&self, ctx: &gwg::Context, offset: Vec2) -> Vec2 {
+		logic::glm::rotate_vec2(
+			&(offset * self.pixel_per_meter(ctx)),
+			self.camera_angle(ctx),
+		)
+	}
+
 	fn location_to_screen_coords(
 		&self,
 		ctx: &gwg::Context,
 		pos: Location,
 	) -> nalgebra::Point2<f32> {
 		let screen_coords = gwg::graphics::screen_coordinates(ctx);
-		let loc = pos - self.world.state.player.vehicle.pos;
-		let sprite_pos = loc.0 * self.pixel_per_meter(ctx)
+		let loc = pos - self.camera_pos(ctx);
+		let sprite_pos = self.world_to_screen_offset(ctx, loc.0)
 			+ logic::glm::vec2(screen_coords.w, screen_coords.h) * 0.5;
 
 		nalgebra::Point2::new(sprite_pos.x, sprite_pos.y)
 	}
 
+	/// The inverse of [Game::location_to_screen_coords]
+	///
+	/// Notice, the location is not wrapped onto the torus.
+	fn screen_to_location(&self, ctx: &gwg::Context, point: Point2<f32>) -> Location {
+		let screen_coords = gwg::graphics::screen_coordinates(ctx);
+		let offset = point.coords - logic::glm::vec2(screen_coords.w, screen_coords.h) * 0.5;
+		let loc =
+			logic::glm::rotate_vec2(&offset, -self.camera_angle(ctx)) / self.pixel_per_meter(ctx);
+
+		self.camera_pos(ctx) + Distance(loc)
+	}
+
+	/// The top left corner on the screen of an upright sprite centered at `pos`
+	///
+	/// The sprites of the ships, harbors, and resources are rendered from
+	/// all sides, so instead of turning them with the camera, they keep
+	/// standing upright, only their center moves.
+	fn sprite_to_screen_coords(&self, ctx: &gwg::Context, pos: Location, size: f32) -> Point2<f32> {
+		let center = self.location_to_screen_coords(ctx, pos);
+		center - vec1(size * self.pixel_per_meter(ctx)).xx() * 0.5
+	}
+
+	/// The half extent of the visible area around the camera in meters
+	///
+	/// With a turned camera, this is the bounding box of the turned screen.
+	fn visible_half_extent(&self, ctx: &gwg::Context) -> Vec2 {
+		let screen_coords = gwg::graphics::screen_coordinates(ctx);
+		let camera_pos = self.camera_pos(ctx);
+		[
+			Point2::new(0., 0.),
+			Point2::new(screen_coords.w, 0.),
+			Point2::new(0., screen_coords.h),
+			Point2::new(screen_coords.w, screen_coords.h),
+		]
+		.into_iter()
+		.map(|corner| (self.screen_to_location(ctx, corner) - camera_pos).0.abs())
+		.fold(Vec2::zeros(), |extent, corner| extent.sup(&corner))
+	}
+
+	/// The corners of the tile centered at `center` on the screen
+	///
+	/// Notice, the center is not wrapped onto the torus.
+	fn tile_to_screen_polygon(&self, ctx: &gwg::Context, center: Vec2) -> [Point2<f32>; 4] {
+		let half = TILE_SIZE as f32 * 0.5;
+		[
+			vec2(-half, -half),
+			vec2(half, -half),
+			vec2(half, half),
+			vec2(-half, half),
+		]
+		.map(|corner| self.location_to_screen_coords(ctx, Location(center + corner)))
+	}
+
+	/// Like [Game::location_to_screen_coords], but via the shortest way across the torus
+	///
+	/// Thus, it works for any location near the camera, even across the
+	/// border of the map.
+	fn torus_to_screen_coords(&self, ctx: &gwg::Context, pos: Location) -> Point2<f32> {
+		let screen_coords = gwg::graphics::screen_coordinates(ctx);
+		let offset = self
+			.world
+			.init
+			.terrain
+			.torus_distance(self.camera_pos(ctx), pos);
+		let p = self.world_to_screen_offset(ctx, offset.0)
+			+ logic::glm::vec2(screen_coords.w, screen_coords.h) * 0.5;
+
+		Point2::new(p.x, p.y)
+	}
+
+	/// Resets the world state as freshly generated, keeping the loaded assets
+	///
+	/// This resets the money, the cargo, the position, and the resources, while
+	/// the debugging settings are kept.
+	#[cfg(feature = "dev")]
+	fn restart(&mut self, difficulty: Difficulty) {
+		let setting = &self.world.init.terrain_setting;
+		let (mut world, mut rng) = GameLoader::generate_world(
+			self.seed,
+			setting.resources.clone(),
+			setting.resource_density,
+		);
+		GameLoader::setup_player(&mut world, &mut rng, difficulty);
+		GameLoader::setup_race(&mut world);
+		self.hotseat = GameLoader::setup_hotseat(&world);
+		self.world.state = world.state;
+
+		// Forget everything about the previous voyage
+		self.wake.clear();
+		self.particles = Default::default();
+		self.toasts.clear();
+		self.target_harbor = None;
+		self.target_spot = None;
+		self.warned_calm = None;
+		self.event_log.clear();
+		self.throw_back = false;
+		self.stuck_ticks = 0;
+		self.kedging = false;
+		self.race_reported = false;
+		self.auto_reef_pause = 0;
+		self.sail_tearing = false;
+		if let Some(recording) = &mut self.recording {
+			*recording = Trajectory::new(
+				Self::recorded_world(&self.world),
+				self.world.state.timestamp,
+			);
+		}
+	}
+
+	/// Enters the photo mode at the ship, or leaves it, back to the ship
+	fn toggle_photo_mode(&mut self) {
+		self.photo = match self.photo.take() {
+			Some(photo) => {
+				self.zoom_factor_exp = photo.zoom_factor_exp;
+				None
+			},
+			None => {
+				Some(PhotoMode {
+					camera: self.world.state.player.vehicle.pos,
+					zoom_factor_exp: self.zoom_factor_exp,
+				})
+			},
+		};
+	}
+
+	/// Drops the pending ticks of the timer, resuming right where the world stopped
+	fn skip_pending_ticks(ctx: &mut gwg::Context) {
+		while gwg::timer::check_update_time(ctx, TICKS_PER_SECOND.into()) {}
+	}
+
+	/// Pushes the given scene on top, holding the world still meanwhile
+	///
+	/// Neither the ticks pending by now nor those passing while the scene is
+	/// shown are caught up on later.
+	fn suspend(
+		&mut self,
+		ctx: &mut gwg::Context,
+		scene: Box<dyn Scene<GlobalState>>,
+	) -> SceneSwitch<GlobalState> {
+		Self::skip_pending_ticks(ctx);
+		self.suspended = true;
+		SceneSwitch::Push(scene)
+	}
+
+	/// Pans the camera of the photo mode along the held arrow keys
+	///
+	/// The camera pans along the screen, even if it is turned with the ship.
+	fn pan_photo_camera(&mut self, ctx: &gwg::Context) {
+		use gwg::input::keyboard::is_key_pressed;
+
+		let mut dir = Vec2::zeros();
+		if is_key_pressed(ctx, KeyCode::Left) || is_key_pressed(ctx, KeyCode::A) {
+			dir.x -= 1.0;
+		}
+		if is_key_pressed(ctx, KeyCode::Right) || is_key_pressed(ctx, KeyCode::D) {
+			dir.x += 1.0;
+		}
+		if is_key_pressed(ctx, KeyCode::Up) || is_key_pressed(ctx, KeyCode::W) {
+			dir.y -= 1.0;
+		}
+		if is_key_pressed(ctx, KeyCode::Down) || is_key_pressed(ctx, KeyCode::S) {
+			dir.y += 1.0;
+		}
+		if dir == Vec2::zeros() {
+			return;
+		}
+
+		let screen_coords = gwg::graphics::screen_coordinates(ctx);
+		let center = Point2::new(screen_coords.w, screen_coords.h) * 0.5;
+		let step = dir.normalize() * PHOTO_PAN_SPEED * timer::delta(ctx).as_secs_f32();
+		let camera = self.screen_to_location(ctx, center + step);
+		let camera = self.world.init.terrain.map_loc_on_torus(camera);
+		if let Some(photo) = &mut self.photo {
+			photo.camera = camera;
+		}
+	}
+
+	/// Tints the visible tiles covered with ice
+	fn draw_ice(
+		&self,
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::Context,
+	) -> gwg::GameResult<()> {
+		let terrain = &self.world.init.terrain;
+		if terrain.surface.is_empty() {
+			return Ok(());
+		}
+		let camera_tile = TileCoord::try_from(self.camera_pos(ctx)).unwrap();
+
+		// The number of tiles from the center to the screen edges, rounded up
+		let extent = self.visible_half_extent(ctx);
+		let reach_x = (extent.x / TILE_SIZE as f32).ceil() as i32 + 1;
+		let reach_y = (extent.y / TILE_SIZE as f32).ceil() as i32 + 1;
+		let edge = i32::from(terrain.edge_length);
+
+		let mut mb = MeshBuilder::new();
+		let mut count = 0;
+		for dy in -reach_y..=reach_y {
+			for dx in -reach_x..=reach_x {
+				let tile = TileCoord::new(
+					(i32::from(camera_tile.x) + dx).rem_euclid(edge) as u16,
+					(i32::from(camera_tile.y) + dy).rem_euclid(edge) as u16,
+				);
+				if terrain.surface(tile) != Surface::Ice {
+					continue;
+				}
+
+				// Place the tile relative to the camera, not wrapped around the torus
+				let center =
+					Location::from(camera_tile).0 + vec2(dx as f32, dy as f32) * TILE_SIZE as f32;
+				mb.polygon(
+					DrawMode::fill(),
+					&self.tile_to_screen_polygon(ctx, center),
+					ICE_COLOR,
+				)?;
+				count += 1;
+			}
+		}
+
+		if count > 0 {
+			let mesh = mb.build(ctx, quad_ctx)?;
+			draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+		}
+
+		Ok(())
+	}
+
+	/// Draws the visible obstacles as simple shapes
+	fn draw_obstacles(
+		&self,
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::Context,
+	) -> gwg::GameResult<()> {
+		let terrain = &self.world.init.terrain;
+		let camera_pos = self.camera_pos(ctx);
+		let pixel_per_meter = self.pixel_per_meter(ctx);
+		let screen_coords = gwg::graphics::screen_coordinates(ctx);
+		let half_diagonal = vec2(screen_coords.w, screen_coords.h).magnitude() * 0.5;
+
+		let mut mb = MeshBuilder::new();
+		let mut count = 0;
+		for obstacle in &self.world.state.obstacles {
+			let distance = terrain.torus_distance(camera_pos, obstacle.loc).magnitude();
+			if (distance - obstacle.radius) * pixel_per_meter > half_diagonal {
+				continue;
+			}
+
+			let (fill, outline) = match obstacle.kind {
+				ObstacleKind::Rock => (ROCK_COLOR, ROCK_OUTLINE_COLOR),
+				ObstacleKind::Wreck => (WRECK_COLOR, WRECK_OUTLINE_COLOR),
+			};
+			let center = self.torus_to_screen_coords(ctx, obstacle.loc);
+			let radius = obstacle.radius * pixel_per_meter;
+			mb.circle(DrawMode::fill(), center, radius, 0.5, fill)?;
+			mb.circle(
+				DrawMode::stroke(OBSTACLE_OUTLINE_WIDTH * pixel_per_meter),
+				center,
+				radius,
+				0.5,
+				outline,
+			)?;
+			count += 1;
+		}
+
+		if count > 0 {
+			let mesh = mb.build(ctx, quad_ctx)?;
+			draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+		}
+
+		Ok(())
+	}
+
+	/// Draws the particles thrown up by the events
+	fn draw_particles(
+		&self,
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::Context,
+	) -> gwg::GameResult<()> {
+		let pixel_per_meter = self.pixel_per_meter(ctx);
+
+		let mut mb = MeshBuilder::new();
+		let mut count = 0;
+		for particle in self.particles.iter() {
+			let (loc, height) = particle.pos();
+			// The higher up, the further up on the screen
+			let point = self.torus_to_screen_coords(ctx, loc) - vec2(0.0, height * pixel_per_meter);
+			mb.circle(
+				DrawMode::fill(),
+				point,
+				PARTICLE_SIZE * pixel_per_meter,
+				0.5,
+				particle.color(),
+			)?;
+			count += 1;
+		}
+
+		if count > 0 {
+			let mesh = mb.build(ctx, quad_ctx)?;
+			draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+		}
+
+		Ok(())
+	}
+
+	/// Draws the wake trail behind the player ship
+	///
+	/// The trail tapers and fades out towards its end, and its intensity
+	/// follows the speed of the ship at each position.
+	fn draw_wake(
+		&self,
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::Context,
+	) -> gwg::GameResult<()> {
+		let terrain = &self.world.init.terrain;
+		let player_pos = self.player_render_pos(ctx);
+		let pixel_per_meter = self.pixel_per_meter(ctx);
+
+		let speed = self.world.state.player.vehicle.ground_speed();
+		let points: Vec<_> = self
+			.wake
+			.iter()
+			.copied()
+			.chain([(player_pos, speed)])
+			.collect();
+
+		let mut mb = MeshBuilder::new();
+		let mut segments = 0;
+		for (i, pair) in points.windows(2).enumerate() {
+			let (from, _) = pair[0];
+			let (to, speed) = pair[1];
+			let length = terrain.torus_distance(from, to).magnitude();
+			if length < f32::EPSILON || length > WAKE_MAX_SEGMENT {
+				continue;
+			}
+
+			// The younger the segment, the wider and more opaque it is
+			let youth = (i + 1) as f32 / (points.len() - 1) as f32;
+			let intensity = (speed / WAKE_FULL_SPEED).min(1.0);
+			let alpha = WAKE_MAX_ALPHA * youth * intensity;
+			if alpha <= 0.0 {
+				continue;
+			}
+			let color = Color {
+				a: alpha,
+				..WAKE_COLOR
+			};
+
+			mb.line(
+				&[
+					self.torus_to_screen_coords(ctx, from),
+					self.torus_to_screen_coords(ctx, to),
+				],
+				WAKE_WIDTH * youth * pixel_per_meter,
+				color,
+			)?;
+			segments += 1;
+		}
+
+		if segments > 0 {
+			let mesh = mb.build(ctx, quad_ctx)?;
+			draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+		}
+
+		Ok(())
+	}
+
+	/// Draws the rolling graph of the last frame times, left bottom corner
+	///
+	/// The horizontal line marks the frame time at 60 FPS.
+	#[cfg(feature = "dev")]
+	fn draw_frame_graph(
+		&self,
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::Context,
+	) -> gwg::GameResult<()> {
+		let screen_coords = gwg::graphics::screen_coordinates(ctx);
+		let frame = Rect::new(
+			5.,
+			screen_coords.h - FRAME_GRAPH_HEIGHT - 5.,
+			FRAME_GRAPH_WIDTH,
+			FRAME_GRAPH_HEIGHT,
+		);
+		let height_of =
+			|time: f32| frame.bottom() - (time / FRAME_GRAPH_MAX_TIME).min(1.0) * frame.h;
+
+		let mut mb = MeshBuilder::new();
+		mb.rectangle(DrawMode::fill(), frame, Color::new(0.0, 0.0, 0.0, 0.5))?;
+		let target = height_of(1. / 60.);
+		mb.line(
+			&[
+				Point2::new(frame.left(), target),
+				Point2::new(frame.right(), target),
+			],
+			1.,
+			Color::GREEN,
+		)?;
+
+		if self.frame_times.len() >= 2 {
+			let step = frame.w / (FRAME_GRAPH_LENGTH - 1) as f32;
+			let points =
+				Vec::from_iter(self.frame_times.iter().enumerate().map(|(i, &time)| {
+					Point2::new(frame.left() + i as f32 * step, height_of(time))
+				}));
+			mb.line(&points, 1., Color::YELLOW)?;
+		}
+
+		let mesh = mb.build(ctx, quad_ctx)?;
+		draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+
+		Ok(())
+	}
+
 	fn draw_debugging(
 		&self,
 		ctx: &mut gwg::Context,
@@ -592,27 +1988,25 @@ impl Game {
 				.build(ctx, quad_ctx)?;
 			draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
 
+			// Ship's resource pickup range (relative to the resource centers)
+			let mesh = MeshBuilder::new()
+				.circle(
+					DrawMode::Stroke(StrokeOptions::DEFAULT),
+					self.location_to_screen_coords(ctx, self.world.state.player.vehicle.pos),
+					self.world.state.player.vehicle.collection_radius() * pixel_per_meter,
+					1.0,
+					Color::CYAN,
+				)?
+				.build(ctx, quad_ctx)?;
+			draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+
 			// Ship's tile bounding box
 			let player_tile = TileCoord::try_from(self.world.state.player.vehicle.pos).unwrap();
 			let player_tile_loc = Location::from(player_tile);
-			let player_tile_top_left = self.location_to_screen_coords(
-				ctx,
-				Location(player_tile_loc.0 - vec1(TILE_SIZE as f32 * 0.5).xx()),
-			);
-			let player_tile_bottom_right = self.location_to_screen_coords(
-				ctx,
-				Location(player_tile_loc.0 + vec1(TILE_SIZE as f32 * 0.5).xx()),
-			);
-			let rect = Rect::new(
-				player_tile_top_left.x,
-				player_tile_top_left.y,
-				player_tile_bottom_right.x - player_tile_top_left.x,
-				player_tile_bottom_right.y - player_tile_top_left.y,
-			);
 			let mesh = MeshBuilder::new()
-				.rectangle(
+				.polygon(
 					DrawMode::Stroke(StrokeOptions::DEFAULT),
-					rect,
+					&self.tile_to_screen_polygon(ctx, player_tile_loc.0),
 					Color::MAGENTA,
 				)?
 				.build(ctx, quad_ctx)?;
@@ -639,6 +2033,64 @@ impl Game {
 			draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
 		}
 
+		#[cfg(feature = "dev")]
+		if self.show_unreachable_water {
+			self.draw_unreachable_water(ctx, quad_ctx)?;
+		}
+
+		Ok(())
+	}
+
+	/// Tints all visible water tiles outside the body of water of the player
+	///
+	/// This reveals pockets of water, that are cut off by the world generation.
+	#[cfg(feature = "dev")]
+	fn draw_unreachable_water(
+		&self,
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::Context,
+	) -> gwg::GameResult<()> {
+		let terrain = &self.world.init.terrain;
+		let player_tile = TileCoord::try_from(self.world.state.player.vehicle.pos).unwrap();
+		let player_region = self.water_regions.get(&player_tile);
+		let camera_tile = TileCoord::try_from(self.camera_pos(ctx)).unwrap();
+
+		// The number of tiles from the center to the screen edges, rounded up
+		let extent = self.visible_half_extent(ctx);
+		let reach_x = (extent.x / TILE_SIZE as f32).ceil() as i32 + 1;
+		let reach_y = (extent.y / TILE_SIZE as f32).ceil() as i32 + 1;
+		let edge = i32::from(terrain.edge_length);
+
+		let mut mb = MeshBuilder::new();
+		let mut count = 0;
+		for dy in -reach_y..=reach_y {
+			for dx in -reach_x..=reach_x {
+				let tile = TileCoord::new(
+					(i32::from(camera_tile.x) + dx).rem_euclid(edge) as u16,
+					(i32::from(camera_tile.y) + dy).rem_euclid(edge) as u16,
+				);
+				let region = self.water_regions.get(&tile);
+				if region.is_none() || region == player_region {
+					continue;
+				}
+
+				// Place the tile relative to the camera, not wrapped around the torus
+				let center =
+					Location::from(camera_tile).0 + vec2(dx as f32, dy as f32) * TILE_SIZE as f32;
+				mb.polygon(
+					DrawMode::fill(),
+					&self.tile_to_screen_polygon(ctx, center),
+					UNREACHABLE_WATER_COLOR,
+				)?;
+				count += 1;
+			}
+		}
+
+		if count > 0 {
+			let mesh = mb.build(ctx, quad_ctx)?;
+			draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+		}
+
 		Ok(())
 	}
 }
@@ -656,8 +2108,42 @@ impl Scene<GlobalState> for Game {
 	) -> SceneSwitch<GlobalState> {
 		use gwg::input::keyboard::is_key_pressed;
 
+		// Back from a scene on top, the world continues where it stopped
+		if std::mem::take(&mut self.suspended) {
+			Self::skip_pending_ticks(ctx);
+		}
+
+		// Carry out the choice of the pause menu
+		match glob.pause_action.take() {
+			Some(PauseAction::MainMenu) => {
+				self.save_recording();
+				return SceneSwitch::Pop;
+			},
+			#[cfg(not(target_family = "wasm"))]
+			Some(PauseAction::Quit) => {
+				self.save_recording();
+				gwg::event::quit(ctx);
+				return SceneSwitch::None;
+			},
+			Some(PauseAction::Resume) | None => {},
+		}
+		if std::mem::take(&mut self.pausing) {
+			return self.suspend(ctx, Box::new(PauseMenu::new()));
+		}
+
+		// In photo mode, the world holds still, while the camera moves freely
+		if self.photo.is_some() {
+			self.pan_photo_camera(ctx);
+			Self::skip_pending_ticks(ctx);
+			return SceneSwitch::None;
+		}
+
+		let auto_reef = glob.settings.auto_reef;
 		let audios = glob.audios.as_mut().unwrap();
 
+		// While typing into the cheat console, the keys do not steer the ship
+		let typing = self.is_typing();
+
 		let mut rng = wyhash::WyRng::seed_from_u64((gwg::timer::time() * 1000.) as u64);
 
 		let mut did_trade_successful = false;
@@ -669,34 +2155,138 @@ impl Scene<GlobalState> for Game {
 		let mut collision_harbor_in_this_frame_st = 0.0_f32;
 		let mut collision_beach_in_this_frame_st = 0.0_f32;
 
-		let mut tickies = 0;
+		// The very first frame took all the loading time, which is not caught up on
+		let mut budget = TickBudget::new(if self.init { 1 } else { MAX_CATCH_UP_TICKS });
 		while gwg::timer::check_update_time(ctx, TICKS_PER_SECOND.into()) {
-			tickies += 1;
-			if self.init && tickies > 1 {
-				// Just ignore additional frames
-				continue;
-			}
-			if tickies > 10 {
-				// Just ignore additional frames
+			if !budget.admit() {
+				// Drop the pending tick, keeping the game in real time
 				continue;
 			}
 
 			// Rudder input
 			let mut rudder = 0.0;
-			if is_key_pressed(ctx, KeyCode::Left) || is_key_pressed(ctx, KeyCode::A) {
-				rudder -= 1.0;
+			if !typing {
+				if is_key_pressed(ctx, KeyCode::Left) || is_key_pressed(ctx, KeyCode::A) {
+					rudder -= 1.0;
+				}
+				if is_key_pressed(ctx, KeyCode::Right) || is_key_pressed(ctx, KeyCode::D) {
+					rudder += 1.0;
+				}
+			}
+
+			self.input.rudder = BiPolarFraction::from_f32(rudder).unwrap();
+
+			// The auto-reef assist trims the sails, unless they were just reefed by hand
+			if auto_reef {
+				if self.auto_reef_pause > 0 {
+					self.auto_reef_pause -= 1;
+				} else {
+					let vehicle = &self.world.state.player.vehicle;
+					self.input.reefing = vehicle.auto_reefing(self.world.state.wind);
+				}
+			}
+
+			// Kedging off, until the ship is free again
+			if self.kedging {
+				self.kedging = self.world.state.kedge_off(&self.world.init);
+			}
+
+			let weight_before = self.world.state.player.vehicle.resource_weight;
+			let condition_before = self.world.state.player.vehicle.sail.condition;
+			let mut events = self.world.state.update(&self.world.init, &self.input);
+			let tick = self.world.state.timestamp;
+			self.logbook.note_catch(
+				tick,
+				self.world
+					.state
+					.player
+					.vehicle
+					.resource_weight
+					.saturating_sub(weight_before),
+			);
+			if std::mem::take(&mut self.throw_back) {
+				events.extend(self.world.state.throw_back_cargo(&self.world.init));
+			}
+
+			// Warn about carrying too much sail, once it starts tearing
+			let sail_tearing = self.world.state.player.vehicle.sail.condition < condition_before;
+			if sail_tearing && !self.sail_tearing {
+				self.notifications
+					.push("The sail is tearing, reef it!", NOTIFICATION_FAIL_COLOR);
+			}
+			self.sail_tearing = sail_tearing;
+
+			// Offer kedging off, once the ship is stuck for a while
+			if self.world.state.is_stuck(&self.world.init) {
+				self.stuck_ticks = self.stuck_ticks.saturating_add(1);
+				if self.stuck_ticks == KEDGE_STUCK_TICKS {
+					self.notifications
+						.push("Stuck? Press K to kedge off", NOTIFICATION_COLOR);
+				}
+			} else {
+				self.stuck_ticks = 0;
+			}
+
+			// Record the events, forgetting the outdated ones
+			cfg_if! {
+				if #[cfg(feature = "dev")] {
+					let now = self.world.state.timestamp;
+					self.event_log.extend(events.iter().cloned().map(|ev| (now, ev)));
+					while self
+						.event_log
+						.front()
+						.map_or(false, |(tick, _)| tick.0 + EVENT_LOG_TICKS < now.0)
+					{
+						self.event_log.pop_front();
+					}
+				}
+			}
+
+			if let Some(recording) = &mut self.recording {
+				recording.record(self.world.state.timestamp, &self.world.state.player.vehicle);
+			}
+
+			// Sample the wake trail, forgetting the oldest positions
+			if tick.0 % WAKE_SAMPLE_TICKS == 0 {
+				let vehicle = &self.world.state.player.vehicle;
+				self.wake.push_back((vehicle.pos, vehicle.ground_speed()));
+				if self.wake.len() > WAKE_LENGTH {
+					self.wake.pop_front();
+				}
 			}
-			if is_key_pressed(ctx, KeyCode::Right) || is_key_pressed(ctx, KeyCode::D) {
-				rudder += 1.0;
+
+			cfg_if! {
+				if #[cfg(not(target_family = "wasm"))] {
+					// Once per second is plenty for any dashboard
+					let full_second = self.world.state.timestamp.0 % u64::from(TICKS_PER_SECOND) == 0;
+					if let Some(telemetry) = self.telemetry.as_mut().filter(|_| full_second) {
+						telemetry.write(&self.world);
+					}
+				}
 			}
 
-			self.input.rudder = BiPolarFraction::from_f32(rudder).unwrap();
-			let events = self.world.state.update(&self.world.init, &self.input);
+			// Advance the wave animation by one logical tick, so that the waves
+			// stay in sync with the logical wind regardless of the frame rate.
+			let tick_duration = 1. / f32::from(TICKS_PER_SECOND);
+
+			// Water wave animation, adding half the wind to the offset
+			self.water_wave_offset += self.world.state.wind.0 * tick_duration / 4.;
+			// Modulo the waves by tile size
+			self.water_wave_offset.x %= TILE_SIZE as f32;
+			self.water_wave_offset.y %= TILE_SIZE as f32;
+
+			// Secondary water wave animation, adding half the wind to the offset
+			self.water_wave_2_offset += self.world.state.wind.0 * tick_duration * 2. / 3.;
+			// Modulo the waves by tile size
+			self.water_wave_2_offset.x %= TILE_SIZE as f32;
+			self.water_wave_2_offset.y %= TILE_SIZE as f32;
 
 			// Do event processing
 			for ev in &events {
+				self.particles.burst(ev, &mut rng);
+
 				match ev {
-					Event::Fishy => {
+					Event::Fishy(_) => {
 						if rng.gen_bool(COMPLIMENT_PROBABILITY) {
 							let compliment = COMPLIMENTS.choose(&mut rng).unwrap();
 
@@ -709,6 +2299,34 @@ impl Scene<GlobalState> for Game {
 							self.fished_compliments += 1;
 						}
 					},
+					Event::Splash(weight, _) => {
+						self.notifications.push(
+							format!("Threw {weight} kg back into the sea"),
+							NOTIFICATION_COLOR,
+						);
+					},
+					Event::TileCollision(speed, _)
+					| Event::HarborCollision(speed, _)
+					| Event::ObstacleCollision(speed, _) => {
+						self.logbook.note_collision(tick, *speed);
+						glob.haptics.rumble_impact(*speed);
+					},
+					Event::Grounding(_) => {
+						self.notifications
+							.push("Ran aground!", NOTIFICATION_FAIL_COLOR);
+					},
+					Event::Checkpoint(idx, _) => {
+						let count = self
+							.world
+							.state
+							.race
+							.as_ref()
+							.map_or(0, |race| race.checkpoints.len());
+						self.notifications.push(
+							format!("Checkpoint {}/{count} passed", idx + 1),
+							CHECKPOINT_COLOR,
+						);
+					},
 					_ => {
 						// Nothing of interest
 					},
@@ -719,7 +2337,7 @@ impl Scene<GlobalState> for Game {
 			if audios.sound_enabled {
 				for ev in events {
 					match ev {
-						Event::Fishy => {
+						Event::Fishy(_) => {
 							let fishies = [
 								&audios.sound_fishy_1,
 								&audios.sound_fishy_2,
@@ -729,30 +2347,38 @@ impl Scene<GlobalState> for Game {
 
 							sound.play(ctx).unwrap();
 						},
-						Event::Shoe => {
+						Event::Shoe(_) => {
 							let shoe = [&audios.sound_shoe];
 							let sound = shoe.choose(&mut rng).unwrap();
 
 							sound.play(ctx).unwrap();
 						},
-						Event::Starfish => {
+						Event::Starfish(_) => {
 							let star = [&audios.sound_blub];
 							let sound = star.choose(&mut rng).unwrap();
 
 							sound.play(ctx).unwrap();
 						},
-						Event::Grass => {
+						Event::Grass(_) => {
 							let grass = [&audios.sound_grass];
 							let sound = grass.choose(&mut rng).unwrap();
 
 							sound.play(ctx).unwrap();
 						},
-						Event::HarborCollision(s) => {
+						Event::Splash(..) | Event::Checkpoint(..) => {
+							audios.sound_blub.play(ctx).unwrap();
+						},
+						Event::Grounding(_) => {
+							collision_beach_in_this_frame = true;
+							collision_beach_in_this_frame_st =
+								collision_beach_in_this_frame_st.max(1.0);
+						},
+						Event::HarborCollision(s, _) => {
 							collision_harbor_in_this_frame = true;
 							collision_harbor_in_this_frame_st =
 								collision_harbor_in_this_frame_st.max(s);
 						},
-						Event::TileCollision(s) => {
+						Event::TileCollision(s, _) | Event::ObstacleCollision(s, _) => {
 							collision_beach_in_this_frame = true;
 							collision_beach_in_this_frame_st =
 								collision_beach_in_this_frame_st.max(s);
@@ -763,11 +2389,17 @@ impl Scene<GlobalState> for Game {
 
 			// Selling (fixed with logic ticks, so it is independent from the frame rate)
 			if let Some(mut trade) = self.world.state.get_trading(&self.world.init) {
-				if is_key_pressed(ctx, KeyCode::E) {
+				let selling = !typing
+					&& (is_key_pressed(ctx, TradeAction::SellFish.key())
+						|| (self.trade_selection == TradeAction::SellFish
+							&& (is_key_pressed(ctx, KeyCode::Enter)
+								|| is_key_pressed(ctx, KeyCode::Space))));
+				if selling {
 					let res = trade.sell_fish(10);
 					if let Some(proceeds) = res {
 						if proceeds > 0 {
 							did_trade_successful = true;
+							self.logbook.note_sale(tick);
 						} else {
 							did_trade_fail = true;
 						}
@@ -775,6 +2407,26 @@ impl Scene<GlobalState> for Game {
 				}
 			}
 		}
+		// Report falling behind only now and then, slow frames tend to come in droves
+		if !self.init {
+			let (last, dropped) = &mut self.behind_report;
+			*dropped += budget.dropped();
+			let now = time();
+			if *dropped > 0 && now - *last >= BEHIND_REPORT_INTERVAL {
+				println!(
+					"[game] falling behind, dropped {dropped} ticks in the last {:.0} s",
+					now - *last
+				);
+				*last = now;
+				*dropped = 0;
+			}
+		}
+		#[cfg(feature = "dev")]
+		{
+			self.frame_ticks = (budget.simulated(), budget.dropped());
+			self.sim_rate.record(time(), budget.simulated());
+		}
+
 		// Play collision event sounds
 		if audios.sound_enabled {
 			if collision_harbor_in_this_frame && !audios.collision_harbor_in_this_frame {
@@ -828,12 +2480,60 @@ impl Scene<GlobalState> for Game {
 
 		// Clean up toasts
 		self.toasts.retain(|toast| toast.active());
+		self.notifications.clean_up();
+		self.particles.clean_up();
+
+		// Hot-reload the asset parameters
+		cfg_if! {
+			if #[cfg(all(feature = "dev", not(target_family = "wasm")))] {
+				if let Some(config) = self.asset_config_watcher.poll() {
+					let images = &mut self.images;
+					let changed = images
+						.ship_batches
+						.basic
+						.asset_batches_mut()
+						.chain(images.resource_batches.asset_batches_mut())
+						.chain(std::iter::once(&mut images.building_batches.harbor))
+						.map(|batch| batch.reload_params(&config))
+						.filter(|&changed| changed)
+						.count();
+					println!("[assets] reloaded the config, {changed} assets changed");
+				}
+			}
+		}
+
+		// Warn about an approaching calm
+		let now = self.world.state.timestamp;
+		let calm = WorldState::next_calm(
+			&self.world.init,
+			now,
+			CALM_WARNING_TIME * u64::from(TICKS_PER_SECOND),
+		);
+		if let Some(calm_begin) = calm {
+			if calm_begin > now && self.warned_calm != calm {
+				self.warned_calm = calm;
+				self.notifications
+					.push("Calm approaching", NOTIFICATION_COLOR);
+			}
+		}
+
+		// Process the tutorial
+		let tutorial_prompt = self
+			.tutorial
+			.as_mut()
+			.and_then(|t| t.update(&mut self.world));
+		if let Some(prompt) = tutorial_prompt {
+			self.toast_at_player(prompt, TUTORIAL_COLOR);
+			self.notifications.push(prompt, TUTORIAL_COLOR);
+		}
 
 		// Process achievements
 
 		if !self.achievements.admiral && self.is_sail_maxed() && self.is_hull_maxed() {
 			self.achievements.admiral = true;
 			self.toast_at_player("Admiral", ACHIEVEMENT_COLOR);
+			self.notifications
+				.push("Achievement unlocked: Admiral", ACHIEVEMENT_COLOR);
 			// TODO: play sound
 		}
 
@@ -843,6 +2543,8 @@ impl Scene<GlobalState> for Game {
 		if !self.achievements.speeder && self.max_speed >= ACHIEVEMENT_SPEEDER_SPPED {
 			self.achievements.speeder = true;
 			self.toast_at_player("Speeder", ACHIEVEMENT_COLOR);
+			self.notifications
+				.push("Achievement unlocked: Speeder", ACHIEVEMENT_COLOR);
 			// TODO: play sound
 		}
 
@@ -850,6 +2552,8 @@ impl Scene<GlobalState> for Game {
 		if !self.achievements.businessman && self.max_money >= ACHIEVEMENT_BUSINESSMAN_MONEY {
 			self.achievements.businessman = true;
 			self.toast_at_player("Businessman", ACHIEVEMENT_COLOR);
+			self.notifications
+				.push("Achievement unlocked: Businessman", ACHIEVEMENT_COLOR);
 			// TODO: play sound
 		}
 
@@ -858,16 +2562,43 @@ impl Scene<GlobalState> for Game {
 		{
 			self.achievements.charmer = true;
 			self.toast_at_player("Fishing for compliments", ACHIEVEMENT_COLOR);
+			self.notifications.push(
+				"Achievement unlocked: Fishing for compliments",
+				ACHIEVEMENT_COLOR,
+			);
 			// TODO: play sound
 		}
 
 		self.init = false;
 
-		if is_key_pressed(ctx, KeyCode::Escape) {
-			SceneSwitch::Pop
-		} else {
-			SceneSwitch::None
+		// Show the results, once the race is over
+		let race_outcome = self.world.state.race.as_ref().and_then(|race| {
+			race.outcome()
+				.map(|outcome| (outcome, race.checkpoints.len()))
+		});
+		if let Some((outcome, count)) = race_outcome {
+			if !std::mem::replace(&mut self.race_reported, true) {
+				return self.suspend(ctx, Box::new(RaceResults::new(outcome, count)));
+			}
 		}
+
+		// Hand the ship over to the next player, once the turn is over
+		let turn_end = self.hotseat.as_mut().and_then(|hotseat| {
+			hotseat
+				.update(self.world.state.timestamp, self.world.state.player.money)
+				.map(|end| HotseatResults::new(hotseat, end))
+		});
+		if let Some(results) = turn_end {
+			// Every player starts the turn with the controls let go
+			self.input = Input::default();
+			self.trade_selection = TradeAction::SellFish;
+			self.throw_back = false;
+			self.kedging = false;
+			self.auto_reef_pause = 0;
+			return self.suspend(ctx, Box::new(results));
+		}
+
+		SceneSwitch::None
 	}
 
 	fn draw(
@@ -876,17 +2607,19 @@ impl Scene<GlobalState> for Game {
 		ctx: &mut gwg::Context,
 		quad_ctx: &mut gwg::miniquad::Context,
 	) -> gwg::GameResult<()> {
-		let elapsed = gwg::timer::time_since_start(ctx).as_secs_f32();
-
-		let player_pos = self.world.state.player.vehicle.pos;
+		let player_pos = self.player_render_pos(ctx);
+		let camera_pos = self.camera_pos(ctx);
 		let screen_coords = gwg::graphics::screen_coordinates(ctx);
 		let pixel_per_meter = self.pixel_per_meter(ctx);
+		let camera_angle = self.camera_angle(ctx);
 
 		// Clear screen
-		let red = elapsed.sin() * 0.5 + 0.5;
-		let green = (1.3 + elapsed + 0.3).sin() * 0.5 + 0.5;
-		let blue = (1.13 * elapsed + 0.7).sin() * 0.5 + 0.5;
-		gwg::graphics::clear(ctx, quad_ctx, [red, green, blue, 1.0].into());
+		background::draw_sky(
+			ctx,
+			quad_ctx,
+			crate::OPTIONS.background,
+			self.world.state.wind,
+		)?;
 
 		// Tile sizes
 		let tile_image_size = 64.;
@@ -904,31 +2637,17 @@ impl Scene<GlobalState> for Game {
 
 		// Calculate the top left and bottom right corner where to start and stop drawing the tiles.
 		let (left_top, right_bottom) = {
-			let scm_x = (screen_coords.w / pixel_per_meter)
-				.min(terrain.map_size() - 5. * logic::TILE_SIZE as f32);
-			let scm_y = (screen_coords.h / pixel_per_meter)
-				.min(terrain.map_size() - 5. * logic::TILE_SIZE as f32);
+			let extent = self.visible_half_extent(ctx);
+			let scm_x = (extent.x * 2.).min(terrain.map_size() - 5. * logic::TILE_SIZE as f32);
+			let scm_y = (extent.y * 2.).min(terrain.map_size() - 5. * logic::TILE_SIZE as f32);
 			let dst = Distance::new(scm_x * 0.5, scm_y * 0.5);
 
-			let lt = player_pos - dst - Distance(full_tile * 2.);
-			let rb = player_pos + dst + Distance(full_tile * 2.);
+			let lt = camera_pos - dst - Distance(full_tile * 2.);
+			let rb = camera_pos + dst + Distance(full_tile * 2.);
 
 			(lt, rb)
 		};
 
-		// Water wave animation, adding half the wind to the offset
-		self.water_wave_offset += self.world.state.wind.0 * timer::delta(ctx).as_secs_f32() / 4.;
-		// Modulo the waves by tile size
-		self.water_wave_offset.x %= TILE_SIZE as f32;
-		self.water_wave_offset.y %= TILE_SIZE as f32;
-
-		// Secondary water wave animation, adding half the wind to the offset
-		self.water_wave_2_offset +=
-			self.world.state.wind.0 * timer::delta(ctx).as_secs_f32() * 2. / 3.;
-		// Modulo the waves by tile size
-		self.water_wave_2_offset.x %= TILE_SIZE as f32;
-		self.water_wave_2_offset.y %= TILE_SIZE as f32;
-
 		// Draw the waves (notice the draw order is given way below via the `draw_and_clear`
 		// TODO: draw the wave in wave size i.e. twice the size of a tile.
 		for (tc, _tile) in terrain.iter() {
@@ -947,12 +2666,14 @@ impl Scene<GlobalState> for Game {
 
 				let param = DrawParam::new()
 					.dest(self.location_to_screen_coords(ctx, Location(wave_1)))
+					.rotation(camera_angle)
 					.scale(logic::glm::vec2(scale, scale))
 					.color(Color::new(f1, f1, f1, 1.));
 				self.images.terrain_batches.water_anim.add(param);
 
 				let param = DrawParam::new()
 					.dest(self.location_to_screen_coords(ctx, Location(wave_1 - quarter_tile)))
+					.rotation(camera_angle)
 					.scale(logic::glm::vec2(scale, scale))
 					.color(Color::new(f2, f2, f2, 1.));
 				self.images.terrain_batches.water_anim.add(param);
@@ -962,28 +2683,67 @@ impl Scene<GlobalState> for Game {
 
 				let param = DrawParam::new()
 					.dest(self.location_to_screen_coords(ctx, Location(wave_2)))
+					.rotation(camera_angle)
 					.scale(logic::glm::vec2(scale, scale));
 				self.images.terrain_batches.water_anim_2.add(param);
 			}
 		}
 
-		let ship_pos = self.world.state.player.vehicle.pos.0
-			- logic::glm::vec1(1.22 * 2.5 * logic::VEHICLE_SIZE).xx() * 0.5;
-		let ship_screen_loc = self.location_to_screen_coords(ctx, Location(ship_pos));
+		let ship_size = 1.22 * 2.5 * logic::VEHICLE_SIZE;
+		let ship_screen_loc =
+			self.sprite_to_screen_coords(ctx, terrain.torus_remap(left_top, player_pos), ship_size);
+
+		// The pose of the ghost ship at this tick, if visible
+		let ghost = self
+			.ghost
+			.as_ref()
+			.and_then(|ghost| {
+				ghost.interpolated(terrain, self.world.state.timestamp, self.tick_progress(ctx))
+			})
+			.filter(|sample| terrain.torus_bounds_check(left_top, right_bottom, sample.pos))
+			.map(|sample| {
+				let remapped = terrain.torus_remap(left_top, sample.pos);
+				(
+					self.sprite_to_screen_coords(ctx, remapped, ship_size),
+					sample.heading,
+				)
+			});
 
 		let body = &mut self.images.ship_batches.basic.body[self.world.state.player.vehicle.hull];
 
 		// Draw the player ship
-		let ship_scale = logic::glm::vec1(
-			1.22 * 2.5 * logic::VEHICLE_SIZE * pixel_per_meter / body.params().width as f32,
-		)
-		.xx();
+		let ship_scale =
+			logic::glm::vec1(ship_size * pixel_per_meter / body.params().width as f32).xx();
+
+		// Draw the ghost ship beneath the player ship
+		if let Some((ghost_screen_loc, ghost_heading)) = ghost {
+			let param = DrawParam::new()
+				.dest(ghost_screen_loc)
+				.scale(ship_scale)
+				.color(GHOST_COLOR);
+			body.add_frame(
+				0.0,
+				-f64::from(ghost_heading + camera_angle) + std::f64::consts::PI,
+				0.0,
+				param,
+			);
+		}
+
 		let param = DrawParam::new().dest(ship_screen_loc).scale(ship_scale);
-		let heading = f64::from(self.world.state.player.vehicle.heading);
+		let heading = f64::from(
+			self.world
+				.state
+				.player
+				.vehicle
+				.interpolated_heading(self.tick_progress(ctx)),
+		);
 		let ship_heading = -heading + std::f64::consts::PI;
+		// The frames are rendered from all sides, thus turning the camera
+		// shows the ship from another side
+		let camera_angle_z = -f64::from(camera_angle);
 		body.add_frame(
 			0.0,
-			ship_heading,
+			ship_heading + camera_angle_z,
 			f64::from(self.world.state.player.vehicle.angle_of_list),
 			param,
 		);
@@ -997,15 +2757,13 @@ impl Scene<GlobalState> for Game {
 		let effective_reefing = usize::from(sail_reefing).min(max_sail);
 
 		let sail_ass = &mut sail[effective_reefing];
-		let sail_scale = logic::glm::vec1(
-			1.22 * 2.5 * logic::VEHICLE_SIZE * pixel_per_meter / sail_ass.params().width as f32,
-		)
-		.xx();
+		let sail_scale =
+			logic::glm::vec1(ship_size * pixel_per_meter / sail_ass.params().width as f32).xx();
 		let sail_param = DrawParam::new().dest(ship_screen_loc).scale(sail_scale);
 
 		let sail_orient = match sail_kind {
 			SailKind::Cog => -f64::from(self.world.state.player.vehicle.sail.orientation_rectangle),
-			SailKind::Bermuda | SailKind::Schooner => {
+			SailKind::Bermuda | SailKind::Gaff | SailKind::Schooner => {
 				-f64::from(self.world.state.player.vehicle.sail.orientation_triangle)
 					+ std::f64::consts::PI
 			},
@@ -1015,19 +2773,22 @@ impl Scene<GlobalState> for Game {
 		sail_ass.add_frame(
 			// We need the sail orientation, minus the heading (because the model is in a rotating frame), plus a half turn (because the model is half way turned around).
 			sail_orient - ship_heading + std::f64::consts::PI,
-			ship_heading,
+			ship_heading + camera_angle_z,
 			f64::from(self.world.state.player.vehicle.angle_of_list),
 			sail_param,
 		);
 
-		// Draw the resources (i.e. fishys)
+		// Draw the resources (i.e. fishys), the distant ones at their last
+		// animated pose, see `WorldInit::animation_lod`
 		for resource in &self.world.state.resources {
 			if terrain.torus_bounds_check(left_top, right_bottom, resource.loc) {
 				let remapped = terrain.torus_remap(left_top, resource.loc);
 
-				let resource_pos =
-					remapped.0 - logic::glm::vec1(1.22 * logic::RESOURCE_PACK_FISH_SIZE).xx() * 0.5;
-				let dest = self.location_to_screen_coords(ctx, Location(resource_pos));
+				let dest = self.sprite_to_screen_coords(
+					ctx,
+					remapped,
+					1.22 * logic::RESOURCE_PACK_FISH_SIZE,
+				);
 
 				let batch = match resource.content {
 					ResourcePackContent::Fish0 => &mut self.images.resource_batches.fishes[0],
@@ -1057,6 +2818,8 @@ impl Scene<GlobalState> for Game {
 					},
 					ResourcePackContent::Grass0 => &mut self.images.resource_batches.grass[0],
 					ResourcePackContent::Grass1 => &mut self.images.resource_batches.grass[1],
+					// TODO: give driftwood its own sprite, once the asset repo has one
+					ResourcePackContent::Driftwood => &mut self.images.resource_batches.grass[1],
 				};
 
 				let resource_scale = logic::glm::vec1(
@@ -1076,7 +2839,7 @@ impl Scene<GlobalState> for Game {
 					.scale(resource_scale)
 					.color(Color::new(d_color, d_color, d_color, d_alpha));
 
-				batch.add_frame(0.0, -f64::from(resource.ori), 0.0, param);
+				batch.add_frame(0.0, -f64::from(resource.ori) + camera_angle_z, 0.0, param);
 			}
 		}
 
@@ -1090,15 +2853,17 @@ impl Scene<GlobalState> for Game {
 						/ self.images.building_batches.harbor.params().width as f32,
 				)
 				.xx();
-				let harbor_pos =
-					remapped.0 - logic::glm::vec1(1.22 * 2. * logic::HARBOR_SIZE).xx() * 0.5;
 				let param = DrawParam::new()
-					.dest(self.location_to_screen_coords(ctx, Location(harbor_pos)))
+					.dest(self.sprite_to_screen_coords(
+						ctx,
+						remapped,
+						1.22 * 2. * logic::HARBOR_SIZE,
+					))
 					.scale(harbor_scale);
 
 				self.images.building_batches.harbor.add_frame(
 					0.0,
-					f64::from(harbor.orientation),
+					f64::from(harbor.orientation) + camera_angle_z,
 					0.0,
 					param,
 				);
@@ -1131,6 +2896,7 @@ impl Scene<GlobalState> for Game {
 				let param = DrawParam::new()
 					.dest(dest)
 					.offset(Point2::new(0.5, 0.5))
+					.rotation(camera_angle)
 					.scale(logic::glm::vec2(scale, scale))
 					.color(Color::new(c, c, c, 1.));
 
@@ -1231,7 +2997,8 @@ impl Scene<GlobalState> for Game {
 
 							// The rotation of the mask
 							// The edge masks are all East oriented, turning them clock-wise
-							let param_rot = param.rotation(i as f32 * std::f32::consts::PI / 2.);
+							let param_rot =
+								param.rotation(camera_angle + i as f32 * std::f32::consts::PI / 2.);
 
 							// Determine the mask to be used, by checking how
 							// connected that edge is, that is how many
@@ -1299,7 +3066,8 @@ impl Scene<GlobalState> for Game {
 
 						// The rotation of the mask
 						// The corner mask is North-East oriented, turning them clock-wise
-						let param_rot = param.rotation(i as f32 * std::f32::consts::PI / 2.);
+						let param_rot =
+							param.rotation(camera_angle + i as f32 * std::f32::consts::PI / 2.);
 
 						self.images
 							.terrain_batches
@@ -1325,6 +3093,15 @@ impl Scene<GlobalState> for Game {
 			mask: Vec<&mut SpriteBatch>,
 			tile: &mut SpriteBatch,
 		) -> GameResult {
+			// Without any tiles, there is nothing to be masked, thus skip the
+			// canvas passes entirely, and just discard the masks.
+			if tile.get_sprites().is_empty() {
+				for m in mask {
+					m.clear();
+				}
+				return Ok(());
+			}
+
 			// The mask canvas, needs to be cleared with white
 			graphics::set_canvas(ctx, Some(mask_canvas));
 			graphics::clear(ctx, quad_ctx, [1.0, 1.0, 1.0, 0.0].into());
@@ -1369,7 +3146,6 @@ impl Scene<GlobalState> for Game {
 			[].into_iter()
 				.chain(res.starfishes.iter_mut().map(DerefMut::deref_mut))
 				.chain(res.fishes.iter_mut().map(DerefMut::deref_mut))
-				.chain(res.shoe.iter_mut().map(DerefMut::deref_mut))
 				.chain([&mut tiles.water_anim, &mut tiles.water_anim_2]),
 		)?;
 
@@ -1377,13 +3153,29 @@ impl Scene<GlobalState> for Game {
 		let (tile2, mask2) = tiles.beach_batches();
 		draw_mask_n_tiles(ctx, quad_ctx, mask_canvas, trans_canvas, mask2, tile2)?;
 
-		// Just above them the sea grass
-		draw_and_clear(ctx, quad_ctx, res.grass.iter_mut().map(DerefMut::deref_mut))?;
+		// Just above them the sea grass and the doodads lying on the beach
+		draw_and_clear(
+			ctx,
+			quad_ctx,
+			[].into_iter()
+				.chain(res.grass.iter_mut().map(DerefMut::deref_mut))
+				.chain(res.shoe.iter_mut().map(DerefMut::deref_mut))
+				.chain([res.driftwood.deref_mut()]),
+		)?;
 
 		// And finally the grass land tiles
 		let (tile3, mask3) = tiles.grass_batches();
 		draw_mask_n_tiles(ctx, quad_ctx, mask_canvas, trans_canvas, mask3, tile3)?;
 
+		// The ice floes on the water
+		self.draw_ice(ctx, quad_ctx)?;
+
+		// The rocks and wrecks in the water
+		self.draw_obstacles(ctx, quad_ctx)?;
+
+		// The wake trail beneath the ships
+		self.draw_wake(ctx, quad_ctx)?;
+
 		// Then above all, the harbor and the player's ship
 		draw_and_clear(
 			ctx,
@@ -1408,13 +3200,31 @@ impl Scene<GlobalState> for Game {
 				),
 		)?;
 
-		// Draw Toasts
-		for toast in &self.toasts {
-			let text = Text::new(toast.text.as_str());
-			let params = DrawParam::new().color(toast.color()).dest(
-				self.location_to_screen_coords(ctx, toast.loc) - vec2(text.width(ctx) * 0.5, 0.0),
-			);
-			graphics::draw(ctx, quad_ctx, &text, params)?;
+		// Draw the particles above the ships
+		self.draw_particles(ctx, quad_ctx)?;
+
+		// Draw Toasts, but not on photos
+		if self.photo.is_none() {
+			for toast in &self.toasts {
+				let mut text = Text::new(toast.text.as_str());
+				text.set_font(Default::default(), self.font_size(Font::DEFAULT_FONT_SCALE));
+				let params = DrawParam::new().color(toast.color()).dest(
+					self.location_to_screen_coords(ctx, toast.loc)
+						- vec2(text.width(ctx) * 0.5, 0.0),
+				);
+				self.draw_plain_text(ctx, quad_ctx, &text, params)?;
+			}
+		}
+
+		// Darken the screen edges, beneath the HUD
+		if !crate::OPTIONS.no_vignette {
+			background::draw_vignette(ctx, quad_ctx, self.world.state.wind)?;
+		}
+
+		// In photo mode, there is nothing but the scenery
+		if self.photo.is_some() {
+			gwg::graphics::present(ctx, quad_ctx)?;
+			return Ok(());
 		}
 
 		// Draw some debugging stuff
@@ -1442,6 +3252,20 @@ impl Scene<GlobalState> for Game {
 			Color::BLACK,
 		)?;
 
+		// Frame time graph
+		cfg_if! {
+			if #[cfg(feature = "dev")] {
+				if self.frame_times.len() >= FRAME_GRAPH_LENGTH {
+					self.frame_times.pop_front();
+				}
+				self.frame_times.push_back(timer::delta(ctx).as_secs_f32());
+
+				if crate::OPTIONS.frame_graph {
+					self.draw_frame_graph(ctx, quad_ctx)?;
+				}
+			}
+		}
+
 		// Some Developer text
 		cfg_if! {
 			if #[cfg(feature = "dev")] {
@@ -1515,168 +3339,55 @@ impl Scene<GlobalState> for Game {
 					(Point2::new(left_margin, 80.0), Color::WHITE),
 					Color::BLACK,
 				)?;
-			}
-		}
-
-		let player_loc = self.world.state.player.vehicle.pos;
-		let ppm = self.pixel_per_meter(ctx);
-
-		let budget = self.world.state.player.money;
-		let value = self.world.state.player.vehicle.resource_value;
-
-		// The trading "interface"
-		if let Some(mut t) = self.world.state.get_trading(&self.world.init) {
-			let text_color = Color::new(1.0, 1.0, 1.0, 0.85);
-			let inactive_color = Color::new(1.0, 1.0, 1.0, 0.4);
-
-			let harbor_dist = self
-				.world
-				.init
-				.terrain
-				.torus_distance(player_loc, t.get_harbor().loc);
-			let player_loc_sc = nalgebra::Point2::new(screen_coords.w, screen_coords.h) * 0.5;
-			let harbor_loc_sc = nalgebra::Point2::from(harbor_dist.0 * ppm + player_loc_sc.coords);
-			if t.has_player_valid_speed() {
-				// Trading is possible
-
-				let message = {
-					let hull_upgrade = t.get_price_of_hull_upgrade();
-					let sail_upgrade = t.get_price_for_sail_upgrade();
-
-					match (hull_upgrade, sail_upgrade, t.players_fish_amount()) {
-						(Some(hup), _, _) if budget >= hup => "Time to upgrade!".to_owned(),
-						(_, Some(sup), _) if budget >= sup => "Time to upgrade!".to_owned(),
-						(_, _, fam) if fam > 0 => "Fishy trade?".to_owned(),
-						_ => "Time to fish or cut bait!".to_owned(),
-					}
-				};
-
-				let mut text = Text::new(format!("\"{message}\""));
-				text.set_font(Default::default(), PxScale::from(32.));
-				let mut offset = 0.0;
-				graphics::draw(
-					ctx,
-					quad_ctx,
-					&text,
-					(
-						Point2::new(
-							harbor_loc_sc.x - text.width(ctx) * 0.5,
-							harbor_loc_sc.y - text.height(ctx),
-						),
-						text_color,
-					),
-				)?;
-				offset += text.height(ctx);
-
-				let sell_color = if t.players_fish_amount() > 0 {
-					text_color
-				} else {
-					inactive_color
-				};
-				let mut sell_text = Text::new(format!("E: Sell fish for {value} €"));
-				sell_text.set_font(Default::default(), PxScale::from(20.));
-
-				let (sail_color, sail_message) = if let Some(price) = t.get_price_for_sail_upgrade()
-				{
-					let c = if budget >= price {
-						text_color
-					} else {
-						inactive_color
-					};
-
-					(c, format!("R: Upgrade sail ({price} €)"))
-				} else {
-					(inactive_color, "Your sail is awesome!".to_owned())
-				};
-				let mut sail_text = Text::new(sail_message);
-				sail_text.set_font(Default::default(), PxScale::from(20.));
-
-				let (hull_color, hull_message) = if let Some(price) = t.get_price_of_hull_upgrade()
-				{
-					let c = if budget >= price {
-						text_color
-					} else {
-						inactive_color
-					};
-
-					(c, format!("F: Upgrade hull ({price} €)"))
-				} else {
-					(inactive_color, "Your hull is awesome!".to_owned())
-				};
-				let mut hull_text = Text::new(hull_message);
-				hull_text.set_font(Default::default(), PxScale::from(20.));
-
-				let x_offset = sell_text
-					.width(ctx)
-					.max(sail_text.width(ctx))
-					.max(hull_text.width(ctx))
-					* 0.5;
-				graphics::draw(
-					ctx,
-					quad_ctx,
-					&sell_text,
-					(
-						Point2::new(
-							harbor_loc_sc.x - x_offset,
-							harbor_loc_sc.y - sell_text.height(ctx) + offset,
-						),
-						sell_color,
-					),
-				)?;
-				offset += sell_text.height(ctx) * 1.3;
 
-				graphics::draw(
+				// The simulation rate, falling behind on slow frames
+				let sim_text = Text::new(format!(
+					"Sim: {:.0} / {} ticks/s, last frame: {} ticks, {} dropped",
+					self.sim_rate.rate(),
+					TICKS_PER_SECOND,
+					self.frame_ticks.0,
+					self.frame_ticks.1,
+				));
+				self.draw_text_with_halo(
 					ctx,
 					quad_ctx,
-					&sail_text,
-					(
-						Point2::new(
-							harbor_loc_sc.x - x_offset,
-							harbor_loc_sc.y - sail_text.height(ctx) + offset,
-						),
-						sail_color,
-					),
+					&sim_text,
+					(Point2::new(left_margin, 100.0), Color::WHITE),
+					Color::BLACK,
 				)?;
-				offset += sail_text.height(ctx) * 1.3;
 
-				graphics::draw(
-					ctx,
-					quad_ctx,
-					&hull_text,
-					(
-						Point2::new(
-							harbor_loc_sc.x - x_offset,
-							harbor_loc_sc.y - hull_text.height(ctx) + offset,
-						),
-						hull_color,
-					),
-				)?;
-			} else {
-				// Player is too fast for trading
+				// The cheat console
+				if let Some(console) = &self.console {
+					let prompt_text = Text::new(format!("> {}_", console.input));
+					self.draw_text_with_halo(
+						ctx,
+						quad_ctx,
+						&prompt_text,
+						(Point2::new(left_margin, 130.0), Color::WHITE),
+						Color::BLACK,
+					)?;
+				}
 
-				let mut text = Text::new(
-					if t.players_fish_amount() > 0 {
-						"\"Slow down, sailor!\""
-					} else {
-						"\"Time to fish or cut bait!\""
-					},
-				);
-				text.set_font(Default::default(), PxScale::from(32.));
-				graphics::draw(
-					ctx,
-					quad_ctx,
-					&text,
-					(
-						Point2::new(
-							harbor_loc_sc.x - text.width(ctx) * 0.5,
-							harbor_loc_sc.y - text.height(ctx),
-						),
-						text_color,
-					),
-				)?;
+				// The latest logic events
+				if self.show_event_log {
+					let skip = self.event_log.len().saturating_sub(EVENT_LOG_LINES);
+					for (i, (tick, ev)) in self.event_log.iter().skip(skip).enumerate() {
+						let event_text = Text::new(format!("{:>6}: {:?}", tick.0, ev));
+						self.draw_text_with_halo(
+							ctx,
+							quad_ctx,
+							&event_text,
+							(Point2::new(left_margin, 160.0 + 20.0 * i as f32), Color::WHITE),
+							Color::BLACK,
+						)?;
+					}
+				}
 			}
 		}
 
+		// The trading panel
+		self.draw_trading_panel(ctx, quad_ctx)?;
+
 		// Finally, issue the draw call and what not, finishing this frame for good
 		gwg::graphics::present(ctx, quad_ctx)?;
 
@@ -1690,6 +3401,35 @@ impl Scene<GlobalState> for Game {
 		quad_ctx: &mut gwg::miniquad::Context,
 		keycode: gwg::miniquad::KeyCode,
 	) {
+		// The cheat console takes all the keys while open
+		cfg_if! {
+			if #[cfg(feature = "dev")] {
+				if keycode == KeyCode::F9 {
+					self.console = match self.console {
+						Some(_) => None,
+						None => Some(Default::default()),
+					};
+					return;
+				}
+				if let Some(console) = &mut self.console {
+					if let Some(line) = console.key_down(keycode) {
+						let msg = match line.parse::<super::console::Command>() {
+							Ok(cmd) => cmd.apply(&mut self.world),
+							Err(err) => err,
+						};
+						self.notifications.push(msg, NOTIFICATION_COLOR);
+					}
+					return;
+				}
+			}
+		}
+
+		// Entering or leaving the photo mode
+		if keycode == KeyCode::P || (self.photo.is_some() && keycode == KeyCode::Escape) {
+			self.toggle_photo_mode();
+			return;
+		}
+
 		let audios = glob.audios.as_mut().unwrap();
 
 		// Zoom management
@@ -1703,54 +3443,59 @@ impl Scene<GlobalState> for Game {
 			self.zoom_factor_exp = DEFAULT_ZOOM_LEVEL;
 		}
 
+		// In photo mode, the ship is left alone
+		if self.photo.is_some() {
+			return;
+		}
+
 		// Trading interactions.
 		// Check whether the player is at a harbor
-		if let Some(mut t) = self.world.state.get_trading(&self.world.init) {
-			if t.has_player_valid_speed() {
-				// Check for sail upgrade key
-				if keycode == KeyCode::R {
-					let n = t.upgrade_sail();
-					match n {
-						Ok(()) => {
-							// success
-							if audios.sound_enabled {
-								audios.upgrade_sound.play(ctx).unwrap();
-							}
-						},
-						Err(e) => {
-							// Failed
-							println!("Failed to upgrade sail: {e}");
-							if audios.sound_enabled {
-								audios.fail_sound.play(ctx).unwrap();
-							}
-						},
-					}
-				}
+		let trading = self
+			.world
+			.state
+			.get_trading(&self.world.init)
+			.map(|t| t.has_player_valid_speed())
+			.unwrap_or(false);
+		if trading {
+			// Navigate the trading panel
+			if keycode == KeyCode::Up {
+				self.trade_selection = self.trade_selection.prev();
+			}
+			if keycode == KeyCode::Down {
+				self.trade_selection = self.trade_selection.next();
+			}
 
-				// Check for hull upgrade key
-				if keycode == KeyCode::F {
-					let n = t.upgrade_hull();
-					match n {
-						Ok(()) => {
-							// success
-							if audios.sound_enabled {
-								audios.upgrade_sound.play(ctx).unwrap();
-							}
-						},
-						Err(e) => {
-							// Failed
-							println!("Failed to upgrade sail: {e}");
-							if audios.sound_enabled {
-								audios.fail_sound.play(ctx).unwrap();
-							}
-						},
-					}
-				}
+			// Confirm the selected action, or use its short cut key
+			let action = if keycode == KeyCode::Enter || keycode == KeyCode::Space {
+				Some(self.trade_selection)
+			} else {
+				TradeAction::iter().find(|a| a.key() == keycode)
+			};
+			match action {
+				Some(TradeAction::RepairSail) => {
+					self.upgrade_gear(audios, ctx, TradeAction::RepairSail);
+				},
+				Some(TradeAction::RepairHull) => {
+					self.upgrade_gear(audios, ctx, TradeAction::RepairHull);
+				},
+				Some(TradeAction::UpgradeSail) => {
+					self.upgrade_gear(audios, ctx, TradeAction::UpgradeSail);
+				},
+				Some(TradeAction::UpgradeHull) => {
+					self.upgrade_gear(audios, ctx, TradeAction::UpgradeHull);
+				},
+				Some(TradeAction::UpgradeMagnet) => {
+					self.upgrade_gear(audios, ctx, TradeAction::UpgradeMagnet);
+				},
+				Some(TradeAction::SellFish) | None => {
+					// Selling is done with the logic ticks, see `update`
+				},
 			}
 		}
 
 		// Reefing input
-		if keycode == KeyCode::Up || keycode == KeyCode::W {
+		// While trading, the arrow keys are used to navigate the trading panel
+		if keycode == KeyCode::W || (!trading && keycode == KeyCode::Up) {
 			self.input.reefing = self.input.reefing.increase();
 
 			// Limit reefing
@@ -1758,17 +3503,124 @@ impl Scene<GlobalState> for Game {
 			if self.input.reefing > max_reefing {
 				self.input.reefing = max_reefing;
 			}
+			self.auto_reef_pause = AUTO_REEF_PAUSE_TICKS;
+		}
+		if keycode == KeyCode::S || (!trading && keycode == KeyCode::Down) {
+			self.input.reefing = self.input.reefing.decrease();
+			self.auto_reef_pause = AUTO_REEF_PAUSE_TICKS;
+		}
+
+		// Target harbor selection, cycling through all harbors and none
+		if keycode == KeyCode::T {
+			let next = self.target_harbor.map_or(0, |idx| idx + 1);
+			self.target_harbor = (next < self.world.state.harbors.len()).then(|| next);
+
+			let msg = match self.target_harbor {
+				Some(idx) => format!("Target: harbor {}", idx + 1),
+				None => "Target cleared".to_owned(),
+			};
+			self.notifications.push(msg, NOTIFICATION_COLOR);
+		}
+
+		// Bookmarking the current spot, or removing the bookmark nearby
+		if keycode == KeyCode::B {
+			let seed = self.world.init.seed;
+			let pos = self.world.state.player.vehicle.pos;
+			let added = self.bookmarks.toggle(seed, &self.world.init.terrain, pos);
+			self.bookmarks.save();
+			// The indices shifted, thus the target is gone
+			self.target_spot = None;
+
+			let msg = if added {
+				"Bookmarked this spot"
+			} else {
+				"Bookmark removed"
+			};
+			self.notifications.push(msg, NOTIFICATION_COLOR);
+		}
+
+		// Target spot selection, cycling through all bookmarked spots and none
+		if keycode == KeyCode::G {
+			let count = self.bookmarks.spots(self.world.init.seed).len();
+			let next = self.target_spot.map_or(0, |idx| idx + 1);
+			self.target_spot = (next < count).then(|| next);
+
+			let msg = match self.target_spot {
+				Some(idx) => format!("Target: spot {}", idx + 1),
+				None if count == 0 => "No spots bookmarked, press B to bookmark one".to_owned(),
+				None => "Target cleared".to_owned(),
+			};
+			self.notifications.push(msg, NOTIFICATION_COLOR);
+		}
+
+		// Trawl net input
+		if keycode == KeyCode::N {
+			self.input.net = !self.input.net;
+		}
+
+		// Throwing back cargo is done with the logic ticks, see `update`
+		if keycode == KeyCode::Q {
+			self.throw_back = true;
+		}
+
+		// Kedging off is done with the logic ticks too, but only when stuck
+		if keycode == KeyCode::K && self.stuck_ticks >= KEDGE_STUCK_TICKS {
+			self.kedging = true;
+			self.notifications.push("Kedging off", NOTIFICATION_COLOR);
+		}
+
+		// Sound & Music management
+		if keycode == KeyCode::Key1 {
+			audios.enable_sound(ctx, !audios.sound_enabled).unwrap();
+		}
+		if keycode == KeyCode::Key2 {
+			audios.enable_music(ctx, !audios.music_enabled).unwrap();
+		}
+
+		// Debugging toggles
+		cfg_if! {
+			if #[cfg(feature = "dev")] {
+				let dbg = &mut self.world.init.dbg;
+				let toggled = match keycode {
+					KeyCode::F4 => {
+						self.restart(glob.difficulty);
+						Some("Scuttled, restarting on the same map".to_owned())
+					},
+					KeyCode::F5 => {
+						dbg.ship_engine = !dbg.ship_engine;
+						Some(format!("Engine: {}", dbg.ship_engine))
+					},
+					KeyCode::F6 => {
+						// Freeze the wind to its current direction
+						dbg.fixed_wind_direction = match dbg.fixed_wind_direction {
+							Some(_) => None,
+							None => Some(self.world.state.wind.angle()),
+						};
+						Some(format!("Fixed wind: {:?}", dbg.fixed_wind_direction))
+					},
+					KeyCode::F7 => {
+						dbg.wind_turning = !dbg.wind_turning;
+						Some(format!("Turning wind: {}", dbg.wind_turning))
+					},
+					KeyCode::F8 => {
+						self.show_event_log = !self.show_event_log;
+						None
+					},
+					KeyCode::F10 => {
+						self.show_unreachable_water = !self.show_unreachable_water;
+						Some(format!("Unreachable water: {}", self.show_unreachable_water))
+					},
+					_ => None,
+				};
+				if let Some(msg) = toggled {
+					self.notifications.push(msg, NOTIFICATION_COLOR);
+				}
+			}
 		}
-		if keycode == KeyCode::Down || keycode == KeyCode::S {
-			self.input.reefing = self.input.reefing.decrease();
-		}
 
-		// Sound & Music management
-		if keycode == KeyCode::Key1 {
-			audios.enable_sound(ctx, !audios.sound_enabled).unwrap();
-		}
-		if keycode == KeyCode::Key2 {
-			audios.enable_music(ctx, !audios.music_enabled).unwrap();
+		// Pausing is done in `update`, which may push the pause menu
+		if keycode == KeyCode::Escape {
+			self.pausing = true;
 		}
 
 		// Full screen key
@@ -1779,17 +3631,21 @@ impl Scene<GlobalState> for Game {
 		}
 	}
 
-	/*
-	TODO: what to do about that?
 	fn text_input_event(
 		&mut self,
+		_glob: &mut GlobalState,
 		_ctx: &mut gwg::Context,
-		_quad_ctx: &mut gwg::miniquad::Context,
+		_quad_ctx: &mut gwg::miniquad::GraphicsContext,
 		character: char,
 	) {
-		self.input_text.push(character);
+		// Typing into the cheat console, see `key_down_event` for the other keys
+		#[cfg(feature = "dev")]
+		if let Some(console) = &mut self.console {
+			console.text_input(character);
+		}
+		#[cfg(not(feature = "dev"))]
+		let _ = character;
 	}
-	*/
 
 	fn resize_event(
 		&mut self,
@@ -1815,11 +3671,7 @@ impl Game {
 		quad_ctx: &mut gwg::miniquad::Context,
 	) -> gwg::GameResult<()> {
 		let screen_coords = gwg::graphics::screen_coordinates(ctx);
-		let player_loc = self
-			.world
-			.init
-			.terrain
-			.map_loc_on_torus(self.world.state.player.vehicle.pos);
+		let player_loc = self.player_render_pos(ctx);
 
 
 		// -- Wind indicator --
@@ -1835,34 +3687,37 @@ impl Game {
 		let color2 = &self.images.ui.wind_speed_colors[color_idx2];
 
 		let color = color1.mix(color2, mix_factor);
-		let padding = 128.;
+		let padding = 128. * self.ui_scale;
 
-		// Draw additional info text
+		// Draw additional info text, the exact numbers are for debugging only
 		let text_height = {
-			cfg_if! {
-				if #[cfg(feature = "dev")] {
-					let mut wind_text = Text::new(format!(
-						"{:.1} m/s, {:.0}°",
-						self.world.state.wind.magnitude(),
-						self.world.state.wind.angle()
-							.rem_euclid(std::f32::consts::TAU)
-							.to_degrees(),
-					));
-					wind_text.set_font(Default::default(), PxScale::from(20.));
-
-					let p = DrawParam::new()
-						.dest(Point2::new(
-							screen_coords.w - padding - wind_text.width(ctx) * 0.5,
-							screen_coords.h - wind_text.height(ctx) - 5.,
-						))
-						.color(color);
-					self.draw_text_with_halo(ctx, quad_ctx, &wind_text, p, Color::BLACK)?;
-
-					wind_text.height(ctx)
-				} else {
-					0.
+			let wind = self.world.state.wind;
+			let label = {
+				cfg_if! {
+					if #[cfg(feature = "dev")] {
+						format!(
+							"{}: {:.1} m/s, {:.0}°",
+							wind.description(),
+							wind.magnitude(),
+							wind.angle().rem_euclid(std::f32::consts::TAU).to_degrees(),
+						)
+					} else {
+						wind.description().to_owned()
+					}
 				}
-			}
+			};
+			let mut wind_text = Text::new(label);
+			wind_text.set_font(Default::default(), self.font_size(FONT_SIZE_NORMAL));
+
+			let p = DrawParam::new()
+				.dest(Point2::new(
+					screen_coords.w - padding - wind_text.width(ctx) * 0.5,
+					screen_coords.h - wind_text.height(ctx) - 5.,
+				))
+				.color(color);
+			self.draw_text_with_halo(ctx, quad_ctx, &wind_text, p, Color::BLACK)?;
+
+			wind_text.height(ctx)
 		};
 
 		// Draw wind indicator arrow
@@ -1873,52 +3728,97 @@ impl Game {
 			))
 			.offset(Point2::new(0.5, 0.5))
 			.color(color)
-			.scale(logic::glm::vec1(normed_wind_speed).xx())
-			.rotation(self.world.state.wind.angle() + std::f32::consts::FRAC_PI_2);
+			.scale(logic::glm::vec1(normed_wind_speed * self.ui_scale).xx())
+			// The arrow stays in place, but turns along with the world
+			.rotation(
+				self.world.state.wind.angle()
+					+ std::f32::consts::FRAC_PI_2
+					+ self.camera_angle(ctx),
+			);
 		gwg::graphics::draw(ctx, quad_ctx, &self.images.ui.wind_direction_indicator, p)?;
 
+		// Draw the sail trim indicator, just above the wind indicator
+		let vehicle = &self.world.state.player.vehicle;
+		let efficiency = vehicle
+			.sail
+			.efficiency(vehicle.apparent_wind(self.world.state.wind))
+			.clamp(0.0, 1.0);
+		let bar_width = padding;
+		let bar_height = 10. * self.ui_scale;
+		let bar_frame = Rect::new(
+			screen_coords.w - padding - bar_width * 0.5,
+			screen_coords.h - 2. * padding - text_height - bar_height,
+			bar_width,
+			bar_height,
+		);
+		let bar_fill = Rect {
+			w: bar_width * efficiency,
+			..bar_frame
+		};
+		let bar_color = Color::RED.mix(&Color::GREEN, efficiency);
+		let mesh = {
+			let mut mb = MeshBuilder::new();
+
+			// Notice, an empty rectangle can not be tessellated
+			if bar_fill.w > 0. {
+				mb.rectangle(DrawMode::fill(), bar_fill, bar_color)?;
+			}
+			mb.rectangle(
+				DrawMode::Stroke(StrokeOptions::DEFAULT),
+				bar_frame,
+				Color::WHITE,
+			)?;
+
+			mb.build(ctx, quad_ctx)?
+		};
+		draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+
+		let mut trim_text = Text::new(format!("Trim: {:.0}%", efficiency * 100.));
+		trim_text.set_font(Default::default(), self.font_size(FONT_SIZE_SMALL));
+		let p = DrawParam::new()
+			.dest(Point2::new(
+				bar_frame.x + (bar_frame.w - trim_text.width(ctx)) * 0.5,
+				bar_frame.y - trim_text.height(ctx) - 2.,
+			))
+			.color(bar_color);
+		self.draw_text_with_halo(ctx, quad_ctx, &trim_text, p, Color::BLACK)?;
+
 
 
 		// -- Harbor indicators --
-		for harbor_distance in self.world.state.harbors.iter().map(|harbor| {
-			self.world
-				.init
-				.terrain
-				.torus_distance(player_loc, harbor.loc)
-		}) {
+		// They fade out with the distance, up to half the map length
+		let max_dist = self.map_length() * 0.5;
+		for (idx, harbor_distance) in self
+			.world
+			.state
+			.harbors
+			.iter()
+			.map(|harbor| {
+				self.world
+					.init
+					.terrain
+					.torus_distance(player_loc, harbor.loc)
+			})
+			.enumerate()
+		{
 			let player_loc_sc = nalgebra::Point2::new(screen_coords.w, screen_coords.h) * 0.5;
 			let harbor_loc_sc = nalgebra::Point2::from(
-				harbor_distance.0 * self.pixel_per_meter(ctx) + player_loc_sc.coords,
+				self.world_to_screen_offset(ctx, harbor_distance.0) + player_loc_sc.coords,
 			);
+			let is_target = self.target_harbor == Some(idx);
+
+			// Skip the harbors too far away to be shown at all, except the target
+			if !is_target && harbor_distance.magnitude() >= max_dist {
+				continue;
+			}
+
+			// The end of the bearing line, either the harbor itself or its edge marker
+			let mut bearing_end = harbor_loc_sc;
 
 			if !screen_coords.contains(harbor_loc_sc) {
-				let towards_harbor = (harbor_loc_sc - player_loc_sc).normalize();
-				let harbor_line = Line(player_loc_sc, harbor_loc_sc);
-
-				let screen_corners = [
-					nalgebra::Point2::new(screen_coords.x, screen_coords.y + screen_coords.h),
-					nalgebra::Point2::new(
-						screen_coords.x + screen_coords.w,
-						screen_coords.y + screen_coords.h,
-					),
-					nalgebra::Point2::new(screen_coords.x + screen_coords.w, screen_coords.y),
-					nalgebra::Point2::new(screen_coords.x, screen_coords.y),
-				];
-
-				let display_point = (0..screen_corners.len())
-					.map(|idx1: usize| {
-						let idx2 = (idx1 + 1) % screen_corners.len();
-						Line(screen_corners[idx1], screen_corners[idx2])
-					})
-					.filter_map(|line| harbor_line.intersect(&line))
-					.filter(|intersection_point| {
-						screen_coords.contains(intersection_point - towards_harbor * 0.01)
-					})
-					.min_by(|a, b| {
-						let dst_a = logic::glm::distance2(&harbor_loc_sc.coords, &a.coords);
-						let dst_b = logic::glm::distance2(&harbor_loc_sc.coords, &b.coords);
-						dst_a.partial_cmp(&dst_b).unwrap()
-					});
+				// The marker is always in the direction of the harbor, never behind
+				let display_point =
+					math::ray_exit(screen_coords, player_loc_sc, harbor_loc_sc - player_loc_sc);
 
 				if let Some(point) = display_point {
 					let inset = self.images.ui.harbor_indicator.width() as f32;
@@ -1932,18 +3832,25 @@ impl Game {
 							screen_coords.y + screen_coords.h - inset,
 						),
 					);
-					let max_dist = self.map_length() * 0.5;
 					let harbor_dst = harbor_distance.magnitude();
 					let harbor_closeness = (max_dist - harbor_dst).max(0.0) / max_dist;
 
 					let mut p = DrawParam::new()
 						.dest(draw_point)
 						.offset(Point2::new(0.5, 0.5));
-					p.color.a = harbor_closeness;
+					if is_target {
+						// Always show the target clearly
+						p = p
+							.color(TARGET_HARBOR_COLOR)
+							.scale(logic::glm::vec2(1.5, 1.5));
+					} else {
+						p.color.a = harbor_closeness;
+					}
 					gwg::graphics::draw(ctx, quad_ctx, &self.images.ui.harbor_indicator, p)?;
+					bearing_end = draw_point;
 
 					let mut text = Text::new(format!("{}m", harbor_distance.magnitude().round()));
-					text.set_font(Default::default(), PxScale::from(18.));
+					text.set_font(Default::default(), self.font_size(FONT_SIZE_SMALL));
 					graphics::draw(
 						ctx,
 						quad_ctx,
@@ -1955,24 +3862,180 @@ impl Game {
 					)?;
 				}
 			}
+
+			// Bearing line towards the target harbor
+			if is_target && logic::glm::distance2(&player_loc_sc.coords, &bearing_end.coords) > 1.0
+			{
+				let mesh = MeshBuilder::new()
+					.line(
+						&[player_loc_sc, bearing_end],
+						TARGET_HARBOR_LINE_WIDTH,
+						TARGET_HARBOR_COLOR,
+					)?
+					.build(ctx, quad_ctx)?;
+				draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+			}
+		}
+
+		// -- Bookmarked spots --
+		let spots = self.bookmarks.spots(self.world.init.seed);
+		if !spots.is_empty() {
+			let player_loc_sc = nalgebra::Point2::new(screen_coords.w, screen_coords.h) * 0.5;
+			let marker_size = BOOKMARK_MARKER_SIZE * self.ui_scale;
+			let mut labels = Vec::new();
+
+			let mut mb = MeshBuilder::new();
+			for (idx, &spot) in spots.iter().enumerate() {
+				let spot_distance = self.world.init.terrain.torus_distance(player_loc, spot);
+				let spot_sc = nalgebra::Point2::from(
+					self.world_to_screen_offset(ctx, spot_distance.0) + player_loc_sc.coords,
+				);
+				let color = if self.target_spot == Some(idx) {
+					TARGET_HARBOR_COLOR
+				} else {
+					BOOKMARK_COLOR
+				};
+
+				// Off screen, the marker sits at the edge in the direction of the spot
+				let marker = if screen_coords.contains(spot_sc) {
+					spot_sc
+				} else {
+					let point =
+						match math::ray_exit(screen_coords, player_loc_sc, spot_sc - player_loc_sc)
+						{
+							Some(point) => point,
+							None => continue,
+						};
+					let marker = nalgebra::Point2::new(
+						point.x.clamp(
+							screen_coords.x + marker_size,
+							screen_coords.x + screen_coords.w - marker_size,
+						),
+						point.y.clamp(
+							screen_coords.y + marker_size,
+							screen_coords.y + screen_coords.h - marker_size,
+						),
+					);
+					labels.push((marker, spot_distance.magnitude(), color));
+					marker
+				};
+
+				mb.circle(DrawMode::stroke(2.0), marker, marker_size * 0.5, 0.5, color)?;
+				if self.target_spot == Some(idx)
+					&& logic::glm::distance2(&player_loc_sc.coords, &marker.coords) > 1.0
+				{
+					mb.line(&[player_loc_sc, marker], TARGET_HARBOR_LINE_WIDTH, color)?;
+				}
+			}
+			let mesh = mb.build(ctx, quad_ctx)?;
+			draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+
+			for (marker, distance, color) in labels {
+				let mut text = Text::new(format!("{}m", distance.round()));
+				text.set_font(Default::default(), self.font_size(FONT_SIZE_SMALL));
+				graphics::draw(
+					ctx,
+					quad_ctx,
+					&text,
+					(
+						Point2::new(
+							marker.x - text.width(ctx) * 0.5,
+							marker.y + marker_size * 0.5,
+						),
+						color,
+					),
+				)?;
+			}
+		}
+
+		// -- Next race checkpoint --
+		if let Some(checkpoint) = self
+			.world
+			.state
+			.race
+			.as_ref()
+			.filter(|race| race.outcome().is_none())
+			.and_then(|race| race.next_checkpoint())
+		{
+			let player_loc_sc = nalgebra::Point2::new(screen_coords.w, screen_coords.h) * 0.5;
+			let checkpoint_distance = self
+				.world
+				.init
+				.terrain
+				.torus_distance(player_loc, checkpoint);
+			let checkpoint_sc = nalgebra::Point2::from(
+				self.world_to_screen_offset(ctx, checkpoint_distance.0) + player_loc_sc.coords,
+			);
+
+			// On screen, the ring shows the actual extent of the checkpoint,
+			// otherwise a marker sits at the edge in its direction
+			let (marker, radius) = if screen_coords.contains(checkpoint_sc) {
+				(
+					Some(checkpoint_sc),
+					CHECKPOINT_SIZE * 0.5 * self.pixel_per_meter(ctx),
+				)
+			} else {
+				let marker_size = CHECKPOINT_MARKER_SIZE * self.ui_scale;
+				let marker =
+					math::ray_exit(screen_coords, player_loc_sc, checkpoint_sc - player_loc_sc)
+						.map(|point| {
+							nalgebra::Point2::new(
+								point.x.clamp(
+									screen_coords.x + marker_size,
+									screen_coords.x + screen_coords.w - marker_size,
+								),
+								point.y.clamp(
+									screen_coords.y + marker_size,
+									screen_coords.y + screen_coords.h - marker_size,
+								),
+							)
+						});
+				(marker, marker_size * 0.5)
+			};
+
+			if let Some(marker) = marker {
+				let mut mb = MeshBuilder::new();
+				mb.circle(DrawMode::stroke(3.0), marker, radius, 0.5, CHECKPOINT_COLOR)?;
+				if logic::glm::distance2(&player_loc_sc.coords, &marker.coords) > 1.0 {
+					mb.line(
+						&[player_loc_sc, marker],
+						TARGET_HARBOR_LINE_WIDTH,
+						CHECKPOINT_COLOR,
+					)?;
+				}
+				let mesh = mb.build(ctx, quad_ctx)?;
+				draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+
+				let mut text = Text::new(format!("{}m", checkpoint_distance.magnitude().round()));
+				text.set_font(Default::default(), self.font_size(FONT_SIZE_SMALL));
+				graphics::draw(
+					ctx,
+					quad_ctx,
+					&text,
+					(
+						Point2::new(marker.x - text.width(ctx) * 0.5, marker.y + radius),
+						CHECKPOINT_COLOR,
+					),
+				)?;
+			}
 		}
 
 		// Fishy indicator
 		let p = DrawParam::new()
 			.dest(Point2::new(0.0, 0.0))
 			.offset(Point2::new(-0.25, -0.25))
-			.scale(logic::glm::vec2(0.5, 0.5));
+			.scale(logic::glm::vec1(0.5 * self.ui_scale).xx());
 		gwg::graphics::draw(ctx, quad_ctx, &self.images.ui.fishy_icon, p)?;
 
 		let mut fishy_text = Text::new(format!(
 			"{} kg",
 			self.world.state.player.vehicle.resource_weight
 		));
-		fishy_text.set_font(Default::default(), PxScale::from(32.0));
+		fishy_text.set_font(Default::default(), self.font_size(FONT_SIZE_HUGE));
 		let p = DrawParam::new()
 			.dest(Point2::new(
-				self.images.ui.fishy_icon.width() as f32 * 0.75,
-				self.images.ui.fishy_icon.height() as f32 * 0.75 * 0.5
+				self.images.ui.fishy_icon.width() as f32 * 0.75 * self.ui_scale,
+				self.images.ui.fishy_icon.height() as f32 * 0.75 * 0.5 * self.ui_scale
 					- fishy_text.height(ctx) as f32 * 0.5,
 			))
 			.color(Color::WHITE)
@@ -1983,25 +4046,173 @@ impl Game {
 		let p = DrawParam::new()
 			.dest(Point2::new(
 				0.0,
-				self.images.ui.fishy_icon.height() as f32 * 0.5,
+				self.images.ui.fishy_icon.height() as f32 * 0.5 * self.ui_scale,
 			))
 			.offset(Point2::new(-0.25, -0.25))
-			.scale(logic::glm::vec2(0.5, 0.5));
+			.scale(logic::glm::vec1(0.5 * self.ui_scale).xx());
 		gwg::graphics::draw(ctx, quad_ctx, &self.images.ui.money_icon, p)?;
 
 		let mut money_text = Text::new(format!("{} €", self.world.state.player.money));
-		money_text.set_font(Default::default(), PxScale::from(32.0));
+		money_text.set_font(Default::default(), self.font_size(FONT_SIZE_HUGE));
 		let p = DrawParam::new()
 			.dest(Point2::new(
-				self.images.ui.money_icon.width() as f32 * 0.75,
-				self.images.ui.fishy_icon.height() as f32 * 0.5
-					+ self.images.ui.money_icon.height() as f32 * 0.75 * 0.5
-					- fishy_text.height(ctx) as f32 * 0.5,
+				self.images.ui.money_icon.width() as f32 * 0.75 * self.ui_scale,
+				(self.images.ui.fishy_icon.height() as f32 * 0.5
+					+ self.images.ui.money_icon.height() as f32 * 0.75 * 0.5)
+					* self.ui_scale - fishy_text.height(ctx) as f32 * 0.5,
 			))
 			.color(Color::WHITE)
 			.offset(Point2::new(-0.5, -0.5));
 		self.draw_text_with_halo(ctx, quad_ctx, &money_text, p, Color::BLACK)?;
 
+		// Gear indicator, below the money
+		let vehicle = &self.world.state.player.vehicle;
+		let next_sail = vehicle
+			.sail
+			.kind
+			.upgrade()
+			.map(|s| format!("{:?} ({} €)", s, s.value()))
+			.unwrap_or_else(|| "maxed".to_owned());
+		let next_hull = vehicle
+			.hull
+			.upgrade()
+			.map(|h| format!("{:?} ({} €)", h, h.value()))
+			.unwrap_or_else(|| "maxed".to_owned());
+		let gear_lines = [
+			format!(
+				"Sail: {:?} {}/{}, {:.0} m², {:.0} %",
+				vehicle.sail.kind,
+				vehicle.sail.reefing.value(),
+				vehicle.sail.kind.max_reefing().value(),
+				vehicle.sail.sail_area(),
+				vehicle.sail.condition * 100.0
+			),
+			format!(
+				"Hull: {:?}, {:.0} %",
+				vehicle.hull,
+				vehicle.hull_integrity * 100.0
+			),
+			format!(
+				"Net: {}",
+				if vehicle.net_deployed {
+					"deployed"
+				} else {
+					"stowed"
+				}
+			),
+			if vehicle.resource_weight > 0 {
+				format!("Catch: {:.0} % fresh", vehicle.freshness * 100.0)
+			} else {
+				"Catch: none".to_owned()
+			},
+			match vehicle.magnet {
+				Magnet::Absent => "Magnet: none".to_owned(),
+				magnet => format!("Magnet: {:?}, {:.0} m", magnet, magnet.radius()),
+			},
+			format!("Next: sail {next_sail}, hull {next_hull}"),
+		];
+		let mut y_offset = (self.images.ui.fishy_icon.height() as f32 * 0.5
+			+ self.images.ui.money_icon.height() as f32 * 0.5
+			+ 8.0) * self.ui_scale;
+		for line in gear_lines {
+			let mut gear_text = Text::new(line);
+			gear_text.set_font(Default::default(), self.font_size(FONT_SIZE_NORMAL));
+			let p = DrawParam::new()
+				.dest(Point2::new(10.0 * self.ui_scale, y_offset))
+				.color(Color::WHITE);
+			self.draw_text_with_halo(ctx, quad_ctx, &gear_text, p, Color::BLACK)?;
+
+			y_offset += gear_text.height(ctx) * 1.2;
+		}
+
+		// Depth sounder, below the gear
+		let elevation = self.world.init.terrain.elevation_at(vehicle.pos);
+		let depth_color = if elevation.classify() == TileType::DeepWater {
+			Color::WHITE
+		} else {
+			DEPTH_SHALLOW_COLOR
+		};
+		let mut depth_text = Text::new(format!("Depth: {} m", -i32::from(elevation.0)));
+		depth_text.set_font(Default::default(), self.font_size(FONT_SIZE_NORMAL));
+		let p = DrawParam::new()
+			.dest(Point2::new(10.0 * self.ui_scale, y_offset))
+			.color(depth_color);
+		self.draw_text_with_halo(ctx, quad_ctx, &depth_text, p, Color::BLACK)?;
+		y_offset += depth_text.height(ctx) * 1.2;
+
+		// The progress of the race, right below
+		if let Some(race) = &self.world.state.race {
+			let line = match race.outcome() {
+				Some(RaceOutcome::Finished(_)) => "Race: finished".to_owned(),
+				Some(RaceOutcome::TimedOut(_)) => "Race: time is up".to_owned(),
+				None => {
+					let seconds = race.remaining() / u64::from(TICKS_PER_SECOND);
+					format!(
+						"Race: {}/{}, {:02}:{:02} left",
+						race.next,
+						race.checkpoints.len(),
+						seconds / 60,
+						seconds % 60
+					)
+				},
+			};
+			let mut race_text = Text::new(line);
+			race_text.set_font(Default::default(), self.font_size(FONT_SIZE_NORMAL));
+			let p = DrawParam::new()
+				.dest(Point2::new(10.0 * self.ui_scale, y_offset))
+				.color(CHECKPOINT_COLOR);
+			self.draw_text_with_halo(ctx, quad_ctx, &race_text, p, Color::BLACK)?;
+			y_offset += race_text.height(ctx) * 1.2;
+		}
+
+		// The player at the helm and the time left in the turn, right below
+		if let Some(hotseat) = self.hotseat.as_ref().filter(|hotseat| !hotseat.is_over()) {
+			let seconds =
+				hotseat.remaining(self.world.state.timestamp) / u64::from(TICKS_PER_SECOND);
+			let mut turn_text = Text::new(format!(
+				"Player {}: {:02}:{:02} left",
+				hotseat.player() + 1,
+				seconds / 60,
+				seconds % 60
+			));
+			turn_text.set_font(Default::default(), self.font_size(FONT_SIZE_NORMAL));
+			let p = DrawParam::new()
+				.dest(Point2::new(10.0 * self.ui_scale, y_offset))
+				.color(TARGET_HARBOR_COLOR);
+			self.draw_text_with_halo(ctx, quad_ctx, &turn_text, p, Color::BLACK)?;
+		}
+
+		// Notifications, right top corner, just below the FPS
+		let mut y_offset = 24.0 * self.ui_scale;
+		for notification in self.notifications.iter() {
+			let mut text = Text::new(notification.text.as_str());
+			text.set_font(Default::default(), self.font_size(FONT_SIZE_NOTIFICATION));
+			let p = DrawParam::new()
+				.dest(Point2::new(
+					screen_coords.w - text.width(ctx) - 10.0 * self.ui_scale,
+					y_offset,
+				))
+				.color(notification.color());
+			let mut halo_color = Color::BLACK;
+			halo_color.a = notification.color().a;
+			self.draw_text_with_halo(ctx, quad_ctx, &text, p, halo_color)?;
+
+			y_offset += text.height(ctx) * 1.2;
+		}
+
+		// Current tutorial objective, top center
+		if let Some(prompt) = self.tutorial.as_ref().and_then(|t| t.prompt()) {
+			let mut text = Text::new(prompt);
+			text.set_font(Default::default(), self.font_size(FONT_SIZE_LARGE));
+			let p = DrawParam::new()
+				.dest(Point2::new(
+					(screen_coords.w - text.width(ctx)) * 0.5,
+					64.0 * self.ui_scale,
+				))
+				.color(TUTORIAL_COLOR);
+			self.draw_text_with_halo(ctx, quad_ctx, &text, p, Color::BLACK)?;
+		}
+
 		let sail_progress = if self.is_sail_maxed() { 1 } else { 0 };
 		let hull_progress = if self.is_hull_maxed() { 1 } else { 0 };
 
@@ -2043,16 +4254,19 @@ impl Game {
 			};
 
 			let mut text = Text::new(name.to_owned());
-			text.set_font(Default::default(), PxScale::from(28.0));
+			text.set_font(Default::default(), self.font_size(FONT_SIZE_LARGE));
 
 			y_offset += text.height(ctx);
 
-			graphics::draw(
+			self.draw_plain_text(
 				ctx,
 				quad_ctx,
 				&text,
 				(
-					Point2::new(35.0, screen_coords.h - y_offset * 1.3 - 35.0),
+					Point2::new(
+						35.0 * self.ui_scale,
+						screen_coords.h - y_offset * 1.3 - 35.0 * self.ui_scale,
+					),
 					color,
 				),
 			)?;
@@ -2080,6 +4294,289 @@ impl Game {
 		self.world.state.player.vehicle.hull.upgrade().is_none()
 	}
 
+	/// The font size of a HUD text with the given base size in pixels
+	fn font_size(&self, base: f32) -> PxScale {
+		PxScale::from(base * self.ui_scale)
+	}
+
+	/// Saves the recorded trajectory, if requested, when leaving the game
+	///
+	/// Also exports the ghost file for sharing, if requested.
+	fn save_recording(&self) {
+		cfg_if! {
+			if #[cfg(not(target_family = "wasm"))] {
+				if let (Some(path), Some(recording)) = (&crate::OPTIONS.record, &self.recording) {
+					super::replay::save_trajectory(path, recording);
+				}
+				if let (Some(path), Some(run)) = (&crate::OPTIONS.export_ghost, self.shareable_run()) {
+					super::replay::export_ghost(path, &run);
+				}
+			}
+		}
+	}
+
+	/// The recorded run worth sharing as ghost, if any
+	///
+	/// In a race, this is only a finished race, ending at the finish line,
+	/// scored by its time. Outside of races, the voyage is scored by the money.
+	#[cfg(not(target_family = "wasm"))]
+	fn shareable_run(&self) -> Option<Ghost> {
+		let mut trajectory = self.recording.clone()?;
+		let score = match self.world.state.race.as_ref().map(|race| race.outcome()) {
+			None => Score::Voyage(self.world.state.player.money),
+			Some(Some(RaceOutcome::Finished(ticks))) => {
+				trajectory.truncate(Tick(trajectory.start.0 + ticks));
+				Score::Race(ticks)
+			},
+			Some(_) => return None,
+		};
+
+		Some(Ghost {
+			score,
+			trajectory,
+		})
+	}
+
+	/// Whether the keys are currently used to type into the cheat console
+	fn is_typing(&self) -> bool {
+		#[cfg(feature = "dev")]
+		if self.console.is_some() {
+			return true;
+		}
+		false
+	}
+
+	/// Try to upgrade or repair the ship's gear at the current harbor
+	fn upgrade_gear(&mut self, audios: &mut Audios, ctx: &mut gwg::Context, action: TradeAction) {
+		if let Some(mut t) = self.world.state.get_trading(&self.world.init) {
+			let (done, verb, part, res) = match action {
+				TradeAction::RepairSail => ("Repaired", "repair", "sail", t.repair_sail()),
+				TradeAction::RepairHull => ("Repaired", "repair", "hull", t.repair_hull()),
+				TradeAction::UpgradeSail => ("Upgraded", "upgrade", "sail", t.upgrade_sail()),
+				TradeAction::UpgradeHull => ("Upgraded", "upgrade", "hull", t.upgrade_hull()),
+				TradeAction::UpgradeMagnet => ("Upgraded", "upgrade", "magnet", t.upgrade_magnet()),
+				TradeAction::SellFish => return,
+			};
+
+			match res {
+				Ok(()) => {
+					// success
+					self.notifications
+						.push(format!("{done} the {part}!"), NOTIFICATION_COLOR);
+					if audios.sound_enabled {
+						audios.upgrade_sound.play(ctx).unwrap();
+					}
+				},
+				Err(e) => {
+					// Failed
+					println!("Failed to {verb} {part}: {e}");
+					self.notifications.push(
+						format!("Failed to {verb} {part}: {e}"),
+						NOTIFICATION_FAIL_COLOR,
+					);
+					if audios.sound_enabled {
+						audios.fail_sound.play(ctx).unwrap();
+					}
+				},
+			}
+		}
+	}
+
+	/// Draws the trading panel, if the player is at a harbor
+	fn draw_trading_panel(
+		&mut self,
+		ctx: &mut gwg::Context,
+		quad_ctx: &mut gwg::miniquad::Context,
+	) -> gwg::GameResult<()> {
+		let screen_coords = gwg::graphics::screen_coordinates(ctx);
+
+		let budget = self.world.state.player.money;
+		let value = self.world.state.player.vehicle.current_value();
+
+		// Collect the trading options first, because the `TradeOption`
+		// borrows the world state.
+		let (message, entries) = {
+			if let Some(t) = self.world.state.get_trading(&self.world.init) {
+				if t.has_player_valid_speed() {
+					// Trading is possible
+					let hull_upgrade = t.get_price_of_hull_upgrade();
+					let sail_upgrade = t.get_price_for_sail_upgrade();
+					let sail_repair = t.get_price_of_sail_repair();
+					let hull_repair = t.get_price_of_hull_repair();
+					let magnet_upgrade = t.get_price_of_magnet_upgrade();
+					let fish_amount = t.players_fish_amount();
+
+					let message = match (hull_upgrade, sail_upgrade, fish_amount) {
+						_ if sail_repair.is_some() => "Your sail is in tatters!",
+						_ if hull_repair.is_some() => "Your hull is leaking!",
+						(Some(hup), _, _) if budget >= hup => "Time to upgrade!",
+						(_, Some(sup), _) if budget >= sup => "Time to upgrade!",
+						(_, _, fam) if fam > 0 => "Fishy trade?",
+						_ => "Time to fish or cut bait!",
+					};
+
+					let sell = (
+						TradeAction::SellFish,
+						format!("E: Sell fish for {value} €"),
+						fish_amount > 0,
+					);
+					let repair = if let Some(price) = sail_repair {
+						(
+							TradeAction::RepairSail,
+							format!("V: Repair sail ({price} €)"),
+							budget >= price,
+						)
+					} else {
+						(
+							TradeAction::RepairSail,
+							"Your sail is intact!".to_owned(),
+							false,
+						)
+					};
+					let hull_fix = if let Some(price) = hull_repair {
+						(
+							TradeAction::RepairHull,
+							format!("H: Repair hull ({price} €)"),
+							budget >= price,
+						)
+					} else {
+						(
+							TradeAction::RepairHull,
+							"Your hull is intact!".to_owned(),
+							false,
+						)
+					};
+					let sail = if let Some(price) = sail_upgrade {
+						(
+							TradeAction::UpgradeSail,
+							format!("R: Upgrade sail ({price} €)"),
+							budget >= price,
+						)
+					} else {
+						(
+							TradeAction::UpgradeSail,
+							"Your sail is awesome!".to_owned(),
+							false,
+						)
+					};
+					let hull = if let Some(price) = hull_upgrade {
+						(
+							TradeAction::UpgradeHull,
+							format!("F: Upgrade hull ({price} €)"),
+							budget >= price,
+						)
+					} else {
+						(
+							TradeAction::UpgradeHull,
+							"Your hull is awesome!".to_owned(),
+							false,
+						)
+					};
+
+					let magnet = if let Some(price) = magnet_upgrade {
+						(
+							TradeAction::UpgradeMagnet,
+							format!("M: Upgrade magnet ({price} €)"),
+							budget >= price,
+						)
+					} else {
+						(
+							TradeAction::UpgradeMagnet,
+							"Your magnet is awesome!".to_owned(),
+							false,
+						)
+					};
+
+					(message, vec![sell, repair, hull_fix, sail, hull, magnet])
+				} else {
+					// Player is too fast for trading
+					let message = if t.players_fish_amount() > 0 {
+						"Slow down, sailor!"
+					} else {
+						"Time to fish or cut bait!"
+					};
+
+					(message, Vec::new())
+				}
+			} else {
+				// Not at a harbor
+				return Ok(());
+			}
+		};
+
+		// Type set all the texts
+		let mut title = Text::new(format!("\"{message}\""));
+		title.set_font(Default::default(), self.font_size(FONT_SIZE_HUGE));
+
+		let entry_texts: Vec<_> = entries
+			.into_iter()
+			.map(|(action, label, affordable)| {
+				let is_selected = action == self.trade_selection;
+				let marker = if is_selected { "> " } else { "  " };
+
+				let color = match (is_selected, affordable) {
+					(true, true) => TRADE_HIGHLIGHT_COLOR,
+					(false, true) => TRADE_TEXT_COLOR,
+					(_, false) => TRADE_INACTIVE_COLOR,
+				};
+
+				let mut text = Text::new(format!("{marker}{label}"));
+				text.set_font(Default::default(), self.font_size(FONT_SIZE_NORMAL));
+				(text, color)
+			})
+			.collect();
+
+		let content_height = title.height(ctx)
+			+ entry_texts
+				.iter()
+				.map(|(t, _)| t.height(ctx) * 1.3)
+				.sum::<f32>();
+		let panel_padding = TRADE_PANEL_PADDING * self.ui_scale;
+		let panel_width =
+			(TRADE_PANEL_WIDTH * self.ui_scale).max(title.width(ctx) + 2. * panel_padding);
+		let panel_height = content_height + 2. * panel_padding;
+
+		// The panel is fixed just above the bottom center of the screen
+		let panel = Rect::new(
+			(screen_coords.w - panel_width) * 0.5,
+			screen_coords.h - panel_height - 2. * panel_padding,
+			panel_width,
+			panel_height,
+		);
+
+		// Draw the panel background
+		let mesh = MeshBuilder::new()
+			.rectangle(DrawMode::fill(), panel, TRADE_PANEL_COLOR)?
+			.build(ctx, quad_ctx)?;
+		draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+
+		// Draw the heading
+		let mut y_offset = panel.y + panel_padding;
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&title,
+			(
+				Point2::new(panel.x + (panel.w - title.width(ctx)) * 0.5, y_offset),
+				TRADE_TEXT_COLOR,
+			),
+		)?;
+		y_offset += title.height(ctx);
+
+		// Draw the entries
+		for (text, color) in &entry_texts {
+			graphics::draw(
+				ctx,
+				quad_ctx,
+				text,
+				(Point2::new(panel.x + panel_padding, y_offset), *color),
+			)?;
+			y_offset += text.height(ctx) * 1.3;
+		}
+
+		Ok(())
+	}
+
 	fn toast_at_player(&mut self, message: impl ToString, color: Color) {
 		self.toasts.push(Toast::new(
 			message.to_string(),
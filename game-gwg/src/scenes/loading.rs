@@ -4,7 +4,12 @@ use good_web_game as gwg;
 use good_web_game::event::GraphicsContext;
 use good_web_game::goodies::scene::Scene;
 use good_web_game::goodies::scene::SceneSwitch;
+use good_web_game::graphics::Color;
+use good_web_game::graphics::DrawMode;
 use good_web_game::graphics::Font;
+use good_web_game::graphics::MeshBuilder;
+use good_web_game::graphics::Rect;
+use good_web_game::graphics::StrokeOptions;
 use good_web_game::graphics::Text;
 use good_web_game::graphics::{self,};
 use good_web_game::Context;
@@ -17,17 +22,31 @@ use crate::assets::audio::Audios;
 
 const DEFAULT_DELAY: u16 = 3;
 
+const PROGRESS_BAR_WIDTH: f32 = 300.;
+const PROGRESS_BAR_HEIGHT: f32 = 12.;
+const PROGRESS_BAR_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.8);
+
 
 /// A scene loader
 pub(super) trait Loadable {
 	type Target: Scene<GlobalState> + 'static;
 
+	/// Performs the next loading step
+	///
+	/// Returns the scene, once loading completed.
 	fn load(
-		&self,
+		&mut self,
 		glob: &mut GlobalState,
 		ctx: &mut Context,
 		quad_ctx: &mut GraphicsContext,
-	) -> Self::Target;
+	) -> Option<Self::Target>;
+
+	/// The progress of loading in range [0,1] along with what is loaded next
+	///
+	/// Returns `None`, if the progress is unknown.
+	fn progress(&self) -> Option<(f32, &str)> {
+		None
+	}
 }
 
 /// An `Fn` wrapper as scene loader
@@ -62,16 +81,19 @@ impl<
 	type Target = T;
 
 	fn load(
-		&self,
+		&mut self,
 		glob: &mut GlobalState,
 		ctx: &mut Context,
 		quad_ctx: &mut GraphicsContext,
-	) -> Self::Target {
-		(self.f)(glob, ctx, quad_ctx)
+	) -> Option<Self::Target> {
+		Some((self.f)(glob, ctx, quad_ctx))
 	}
 }
 
 /// Loads the given scene after a short delay.
+///
+/// The loading may take several steps, one per frame, during which the progress
+/// is shown.
 pub struct Loading<S> {
 	loadable: S,
 	delay: u16,
@@ -100,7 +122,10 @@ impl<S: Loadable> Scene<GlobalState> for Loading<S> {
 		quad_ctx: &mut GraphicsContext,
 	) -> SceneSwitch<GlobalState> {
 		if self.delay == 0 {
-			SceneSwitch::Replace(Box::new(self.loadable.load(glob, ctx, quad_ctx)))
+			match self.loadable.load(glob, ctx, quad_ctx) {
+				Some(scene) => SceneSwitch::Replace(Box::new(scene)),
+				None => SceneSwitch::None,
+			}
 		} else {
 			if self.delay == 1 {
 				if glob.audios.is_none() {
@@ -142,6 +167,7 @@ impl<S: Loadable> Scene<GlobalState> for Loading<S> {
 		let mut loading = Text::new("Loading ...");
 		loading.set_font(Font::default(), (2. * Font::DEFAULT_FONT_SCALE).into());
 		loading.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		let loading_height = loading.dimensions(ctx).h;
 		graphics::draw(
 			ctx,
 			quad_ctx,
@@ -149,6 +175,41 @@ impl<S: Loadable> Scene<GlobalState> for Loading<S> {
 			(Point2::new(0., size.1 / 2. + Font::DEFAULT_FONT_SCALE),),
 		)?;
 
+		if let Some((progress, description)) = self.loadable.progress() {
+			let top = size.1 / 2. + 2. * Font::DEFAULT_FONT_SCALE + loading_height;
+			let frame = Rect::new(
+				(size.0 - PROGRESS_BAR_WIDTH) / 2.,
+				top,
+				PROGRESS_BAR_WIDTH,
+				PROGRESS_BAR_HEIGHT,
+			);
+			let bar = Rect {
+				w: frame.w * progress.clamp(0., 1.),
+				..frame
+			};
+			let mesh = MeshBuilder::new()
+				.rectangle(DrawMode::fill(), bar, PROGRESS_BAR_COLOR)?
+				.rectangle(
+					DrawMode::Stroke(StrokeOptions::DEFAULT),
+					frame,
+					PROGRESS_BAR_COLOR,
+				)?
+				.build(ctx, quad_ctx)?;
+			graphics::draw(ctx, quad_ctx, &mesh, (Point2::new(0., 0.),))?;
+
+			let mut step = Text::new(description);
+			step.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+			graphics::draw(
+				ctx,
+				quad_ctx,
+				&step,
+				(Point2::new(
+					0.,
+					top + PROGRESS_BAR_HEIGHT + Font::DEFAULT_FONT_SCALE,
+				),),
+			)?;
+		}
+
 		// Finally, issue the draw call and what not, finishing this frame for good
 		graphics::present(ctx, quad_ctx)?;
 
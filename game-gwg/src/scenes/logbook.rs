@@ -0,0 +1,259 @@
+//! The logbook of notable events across all voyages
+//!
+
+use good_web_game as gwg;
+use good_web_game::event::GraphicsContext;
+use good_web_game::goodies::scene::Scene;
+use good_web_game::goodies::scene::SceneSwitch;
+use good_web_game::graphics;
+use good_web_game::graphics::Font;
+use good_web_game::graphics::Text;
+use good_web_game::Context;
+use good_web_game::GameResult;
+use logic::units::Tick;
+use logic::TICKS_PER_SECOND;
+use miniquad::KeyCode;
+use nalgebra::Point2;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::main_menu::BUTTON_COLOR;
+use super::main_menu::TEXT_COLOR;
+use super::profile;
+use super::GlobalState;
+
+
+/// The number of entries shown at once on the logbook screen
+const VISIBLE_ENTRIES: usize = 12;
+
+
+/// A single timestamped line of the logbook
+#[derive(Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize)]
+pub struct LogEntry {
+	/// The number of the voyage, starting at `1`
+	pub voyage: u32,
+	/// The point in time within the voyage
+	pub tick: Tick,
+	pub text: String,
+}
+
+impl LogEntry {
+	/// The entry as a line, e.g. "Voyage 3, 02:15: First sale"
+	fn line(&self) -> String {
+		let seconds = self.tick.0 / u64::from(TICKS_PER_SECOND);
+		format!(
+			"Voyage {}, {:02}:{:02}: {}",
+			self.voyage,
+			seconds / 60,
+			seconds % 60,
+			self.text
+		)
+	}
+}
+
+/// The best and worst so far, across all voyages
+#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Records {
+	/// The biggest catch within a single tick in kg
+	pub biggest_catch: u32,
+	/// The fastest collision in m/s
+	pub worst_collision: f32,
+}
+
+/// The logbook, persisted in the profile of the player
+#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Logbook {
+	/// The number of voyages started so far
+	pub voyages: u32,
+	pub records: Records,
+	/// Whether fish has been sold during the current voyage
+	#[serde(skip)]
+	sold_fish: bool,
+	/// All entries, oldest first
+	pub entries: Vec<LogEntry>,
+}
+
+impl Logbook {
+	/// Begins the next voyage
+	pub fn start_voyage(&mut self) {
+		self.voyages += 1;
+		self.sold_fish = false;
+	}
+
+	fn append(&mut self, tick: Tick, text: String) {
+		self.entries.push(LogEntry {
+			voyage: self.voyages,
+			tick,
+			text,
+		});
+		self.save();
+	}
+
+	/// Notes the weight in kg caught in a single tick, if it is a new record
+	pub fn note_catch(&mut self, tick: Tick, weight: u32) {
+		if weight > self.records.biggest_catch {
+			self.records.biggest_catch = weight;
+			self.append(tick, format!("Biggest catch so far: {weight} kg"));
+		}
+	}
+
+	/// Notes a collision at the given speed in m/s, if it is a new record
+	pub fn note_collision(&mut self, tick: Tick, speed: f32) {
+		if speed > self.records.worst_collision {
+			self.records.worst_collision = speed;
+			self.append(tick, format!("Worst collision so far at {speed:.1} m/s"));
+		}
+	}
+
+	/// Notes fish sold at a harbor, if it is the first sale of this voyage
+	pub fn note_sale(&mut self, tick: Tick) {
+		if !self.sold_fish {
+			self.sold_fish = true;
+			self.append(tick, "Sold the first fish of this voyage".to_owned());
+		}
+	}
+
+	/// Loads the logbook from the profile, or starts a fresh one
+	pub fn load() -> Self {
+		profile::load("logbook")
+	}
+
+	/// Stores the logbook in the profile
+	pub fn save(&self) {
+		profile::save("logbook", self)
+	}
+}
+
+
+/// The screen showing the logbook, newest entries first
+pub struct LogbookScreen {
+	logbook: Logbook,
+	/// The number of newest entries scrolled past
+	scroll: usize,
+	/// Indicates that the screen shall be closed
+	closing: bool,
+}
+
+impl LogbookScreen {
+	pub fn new() -> Self {
+		Self {
+			logbook: Logbook::load(),
+			scroll: 0,
+			closing: false,
+		}
+	}
+}
+
+impl Scene<GlobalState> for LogbookScreen {
+	fn update(
+		&mut self,
+		_glob: &mut GlobalState,
+		_ctx: &mut Context,
+		_quad_ctx: &mut GraphicsContext,
+	) -> SceneSwitch<GlobalState> {
+		if self.closing {
+			SceneSwitch::Pop
+		} else {
+			SceneSwitch::None
+		}
+	}
+
+	fn draw(
+		&mut self,
+		_glob: &mut GlobalState,
+		ctx: &mut Context,
+		quad_ctx: &mut GraphicsContext,
+	) -> GameResult<()> {
+		let size = graphics::drawable_size(quad_ctx);
+
+		graphics::clear(ctx, quad_ctx, [0.0, 0.0, 0.0, 1.0].into());
+
+		let mut heading = Text::new("Logbook");
+		heading.set_font(Font::default(), (3. * Font::DEFAULT_FONT_SCALE).into());
+		heading.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&heading,
+			(Point2::new(0., 2. * Font::DEFAULT_FONT_SCALE), TEXT_COLOR),
+		)?;
+
+		let mut top = 6. * Font::DEFAULT_FONT_SCALE;
+		if self.logbook.entries.is_empty() {
+			let mut text = Text::new("Nothing noteworthy yet, set sail!");
+			text.set_font(Font::default(), (1.5 * Font::DEFAULT_FONT_SCALE).into());
+			text.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+			graphics::draw(ctx, quad_ctx, &text, (Point2::new(0., top), TEXT_COLOR))?;
+		}
+		for entry in self
+			.logbook
+			.entries
+			.iter()
+			.rev()
+			.skip(self.scroll)
+			.take(VISIBLE_ENTRIES)
+		{
+			let mut text = Text::new(entry.line());
+			text.set_font(Font::default(), (1.5 * Font::DEFAULT_FONT_SCALE).into());
+			text.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+			graphics::draw(ctx, quad_ctx, &text, (Point2::new(0., top), TEXT_COLOR))?;
+			top += text.height(ctx) + 0.5 * Font::DEFAULT_FONT_SCALE;
+		}
+
+		let mut hint = Text::new("Up/Down to scroll, Esc to close");
+		hint.set_font(Font::default(), (1. * Font::DEFAULT_FONT_SCALE).into());
+		hint.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&hint,
+			(
+				Point2::new(0., size.1 - 2. * Font::DEFAULT_FONT_SCALE),
+				BUTTON_COLOR,
+			),
+		)?;
+
+		// Finally, issue the draw call and what not, finishing this frame for good
+		graphics::present(ctx, quad_ctx)?;
+
+		Ok(())
+	}
+
+	fn key_down_event(
+		&mut self,
+		_glob: &mut GlobalState,
+		_ctx: &mut Context,
+		_quad_ctx: &mut GraphicsContext,
+		key: KeyCode,
+	) {
+		let max_scroll = self.logbook.entries.len().saturating_sub(VISIBLE_ENTRIES);
+		match key {
+			KeyCode::Up | KeyCode::W => self.scroll = self.scroll.saturating_sub(1),
+			KeyCode::Down | KeyCode::S => self.scroll = (self.scroll + 1).min(max_scroll),
+			KeyCode::Escape | KeyCode::L | KeyCode::Enter => self.closing = true,
+			_ => {},
+		}
+	}
+
+	fn name(&self) -> &str {
+		"Logbook"
+	}
+
+	fn resize_event(
+		&mut self,
+		_glob: &mut GlobalState,
+		ctx: &mut gwg::Context,
+		_quad_ctx: &mut gwg::miniquad::GraphicsContext,
+		w: f32,
+		h: f32,
+	) {
+		let coordinates = graphics::Rect::new(0., 0., w, h);
+
+		graphics::set_screen_coordinates(ctx, coordinates).expect("Can't resize the window");
+	}
+}
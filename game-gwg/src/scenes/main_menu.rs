@@ -16,15 +16,17 @@ use miniquad::KeyCode;
 use nalgebra::Point2;
 use nalgebra::Vector2;
 
-use super::loading::LoadableFn;
 use super::loading::Loading;
+use super::logbook::LogbookScreen;
+use super::GameLoader;
 use super::GlobalState;
+use super::SeedChoice;
 use crate::draw_version;
 
 
 
-const TEXT_COLOR: Color = Color::new(0.769, 0.769, 0.769, 1.0); // #c4c4c4
-const BUTTON_COLOR: Color = Color::new(0.282, 0.424, 0.557, 1.0); // #486c8e
+pub(super) const TEXT_COLOR: Color = Color::new(0.769, 0.769, 0.769, 1.0); // #c4c4c4
+pub(super) const BUTTON_COLOR: Color = Color::new(0.282, 0.424, 0.557, 1.0); // #486c8e
 const VERSION_COLOR: Color = Color::new(0.192, 0.122, 0.373, 1.0); // #311f5f
 
 
@@ -35,6 +37,10 @@ pub struct MainMenu {
 
 	/// Indicates that the game shall begin
 	lets_continue: bool,
+	/// The seed being typed in, if the seed field is focused
+	seed_field: Option<String>,
+	/// Indicates that the logbook shall be shown
+	show_logbook: bool,
 }
 
 impl MainMenu {
@@ -56,6 +62,8 @@ impl MainMenu {
 			bg,
 			key_bg,
 			lets_continue: crate::OPTIONS.start,
+			seed_field: None,
+			show_logbook: false,
 		})
 	}
 
@@ -123,7 +131,10 @@ impl Scene<GlobalState> for MainMenu {
 	) -> SceneSwitch<GlobalState> {
 		if self.lets_continue {
 			self.lets_continue = false;
-			SceneSwitch::Push(Box::new(Loading::from(LoadableFn::new(super::start_game))))
+			SceneSwitch::Push(Box::new(Loading::from(GameLoader::new())))
+		} else if self.show_logbook {
+			self.show_logbook = false;
+			SceneSwitch::Push(Box::new(LogbookScreen::new()))
 		} else {
 			SceneSwitch::None
 		}
@@ -131,7 +142,7 @@ impl Scene<GlobalState> for MainMenu {
 
 	fn draw(
 		&mut self,
-		_glob: &mut GlobalState,
+		glob: &mut GlobalState,
 		ctx: &mut Context,
 		quad_ctx: &mut GraphicsContext,
 	) -> GameResult<()> {
@@ -186,7 +197,9 @@ impl Scene<GlobalState> for MainMenu {
 
 		// Print version info
 		let mut height = draw_version(ctx, quad_ctx, VERSION_COLOR)?;
-		let full_option_text_height = (2. + 1. + 2.) * Font::DEFAULT_FONT_SCALE;
+		let full_option_text_height =
+			(2. + 1. + 2. + 1. + 1. + 1. + 1. + 1. + 1. + 1. + 1. + 1. + 1.)
+				* Font::DEFAULT_FONT_SCALE;
 		if height + full_option_text_height + 2. * Font::DEFAULT_FONT_SCALE < size.1 / 3. {
 			height = size.1 / 3. - full_option_text_height;
 		} else {
@@ -212,6 +225,103 @@ impl Scene<GlobalState> for MainMenu {
 			}
 		}
 
+		// The difficulty selection
+		let mut difficulty = Text::new(format!("< Difficulty: {:?} >", glob.difficulty));
+		difficulty.set_font(Font::default(), (1. * Font::DEFAULT_FONT_SCALE).into());
+		difficulty.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		height += difficulty.height(ctx) + Font::DEFAULT_FONT_SCALE;
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&difficulty,
+			(Point2::new(0., size.1 - height), TEXT_COLOR),
+		)?;
+
+		// The fish abundance selection
+		let mut abundance = Text::new(format!(
+			"Fish: {:?}  (F to change)",
+			glob.settings.abundance
+		));
+		abundance.set_font(Font::default(), (1. * Font::DEFAULT_FONT_SCALE).into());
+		abundance.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		height += abundance.height(ctx) + Font::DEFAULT_FONT_SCALE;
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&abundance,
+			(Point2::new(0., size.1 - height), TEXT_COLOR),
+		)?;
+
+		// The camera toggle
+		let mut camera = Text::new(format!(
+			"Camera: {}  (C to toggle)",
+			if glob.settings.follow_heading {
+				"follow the ship"
+			} else {
+				"north up"
+			}
+		));
+		camera.set_font(Font::default(), (1. * Font::DEFAULT_FONT_SCALE).into());
+		camera.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		height += camera.height(ctx) + Font::DEFAULT_FONT_SCALE;
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&camera,
+			(Point2::new(0., size.1 - height), TEXT_COLOR),
+		)?;
+
+		// The auto-reef assist toggle
+		let mut auto_reef = Text::new(format!(
+			"Auto-reef assist: {}  (R to toggle)",
+			if glob.settings.auto_reef { "on" } else { "off" }
+		));
+		auto_reef.set_font(Font::default(), (1. * Font::DEFAULT_FONT_SCALE).into());
+		auto_reef.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		height += auto_reef.height(ctx) + Font::DEFAULT_FONT_SCALE;
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&auto_reef,
+			(Point2::new(0., size.1 - height), TEXT_COLOR),
+		)?;
+
+		// The logbook button
+		let mut logbook = Text::new("Press L to read the logbook");
+		logbook.set_font(Font::default(), (1. * Font::DEFAULT_FONT_SCALE).into());
+		logbook.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		height += logbook.height(ctx) + Font::DEFAULT_FONT_SCALE;
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&logbook,
+			(Point2::new(0., size.1 - height), TEXT_COLOR),
+		)?;
+
+		// The seed field
+		let seed_label = match (&self.seed_field, &glob.seed) {
+			(Some(typing), _) => format!("Seed: {typing}_  (Enter to confirm)"),
+			(None, SeedChoice::Random) => {
+				"Seed: random from the clock  (Tab to enter, N to reroll)".to_owned()
+			},
+			(None, SeedChoice::Given(seed)) => {
+				format!("Seed: {seed}, given  (Tab to change, N to reroll)")
+			},
+			(None, SeedChoice::Rerolled(seed)) => {
+				format!("Seed: #{seed}, rerolled  (Tab to enter, N to reroll)")
+			},
+		};
+		let mut seed = Text::new(seed_label);
+		seed.set_font(Font::default(), (1. * Font::DEFAULT_FONT_SCALE).into());
+		seed.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		height += seed.height(ctx) + Font::DEFAULT_FONT_SCALE;
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&seed,
+			(Point2::new(0., size.1 - height), TEXT_COLOR),
+		)?;
+
 		// The start button
 		let mut starting = Text::new("Press any key to start");
 		starting.set_font(Font::default(), (2. * Font::DEFAULT_FONT_SCALE).into());
@@ -235,20 +345,84 @@ impl Scene<GlobalState> for MainMenu {
 
 	fn key_down_event(
 		&mut self,
-		_gameworld: &mut GlobalState,
+		gameworld: &mut GlobalState,
 		ctx: &mut good_web_game::Context,
 		_quad_ctx: &mut miniquad::graphics::GraphicsContext,
 		key: good_web_game::event::KeyCode,
 	) {
+		// While the seed field is focused, the characters come via `text_input_event`
+		if let Some(field) = &mut self.seed_field {
+			match key {
+				KeyCode::Enter | KeyCode::KpEnter => {
+					let seed = std::mem::take(field);
+					if seed.is_empty() {
+						// Back to random seeds, also on the next runs
+						gameworld.seed = SeedChoice::Random;
+						gameworld.settings.rerolled_seed = None;
+						gameworld.settings.save();
+					} else {
+						gameworld.seed = SeedChoice::Given(seed);
+					}
+					self.seed_field = None;
+				},
+				KeyCode::Escape => self.seed_field = None,
+				KeyCode::Backspace => {
+					field.pop();
+				},
+				_ => {},
+			}
+			return;
+		}
+
 		if key == KeyCode::Escape {
 			if cfg!(not(target_family = "wasm")) {
 				good_web_game::event::quit(ctx);
 			}
+		} else if key == KeyCode::Left || key == KeyCode::Right {
+			gameworld.difficulty = gameworld.difficulty.toggle();
+		} else if key == KeyCode::Tab {
+			let typed = match &gameworld.seed {
+				SeedChoice::Given(seed) => seed.clone(),
+				SeedChoice::Random | SeedChoice::Rerolled(_) => String::new(),
+			};
+			self.seed_field = Some(typed);
+		} else if key == KeyCode::N {
+			// Roll a new world and set sail right away, keeping the seed for trying again
+			let rolled = SeedChoice::roll();
+			gameworld.seed = SeedChoice::Rerolled(rolled);
+			gameworld.settings.rerolled_seed = Some(rolled);
+			gameworld.settings.save();
+			self.lets_continue = true;
+		} else if key == KeyCode::L {
+			self.show_logbook = true;
+		} else if key == KeyCode::F {
+			gameworld.settings.abundance = gameworld.settings.abundance.next();
+			gameworld.settings.save();
+		} else if key == KeyCode::C {
+			gameworld.settings.follow_heading = !gameworld.settings.follow_heading;
+			gameworld.settings.save();
+		} else if key == KeyCode::R {
+			gameworld.settings.auto_reef = !gameworld.settings.auto_reef;
+			gameworld.settings.save();
 		} else {
 			self.lets_continue = true;
 		}
 	}
 
+	fn text_input_event(
+		&mut self,
+		_glob: &mut GlobalState,
+		_ctx: &mut good_web_game::Context,
+		_quad_ctx: &mut miniquad::graphics::GraphicsContext,
+		character: char,
+	) {
+		if let Some(field) = &mut self.seed_field {
+			if !character.is_control() {
+				field.push(character);
+			}
+		}
+	}
+
 	fn name(&self) -> &str {
 		"Main Menu"
 	}
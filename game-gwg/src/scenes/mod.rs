@@ -1,6 +1,24 @@
+mod background;
+mod bookmarks;
+#[cfg(feature = "dev")]
+mod console;
+mod haptics;
+mod hotseat;
+mod hotseat_results;
 mod in_game;
 mod loading;
+mod logbook;
 mod main_menu;
+mod pause_menu;
+mod profile;
+mod race_results;
+#[cfg(not(target_family = "wasm"))]
+mod replay;
+mod settings;
+#[cfg(not(target_family = "wasm"))]
+mod telemetry;
+mod tick_budget;
+mod tutorial;
 
 
 use good_web_game::event;
@@ -8,26 +26,73 @@ use good_web_game::event::EventHandler;
 use good_web_game::goodies::scene::SceneStack;
 use good_web_game::Context;
 use good_web_game::GameError;
-pub use in_game::Game;
+pub use in_game::GameLoader;
+use rand::RngCore;
+use rand::SeedableRng;
 
+use self::haptics::Haptics;
 use self::loading::LoadableFn;
 use self::loading::Loading;
 use crate::assets::audio::Audios;
 use crate::scenes::main_menu::MainMenu;
+use crate::scenes::pause_menu::PauseAction;
+use crate::scenes::settings::Settings;
+use crate::Difficulty;
 
 
 /// Some global state (between the scenes)
 struct GlobalState {
 	audios: Option<Audios>,
+	/// The force feedback of the game controllers
+	haptics: Haptics,
+	/// The difficulty preset for the next game
+	difficulty: Difficulty,
+	/// The settings chosen in the main menu
+	settings: Settings,
+	/// The seed of the next game
+	seed: SeedChoice,
+	/// The action chosen in the pause menu, to be carried out by the game
+	pause_action: Option<PauseAction>,
 }
 
-fn start_game(
-	glob: &mut GlobalState,
-	ctx: &mut Context,
-	quad_ctx: &mut event::GraphicsContext,
-) -> Game {
-	Game::new(glob, ctx, quad_ctx).unwrap()
+/// The seed of the next game, along with where it comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SeedChoice {
+	/// A random seed, taken from the clock when the world is generated
+	Random,
+	/// A seed string given by the player, via `--seed` or the main menu
+	Given(String),
+	/// A random seed rolled in the main menu, kept in `Settings::rerolled_seed`
+	Rerolled(u64),
+}
+
+impl SeedChoice {
+	/// The initial choice, preferring `--seed` over the last reroll
+	fn initial(settings: &Settings) -> Self {
+		match (&crate::OPTIONS.seed, settings.rerolled_seed) {
+			(Some(seed), _) => Self::Given(seed.clone()),
+			(None, Some(seed)) => Self::Rerolled(seed),
+			(None, None) => Self::Random,
+		}
+	}
+
+	/// Rolls a fresh random seed, for `Self::Rerolled`
+	fn roll() -> u64 {
+		let mut rng = wyhash::WyRng::seed_from_u64((good_web_game::timer::time() * 1000.) as u64);
+		// Only 63 bits, because TOML can not store larger integers in the settings
+		rng.next_u64() >> 1
+	}
+
+	/// The seed of the world to generate
+	fn seed(&self) -> u64 {
+		match self {
+			Self::Random => good_web_game::timer::time().floor() as u64,
+			Self::Given(seed) => wyhash::wyhash(seed.as_bytes(), 0),
+			Self::Rerolled(seed) => *seed,
+		}
+	}
 }
+
 fn start_main_menu(
 	glob: &mut GlobalState,
 	ctx: &mut Context,
@@ -53,10 +118,26 @@ pub fn create_stack(
 	ctx: &mut Context,
 	_quad_ctx: &mut miniquad::Context,
 ) -> impl EventHandler<GameError> {
+	let mut settings = Settings::load();
+	settings.auto_reef |= crate::OPTIONS.auto_reef;
+	if let Some(scale) = crate::OPTIONS.ui_scale {
+		if !settings.set_ui_scale(scale) {
+			println!(
+				"[settings] the UI scale {scale} is not supported, using {} instead",
+				settings.ui_scale
+			);
+		}
+		settings.save();
+	}
 	let mut stack = SceneStack::new(
 		ctx,
 		GlobalState {
 			audios: None,
+			haptics: Haptics::new(),
+			difficulty: crate::OPTIONS.difficulty,
+			seed: SeedChoice::initial(&settings),
+			settings,
+			pause_action: None,
 		},
 	);
 
@@ -0,0 +1,173 @@
+//! The pause menu of a running game
+//!
+
+use good_web_game as gwg;
+use good_web_game::event::GraphicsContext;
+use good_web_game::goodies::scene::Scene;
+use good_web_game::goodies::scene::SceneSwitch;
+use good_web_game::graphics;
+use good_web_game::graphics::Color;
+use good_web_game::graphics::Font;
+use good_web_game::graphics::Text;
+use good_web_game::Context;
+use good_web_game::GameResult;
+use miniquad::KeyCode;
+use nalgebra::Point2;
+use strum::IntoEnumIterator;
+
+use super::main_menu::BUTTON_COLOR;
+use super::main_menu::TEXT_COLOR;
+use super::GlobalState;
+
+
+const BACKGROUND_COLOR: Color = Color::new(0.192, 0.122, 0.373, 1.0); // #311f5f
+
+
+/// The options of the pause menu
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(strum::EnumIter)]
+pub enum PauseAction {
+	Resume,
+	MainMenu,
+	/// Quitting does not work on WASM, thus it is not offered there
+	#[cfg(not(target_family = "wasm"))]
+	Quit,
+}
+
+impl PauseAction {
+	fn label(self) -> &'static str {
+		match self {
+			Self::Resume => "Resume",
+			Self::MainMenu => "Return to Main Menu",
+			#[cfg(not(target_family = "wasm"))]
+			Self::Quit => "Quit",
+		}
+	}
+
+	fn next(self) -> Self {
+		Self::iter()
+			.skip_while(|&a| a != self)
+			.nth(1)
+			.unwrap_or(self)
+	}
+
+	fn prev(self) -> Self {
+		Self::iter()
+			.take_while(|&a| a != self)
+			.last()
+			.unwrap_or(self)
+	}
+}
+
+
+/// The pause menu, pushed on top of the running game
+///
+/// Closing it puts the chosen action into the global state, so the game
+/// can carry it out (e.g. saving the recorded trajectory before leaving).
+pub struct PauseMenu {
+	selection: PauseAction,
+	/// The confirmed action, closing the menu
+	chosen: Option<PauseAction>,
+}
+
+impl PauseMenu {
+	pub fn new() -> Self {
+		Self {
+			selection: PauseAction::Resume,
+			chosen: None,
+		}
+	}
+}
+
+impl Scene<GlobalState> for PauseMenu {
+	fn update(
+		&mut self,
+		glob: &mut GlobalState,
+		_ctx: &mut Context,
+		_quad_ctx: &mut GraphicsContext,
+	) -> SceneSwitch<GlobalState> {
+		match self.chosen.take() {
+			Some(action) => {
+				glob.pause_action = Some(action);
+				SceneSwitch::Pop
+			},
+			None => SceneSwitch::None,
+		}
+	}
+
+	fn draw(
+		&mut self,
+		_glob: &mut GlobalState,
+		ctx: &mut Context,
+		quad_ctx: &mut GraphicsContext,
+	) -> GameResult<()> {
+		let size = graphics::drawable_size(quad_ctx);
+
+		graphics::clear(ctx, quad_ctx, BACKGROUND_COLOR);
+
+		let mut heading = Text::new("Paused");
+		heading.set_font(Font::default(), (3. * Font::DEFAULT_FONT_SCALE).into());
+		heading.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&heading,
+			(Point2::new(0., size.1 / 3.), TEXT_COLOR),
+		)?;
+
+		// The options, the selected one is highlighted
+		let mut top = size.1 / 2.;
+		for action in PauseAction::iter() {
+			let (label, color) = if action == self.selection {
+				(format!("> {} <", action.label()), TEXT_COLOR)
+			} else {
+				(action.label().to_owned(), BUTTON_COLOR)
+			};
+			let mut option = Text::new(label);
+			option.set_font(Font::default(), (2. * Font::DEFAULT_FONT_SCALE).into());
+			option.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+			graphics::draw(ctx, quad_ctx, &option, (Point2::new(0., top), color))?;
+			top += option.height(ctx) + Font::DEFAULT_FONT_SCALE;
+		}
+
+		// Finally, issue the draw call and what not, finishing this frame for good
+		graphics::present(ctx, quad_ctx)?;
+
+		Ok(())
+	}
+
+	fn key_down_event(
+		&mut self,
+		_glob: &mut GlobalState,
+		_ctx: &mut Context,
+		_quad_ctx: &mut GraphicsContext,
+		key: KeyCode,
+	) {
+		match key {
+			KeyCode::Up | KeyCode::W => self.selection = self.selection.prev(),
+			KeyCode::Down | KeyCode::S => self.selection = self.selection.next(),
+			KeyCode::Enter | KeyCode::KpEnter | KeyCode::Space => {
+				self.chosen = Some(self.selection);
+			},
+			KeyCode::Escape => self.chosen = Some(PauseAction::Resume),
+			_ => {},
+		}
+	}
+
+	fn name(&self) -> &str {
+		"Pause Menu"
+	}
+
+	fn resize_event(
+		&mut self,
+		_glob: &mut GlobalState,
+		ctx: &mut gwg::Context,
+		_quad_ctx: &mut gwg::miniquad::GraphicsContext,
+		w: f32,
+		h: f32,
+	) {
+		let coordinates = graphics::Rect::new(0., 0., w, h);
+
+		graphics::set_screen_coordinates(ctx, coordinates).expect("Can't resize the window");
+	}
+}
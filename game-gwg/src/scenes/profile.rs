@@ -0,0 +1,67 @@
+//! The profile of the player, kept in files in the home directory
+//!
+//! Each part of the profile is a TOML file of its own, named after it, e.g.
+//! `.plenty-of-fish-settings.toml` for the part named `settings`.
+//! There is no profile on the web, thus nothing is ever loaded nor stored.
+//!
+
+use cfg_if::cfg_if;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+
+/// Loads the part of the profile of the given `name`, or starts with the defaults
+pub fn load<T: DeserializeOwned + Default>(name: &str) -> T {
+	cfg_if! {
+		if #[cfg(not(target_family = "wasm"))] {
+			let content = path(&file_name(name)).and_then(|path| std::fs::read_to_string(path).ok());
+			match content.map(|content| toml::from_str(&content)) {
+				Some(Ok(part)) => part,
+				Some(Err(err)) => {
+					println!("[{name}] failed to parse the {name}: {err}");
+					T::default()
+				},
+				None => T::default(),
+			}
+		} else {
+			let _ = name;
+			T::default()
+		}
+	}
+}
+
+/// Stores the part of the profile of the given `name`
+pub fn save<T: Serialize>(name: &str, part: &T) {
+	cfg_if! {
+		if #[cfg(not(target_family = "wasm"))] {
+			let path = match path(&file_name(name)) {
+				Some(path) => path,
+				None => return,
+			};
+			let result = toml::to_string(part)
+				.map_err(|err| err.to_string())
+				.and_then(|content| std::fs::write(path, content).map_err(|err| err.to_string()));
+			if let Err(err) = result {
+				println!("[{name}] failed to save the {name}: {err}");
+			}
+		} else {
+			let _ = (name, part);
+		}
+	}
+}
+
+cfg_if! {
+	if #[cfg(not(target_family = "wasm"))] {
+		/// The path of the file of the given name in the home directory
+		pub fn path(file_name: &str) -> Option<std::path::PathBuf> {
+			std::env::var_os("HOME")
+				.or_else(|| std::env::var_os("USERPROFILE"))
+				.map(|home| std::path::Path::new(&home).join(file_name))
+		}
+
+		/// The name of the file of the part of the profile of the given `name`
+		fn file_name(name: &str) -> String {
+			format!(".plenty-of-fish-{name}.toml")
+		}
+	}
+}
@@ -0,0 +1,161 @@
+//! The results of a race against the clock
+//!
+
+use good_web_game as gwg;
+use good_web_game::event::GraphicsContext;
+use good_web_game::goodies::scene::Scene;
+use good_web_game::goodies::scene::SceneSwitch;
+use good_web_game::graphics;
+use good_web_game::graphics::Font;
+use good_web_game::graphics::Text;
+use good_web_game::Context;
+use good_web_game::GameResult;
+use logic::race::RaceOutcome;
+use logic::TICKS_PER_SECOND;
+use miniquad::KeyCode;
+use nalgebra::Point2;
+
+use super::main_menu::BUTTON_COLOR;
+use super::main_menu::TEXT_COLOR;
+use super::GlobalState;
+
+
+
+/// The screen showing the results of a race, pushed on top of the game
+pub struct RaceResults {
+	outcome: RaceOutcome,
+	/// The number of checkpoints of the race
+	checkpoints: usize,
+	/// Indicates that the screen shall be closed
+	closing: bool,
+}
+
+impl RaceResults {
+	pub fn new(outcome: RaceOutcome, checkpoints: usize) -> Self {
+		Self {
+			outcome,
+			checkpoints,
+			closing: false,
+		}
+	}
+
+	/// The heading and the summary line of the results
+	fn lines(&self) -> (&'static str, String) {
+		match self.outcome {
+			RaceOutcome::Finished(ticks) => {
+				let hundredths = ticks * 100 / u64::from(TICKS_PER_SECOND);
+				let seconds = hundredths / 100;
+				(
+					"Race finished!",
+					format!(
+						"All {} checkpoints in {:02}:{:02}.{:02}",
+						self.checkpoints,
+						seconds / 60,
+						seconds % 60,
+						hundredths % 100
+					),
+				)
+			},
+			RaceOutcome::TimedOut(passed) => {
+				(
+					"Time is up!",
+					format!("Passed {passed} of {} checkpoints", self.checkpoints),
+				)
+			},
+		}
+	}
+}
+
+impl Scene<GlobalState> for RaceResults {
+	fn update(
+		&mut self,
+		_glob: &mut GlobalState,
+		_ctx: &mut Context,
+		_quad_ctx: &mut GraphicsContext,
+	) -> SceneSwitch<GlobalState> {
+		if self.closing {
+			SceneSwitch::Pop
+		} else {
+			SceneSwitch::None
+		}
+	}
+
+	fn draw(
+		&mut self,
+		_glob: &mut GlobalState,
+		ctx: &mut Context,
+		quad_ctx: &mut GraphicsContext,
+	) -> GameResult<()> {
+		let size = graphics::drawable_size(quad_ctx);
+		let (heading, summary) = self.lines();
+
+		graphics::clear(ctx, quad_ctx, [0.0, 0.0, 0.0, 1.0].into());
+
+		let mut heading = Text::new(heading);
+		heading.set_font(Font::default(), (3. * Font::DEFAULT_FONT_SCALE).into());
+		heading.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&heading,
+			(Point2::new(0., size.1 / 3.), TEXT_COLOR),
+		)?;
+
+		let mut text = Text::new(summary);
+		text.set_font(Font::default(), (2. * Font::DEFAULT_FONT_SCALE).into());
+		text.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&text,
+			(Point2::new(0., size.1 / 2.), TEXT_COLOR),
+		)?;
+
+		let mut hint = Text::new("Enter or Esc to continue sailing");
+		hint.set_font(Font::default(), (1. * Font::DEFAULT_FONT_SCALE).into());
+		hint.set_bounds(Point2::new(size.0, size.1), graphics::Align::Center);
+		graphics::draw(
+			ctx,
+			quad_ctx,
+			&hint,
+			(
+				Point2::new(0., size.1 - 2. * Font::DEFAULT_FONT_SCALE),
+				BUTTON_COLOR,
+			),
+		)?;
+
+		// Finally, issue the draw call and what not, finishing this frame for good
+		graphics::present(ctx, quad_ctx)?;
+
+		Ok(())
+	}
+
+	fn key_down_event(
+		&mut self,
+		_glob: &mut GlobalState,
+		_ctx: &mut Context,
+		_quad_ctx: &mut GraphicsContext,
+		key: KeyCode,
+	) {
+		if let KeyCode::Escape | KeyCode::Enter | KeyCode::KpEnter = key {
+			self.closing = true;
+		}
+	}
+
+	fn name(&self) -> &str {
+		"Race Results"
+	}
+
+	fn resize_event(
+		&mut self,
+		_glob: &mut GlobalState,
+		ctx: &mut gwg::Context,
+		_quad_ctx: &mut gwg::miniquad::GraphicsContext,
+		w: f32,
+		h: f32,
+	) {
+		let coordinates = graphics::Rect::new(0., 0., w, h);
+
+		graphics::set_screen_coordinates(ctx, coordinates).expect("Can't resize the window");
+	}
+}
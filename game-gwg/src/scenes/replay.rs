@@ -0,0 +1,93 @@
+//! Reading and writing recorded trajectories, e.g. to race against a ghost ship
+//!
+//! Recordings are plain TOML files, while the shared ghost files are compact
+//! and versioned, see `logic::trajectory`. Both can be loaded as ghost.
+//!
+
+use std::path::Path;
+
+use logic::trajectory::Ghost;
+use logic::trajectory::RecordedWorld;
+use logic::trajectory::Trajectory;
+use logic::trajectory::GHOST_MAGIC;
+
+
+
+/// Loads a recorded trajectory or a ghost file, logging any failure
+///
+/// The trajectory has to be recorded in the given `world`.
+pub fn load_trajectory(path: &Path, world: &RecordedWorld) -> Option<Trajectory> {
+	let bytes = std::fs::read(path)
+		.map_err(|err| println!("[replay] failed to read {}: {err}", path.display()))
+		.ok()?;
+
+	if bytes.starts_with(&GHOST_MAGIC) {
+		return Ghost::import(&bytes, world)
+			.map(|ghost| ghost.trajectory)
+			.map_err(|err| println!("[replay] failed to import {}: {err}", path.display()))
+			.ok();
+	}
+
+	let content = String::from_utf8(bytes)
+		.map_err(|err| println!("[replay] failed to parse {}: {err}", path.display()))
+		.ok()?;
+	let trajectory: Trajectory = toml::from_str(&content)
+		.map_err(|err| println!("[replay] failed to parse {}: {err}", path.display()))
+		.ok()?;
+	trajectory
+		.check_world(world)
+		.map_err(|err| println!("[replay] failed to load {}: {err}", path.display()))
+		.ok()?;
+
+	Some(trajectory)
+}
+
+/// Stores the recorded trajectory, logging any failure
+pub fn save_trajectory(path: &Path, trajectory: &Trajectory) {
+	let result = toml::to_string(trajectory)
+		.map_err(|err| err.to_string())
+		.and_then(|content| std::fs::write(path, content).map_err(|err| err.to_string()));
+
+	match result {
+		Ok(()) => {
+			println!(
+				"[replay] saved {} ticks to {}",
+				trajectory.samples.len(),
+				path.display()
+			)
+		},
+		Err(err) => println!("[replay] failed to save {}: {err}", path.display()),
+	}
+}
+
+/// Exports the run as ghost file for sharing, logging any failure
+///
+/// The run is only exported, if it beats the run already in the file from
+/// the same world, see `Score::beats`.
+pub fn export_ghost(path: &Path, run: &Ghost) {
+	let previous = std::fs::read(path)
+		.ok()
+		.and_then(|bytes| Ghost::import(&bytes, &run.trajectory.world).ok());
+	if let Some(previous) = previous {
+		if !run.score.beats(previous.score) {
+			println!(
+				"[replay] kept the better run ({:?}) in {}",
+				previous.score,
+				path.display()
+			);
+			return;
+		}
+	}
+
+	match std::fs::write(path, run.export()) {
+		Ok(()) => {
+			println!(
+				"[replay] exported {} ticks ({:?}) to {}",
+				run.trajectory.samples.len(),
+				run.score,
+				path.display()
+			)
+		},
+		Err(err) => println!("[replay] failed to export {}: {err}", path.display()),
+	}
+}
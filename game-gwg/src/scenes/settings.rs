@@ -0,0 +1,114 @@
+//! The settings chosen in the main menu
+//!
+
+#[cfg(test)]
+mod test;
+
+use serde::Deserialize;
+use serde::Serialize;
+use strum::IntoEnumIterator;
+
+use super::profile;
+
+
+/// The smallest scale of the HUD, below it the texts become unreadable
+const MIN_UI_SCALE: f32 = 0.5;
+/// The largest scale of the HUD, above it the HUD covers most of the screen
+const MAX_UI_SCALE: f32 = 4.0;
+
+
+/// How plenty the fish in the sea are, scaling the resource density of new worlds
+///
+/// The scarcer the fish, the harder the economy.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize)]
+#[derive(strum::EnumIter)]
+#[serde(rename_all = "lowercase")]
+pub enum Abundance {
+	Scarce,
+	Normal,
+	Plentiful,
+}
+// TODO: use the `#[default]` attribute one day instead
+impl Default for Abundance {
+	fn default() -> Self {
+		Self::Normal
+	}
+}
+impl Abundance {
+	/// The factor of the resource density, see `Setting::resource_density`
+	pub fn factor(self) -> f32 {
+		match self {
+			Self::Scarce => 0.5,
+			Self::Normal => 1.0,
+			Self::Plentiful => 1.5,
+		}
+	}
+
+	/// The next abundance, for cycling in the menu
+	pub fn next(self) -> Self {
+		Self::iter()
+			.cycle()
+			.skip_while(|&a| a != self)
+			.nth(1)
+			.unwrap_or(self)
+	}
+}
+
+
+/// The settings, persisted in the profile of the player
+#[derive(Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+	/// The abundance of fish in new worlds
+	pub abundance: Abundance,
+	/// The seed last rolled in the main menu, offered again on the next run
+	pub rerolled_seed: Option<u64>,
+	/// Whether the camera turns with the ship instead of keeping north up
+	pub follow_heading: bool,
+	/// Whether the auto-reef assist sets the sails, see `Vehicle::auto_reefing`
+	pub auto_reef: bool,
+	/// The scale of the texts and elements of the HUD, see [Self::set_ui_scale]
+	pub ui_scale: f32,
+}
+
+impl Default for Settings {
+	fn default() -> Self {
+		Self {
+			abundance: Default::default(),
+			rerolled_seed: None,
+			follow_heading: false,
+			auto_reef: false,
+			ui_scale: 1.0,
+		}
+	}
+}
+
+impl Settings {
+	/// Loads the settings from the profile, or starts with the defaults
+	pub fn load() -> Self {
+		let mut settings: Self = profile::load("settings");
+		// The profile might have been edited by hand
+		settings.set_ui_scale(settings.ui_scale);
+		settings
+	}
+
+	/// Sets the scale of the HUD, clamped to [MIN_UI_SCALE, MAX_UI_SCALE]
+	///
+	/// A scale that is not a number at all falls back to the default.
+	/// Returns whether the given `scale` was used as is.
+	pub fn set_ui_scale(&mut self, scale: f32) -> bool {
+		self.ui_scale = if scale.is_nan() {
+			Self::default().ui_scale
+		} else {
+			scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+		};
+		self.ui_scale == scale
+	}
+
+	/// Stores the settings in the profile
+	pub fn save(&self) {
+		profile::save("settings", self)
+	}
+}
@@ -0,0 +1,60 @@
+use super::Settings;
+use super::MAX_UI_SCALE;
+use super::MIN_UI_SCALE;
+
+#[test]
+fn ui_scale_within_range_is_used_as_is() {
+	// Arrange
+	let mut settings = Settings::default();
+
+	// Act
+	let as_is = settings.set_ui_scale(1.5);
+
+	// Assert
+	assert!(as_is);
+	assert_eq!(settings.ui_scale, 1.5);
+}
+
+#[test]
+fn ui_scale_out_of_range_is_clamped() {
+	// Arrange
+	let mut settings = Settings::default();
+
+	// Act
+	let tiny = settings.set_ui_scale(0.0);
+	let tiny_scale = settings.ui_scale;
+	let huge = settings.set_ui_scale(100.0);
+	let huge_scale = settings.ui_scale;
+
+	// Assert
+	assert!(!tiny);
+	assert_eq!(tiny_scale, MIN_UI_SCALE);
+	assert!(!huge);
+	assert_eq!(huge_scale, MAX_UI_SCALE);
+}
+
+#[test]
+fn ui_scale_not_a_number_falls_back_to_the_default() {
+	// Arrange
+	let mut settings = Settings::default();
+	settings.set_ui_scale(2.0);
+
+	// Act
+	let as_is = settings.set_ui_scale(f32::NAN);
+
+	// Assert
+	assert!(!as_is);
+	assert_eq!(settings.ui_scale, Settings::default().ui_scale);
+}
+
+#[test]
+fn missing_ui_scale_loads_as_the_default() {
+	// Arrange
+	let content = "abundance = \"scarce\"";
+
+	// Act
+	let settings: Settings = toml::from_str(content).unwrap();
+
+	// Assert
+	assert_eq!(settings.ui_scale, 1.0);
+}
@@ -0,0 +1,65 @@
+//! Writing the ship telemetry for external dashboards or stream overlays
+//!
+//! The telemetry is written one JSON line at a time, either to the stdout,
+//! e.g. to be piped into an overlay, or into a file of its own, which
+//! dashboards can follow, e.g. via `tail -f`. On the stdout, it is mixed with
+//! the log, whose lines never start with `{` though.
+//!
+
+use std::fs::File;
+use std::io::LineWriter;
+use std::io::Stdout;
+use std::io::Write;
+use std::path::Path;
+
+use logic::World;
+
+
+/// The path meaning the stdout instead of a file
+pub const STDOUT_PATH: &str = "-";
+
+
+/// Where the telemetry is written into
+pub enum Telemetry {
+	Stdout(Stdout),
+	File(LineWriter<File>),
+}
+
+impl Telemetry {
+	/// Writes the telemetry to the given `path`, or to the stdout for [STDOUT_PATH]
+	///
+	/// Any previous file at the `path` is replaced.
+	pub fn create(path: &Path) -> std::io::Result<Self> {
+		if path == Path::new(STDOUT_PATH) {
+			Ok(Self::Stdout(std::io::stdout()))
+		} else {
+			Ok(Self::File(LineWriter::new(File::create(path)?)))
+		}
+	}
+
+	/// Writes the current state of the player as a single JSON line
+	pub fn write(&mut self, world: &World) {
+		let state = &world.state;
+		let vehicle = &state.player.vehicle;
+
+		let telemetry = serde_json::json!({
+			"tick": state.timestamp,
+			"pos": vehicle.pos,
+			"heading": vehicle.heading,
+			"speed": vehicle.velocity.magnitude(),
+			"wind": state.wind,
+			"money": state.player.money,
+			"cargo": vehicle.cargo,
+			"cargo_weight": vehicle.resource_weight,
+			"cargo_value": vehicle.resource_value,
+		});
+
+		let result = match self {
+			Self::Stdout(stdout) => writeln!(stdout.lock(), "{telemetry}"),
+			Self::File(file) => writeln!(file, "{telemetry}"),
+		};
+		if let Err(err) = result {
+			println!("[telemetry] failed to write the telemetry: {err}");
+		}
+	}
+}
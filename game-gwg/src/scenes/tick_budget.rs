@@ -0,0 +1,114 @@
+//! The budget of logic ticks simulated per frame
+//!
+//! The logic runs at a fixed rate of `TICKS_PER_SECOND`. After a slow frame,
+//! several ticks are pending, which are caught up on in the next frame. If
+//! a frame is so slow, that catching up would take longer than the frame
+//! itself, the game would fall behind further and further. Thus, only a
+//! limited number of the pending ticks are simulated, and the others are
+//! dropped, keeping the game in real time at the cost of a skipped bit of
+//! simulation.
+//!
+
+#[cfg(test)]
+mod test;
+
+
+/// The most logic ticks caught up on within a single frame
+pub const MAX_CATCH_UP_TICKS: u32 = 10;
+
+
+/// Admits the pending ticks of one frame, up to a maximum
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TickBudget {
+	/// The maximum number of ticks to simulate
+	max: u32,
+	/// The number of ticks simulated so far
+	simulated: u32,
+	/// The number of ticks dropped so far
+	dropped: u32,
+}
+
+impl TickBudget {
+	/// A budget of `max` ticks for this frame
+	pub fn new(max: u32) -> Self {
+		Self {
+			max,
+			simulated: 0,
+			dropped: 0,
+		}
+	}
+
+	/// Whether the next pending tick shall be simulated
+	///
+	/// Otherwise, the tick is dropped. Either way, the tick has to be taken
+	/// from the pending time, so that the dropped time is not caught up on
+	/// in the following frames.
+	pub fn admit(&mut self) -> bool {
+		if self.simulated < self.max {
+			self.simulated += 1;
+			true
+		} else {
+			self.dropped += 1;
+			false
+		}
+	}
+
+	/// The number of ticks simulated in this frame
+	#[cfg(feature = "dev")]
+	pub fn simulated(&self) -> u32 {
+		self.simulated
+	}
+
+	/// The number of ticks dropped in this frame
+	pub fn dropped(&self) -> u32 {
+		self.dropped
+	}
+}
+
+
+/// Measures the rate of the actually simulated ticks, for the dev overlay
+#[cfg(feature = "dev")]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct SimRate {
+	/// The time in seconds, when the current measurement started
+	///
+	/// It is `None` before the first frame is recorded.
+	window_start: Option<f64>,
+	/// The ticks simulated since `window_start`
+	window_ticks: u32,
+	/// The simulated ticks per second, as of the last complete measurement
+	rate: f32,
+}
+
+#[cfg(feature = "dev")]
+impl SimRate {
+	/// The duration of a measurement in seconds
+	const WINDOW: f64 = 1.0;
+
+	/// Records the `ticks` simulated in the frame ending at `now`, in seconds
+	///
+	/// The first frame only starts the measurement, since it is unknown when
+	/// that frame began.
+	pub fn record(&mut self, now: f64, ticks: u32) {
+		let window_start = match self.window_start {
+			Some(start) => start,
+			None => {
+				self.window_start = Some(now);
+				return;
+			},
+		};
+		self.window_ticks += ticks;
+
+		let elapsed = now - window_start;
+		if elapsed >= Self::WINDOW {
+			self.rate = (f64::from(self.window_ticks) / elapsed) as f32;
+			self.window_start = Some(now);
+			self.window_ticks = 0;
+		}
+	}
+
+	/// The simulated ticks per second
+	pub fn rate(&self) -> f32 {
+		self.rate
+	}
+}
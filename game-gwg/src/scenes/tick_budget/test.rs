@@ -0,0 +1,100 @@
+#[cfg(feature = "dev")]
+use super::SimRate;
+use super::TickBudget;
+use super::MAX_CATCH_UP_TICKS;
+
+/// Runs the fixed-rate loop of a frame, which took the given number of ticks
+///
+/// Just like `gwg::timer::check_update_time`, the pending time is taken one
+/// tick at a time. Returns the simulated and the still pending ticks.
+fn run_frame(pending: &mut u32, frame_ticks: u32) -> (u32, u32) {
+	*pending += frame_ticks;
+	let mut budget = TickBudget::new(MAX_CATCH_UP_TICKS);
+	let mut simulated = 0;
+	while *pending > 0 {
+		*pending -= 1;
+		if budget.admit() {
+			simulated += 1;
+		}
+	}
+	assert_eq!(simulated + budget.dropped(), frame_ticks);
+	(simulated, *pending)
+}
+
+#[test]
+fn regular_frames_simulate_all_ticks() {
+	// Arrange
+	let mut pending = 0;
+
+	// Act
+	let frames: Vec<_> = (0..5).map(|_| run_frame(&mut pending, 1)).collect();
+
+	// Assert
+	assert!(frames.iter().all(|&frame| frame == (1, 0)));
+}
+
+#[test]
+fn slow_frame_drops_the_ticks_beyond_the_budget() {
+	// Arrange
+	let mut pending = 0;
+
+	// Act
+	let slow = run_frame(&mut pending, 3 * MAX_CATCH_UP_TICKS);
+	let next = run_frame(&mut pending, 1);
+
+	// Assert
+	assert_eq!(slow, (MAX_CATCH_UP_TICKS, 0));
+	// Nothing is left to catch up on, the game is real time again
+	assert_eq!(next, (1, 0));
+}
+
+#[test]
+fn budget_admits_up_to_its_maximum() {
+	// Arrange
+	let mut budget = TickBudget::new(2);
+
+	// Act
+	let admitted: Vec<_> = (0..4).map(|_| budget.admit()).collect();
+
+	// Assert
+	assert_eq!(admitted, vec![true, true, false, false]);
+	assert_eq!(budget.dropped(), 2);
+}
+
+#[test]
+#[cfg(feature = "dev")]
+fn sim_rate_counts_the_ticks_per_second() {
+	// Arrange
+	let mut rate = SimRate::default();
+
+	// Act
+	// Half a second at full speed, and half a second dropping every other tick
+	for frame in 0..=60 {
+		let ticks = if frame <= 30 { 1 } else { frame % 2 };
+		rate.record(f64::from(frame) / 60.0, ticks);
+	}
+
+	// Assert
+	assert!((rate.rate() - 45.0).abs() < 1.0, "{}", rate.rate());
+}
+
+#[test]
+#[cfg(feature = "dev")]
+fn sim_rate_starts_measuring_at_the_first_frame() {
+	// Arrange
+	let mut rate = SimRate::default();
+	// Long after the start of the clock, e.g. after loading
+	let start = 100.0;
+
+	// Act
+	rate.record(start, 1);
+	let first = rate.rate();
+	for frame in 1..=60 {
+		rate.record(start + f64::from(frame) / 60.0, 1);
+	}
+
+	// Assert
+	// Not measured yet, instead of the ticks per time since the start of the clock
+	assert_eq!(first, 0.0);
+	assert!((rate.rate() - 60.0).abs() < 1.0, "{}", rate.rate());
+}
@@ -0,0 +1,146 @@
+//! The scripted tutorial voyage for new players
+//!
+
+use cfg_if::cfg_if;
+use logic::state::SailKind;
+use logic::state::ShipHull;
+use logic::World;
+
+
+
+/// The steps of the tutorial voyage, in order
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Step {
+	SetSail,
+	CatchFish,
+	ReturnToHarbor,
+	SellFish,
+	Upgrade,
+	Done,
+}
+
+impl Step {
+	/// The prompt telling the player what to do in this step
+	fn prompt(self) -> &'static str {
+		match self {
+			Self::SetSail => "Hoist the sails with W",
+			Self::CatchFish => "Steer with A and D to catch a fish",
+			Self::ReturnToHarbor => "Follow the money bag back to a harbor and slow down",
+			Self::SellFish => "Sell your fish with E",
+			Self::Upgrade => "Earn enough money, then upgrade your sail (R) or hull (F)",
+			Self::Done => "Tutorial complete, plenty of fish are waiting!",
+		}
+	}
+
+	/// The step following this one
+	fn next(self) -> Self {
+		match self {
+			Self::SetSail => Self::CatchFish,
+			Self::CatchFish => Self::ReturnToHarbor,
+			Self::ReturnToHarbor => Self::SellFish,
+			Self::SellFish => Self::Upgrade,
+			Self::Upgrade | Self::Done => Self::Done,
+		}
+	}
+}
+
+/// The state machine of the tutorial voyage
+///
+/// It only observes the world state and detects when the player completed
+/// the current objective.
+#[derive(Debug, Clone)]
+pub struct Tutorial {
+	/// The current objective
+	step: Step,
+	/// Whether the current prompt has already been announced
+	announced: bool,
+	/// The sail at the beginning of the tutorial
+	start_sail: SailKind,
+	/// The hull at the beginning of the tutorial
+	start_hull: ShipHull,
+}
+
+impl Tutorial {
+	pub fn new(world: &World) -> Self {
+		Self {
+			step: Step::SetSail,
+			announced: false,
+			start_sail: world.state.player.vehicle.sail.kind,
+			start_hull: world.state.player.vehicle.hull,
+		}
+	}
+
+	/// The prompt of the current objective, if the tutorial is still running
+	pub fn prompt(&self) -> Option<&'static str> {
+		(self.step != Step::Done).then(|| self.step.prompt())
+	}
+
+	/// Checks the objective of the current step, advancing if completed
+	///
+	/// Returns the prompt to announce, if any.
+	pub fn update(&mut self, world: &mut World) -> Option<&'static str> {
+		if self.step != Step::Done && self.is_completed(world) {
+			self.step = self.step.next();
+			self.announced = false;
+		}
+
+		if self.announced {
+			None
+		} else {
+			self.announced = true;
+			Some(self.step.prompt())
+		}
+	}
+
+	fn is_completed(&self, world: &mut World) -> bool {
+		let vehicle = &world.state.player.vehicle;
+		match self.step {
+			Step::SetSail => vehicle.sail.reefing.value() > 0,
+			Step::CatchFish => vehicle.resource_weight > 0,
+			Step::ReturnToHarbor => {
+				world
+					.state
+					.get_trading(&world.init)
+					.map(|t| t.has_player_valid_speed())
+					.unwrap_or(false)
+			},
+			Step::SellFish => vehicle.resource_weight == 0,
+			Step::Upgrade => {
+				vehicle.sail.kind != self.start_sail || vehicle.hull != self.start_hull
+			},
+			Step::Done => true,
+		}
+	}
+}
+
+
+cfg_if! {
+	if #[cfg(not(target_family = "wasm"))] {
+		use super::profile;
+
+		/// The name of the file marking that the tutorial has been offered already
+		const PROFILE_FILE_NAME: &str = ".plenty-of-fish-in-the-sea";
+
+		/// Checks whether this is the first time the game is played
+		///
+		/// The first run is over right after calling this function.
+		pub fn check_first_run() -> bool {
+			match profile::path(PROFILE_FILE_NAME) {
+				Some(path) if !path.exists() => {
+					if let Err(err) = std::fs::write(&path, "") {
+						println!("[tutorial] failed to write the profile: {err}");
+					}
+					true
+				},
+				_ => false,
+			}
+		}
+	} else {
+		/// Checks whether this is the first time the game is played
+		///
+		/// There is no profile on the web, thus never the first run.
+		pub fn check_first_run() -> bool {
+			false
+		}
+	}
+}
@@ -0,0 +1,145 @@
+//! Benchmarks of the world update
+//!
+//! Run with `cargo bench -p logic`, and with `--features parallel` added to
+//! compare the parallel resource animation.
+
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use logic::generator::Generator;
+use logic::generator::PerlinNoise;
+use logic::generator::Setting;
+use logic::units::Tick;
+use logic::Input;
+use logic::StdRng;
+use logic::World;
+use rand::SeedableRng;
+
+
+
+/// Generates a reproducible world of the given size and resource density
+fn world(edge_length: u16, resource_density: f32) -> World {
+	let setting = Setting {
+		edge_length,
+		resource_density,
+		resources: Default::default(),
+	};
+
+	PerlinNoise.generate(&setting, StdRng::seed_from_u64(42))
+}
+
+fn bench_update_by_size(c: &mut Criterion) {
+	let mut group = c.benchmark_group("WorldState::update by map size");
+
+	for edge_length in [16, 32, 64, 128] {
+		let World {
+			init,
+			mut state,
+		} = world(edge_length, 1.0);
+		let input = Input::default();
+
+		group.bench_with_input(
+			BenchmarkId::from_parameter(edge_length),
+			&edge_length,
+			|b, _| b.iter(|| black_box(state.update(&init, &input))),
+		);
+	}
+
+	group.finish();
+}
+
+fn bench_update_by_density(c: &mut Criterion) {
+	let mut group = c.benchmark_group("WorldState::update by resource density");
+
+	for resource_density in [0.0, 0.5, 1.0, 2.0, 4.0] {
+		let World {
+			init,
+			mut state,
+		} = world(64, resource_density);
+		let input = Input::default();
+
+		group.bench_with_input(
+			BenchmarkId::from_parameter(resource_density),
+			&resource_density,
+			|b, _| b.iter(|| black_box(state.update(&init, &input))),
+		);
+	}
+
+	group.finish();
+}
+
+fn bench_resource_update(c: &mut Criterion) {
+	let World {
+		state, ..
+	} = world(32, 1.0);
+	let mut resource = state.resources[0].clone();
+	let mut tick = Tick(0);
+
+	c.bench_function("ResourcePack::update", |b| {
+		b.iter(|| {
+			tick = tick.next();
+			resource.update(black_box(tick));
+		})
+	});
+}
+
+fn bench_resource_animation(c: &mut Criterion) {
+	let mut group = c.benchmark_group("WorldState::seek_to by map size");
+
+	// All resources are animated at once, so compare the timings with and
+	// without the `parallel` feature to see its speedup
+	for edge_length in [64, 128, 256] {
+		let World {
+			init,
+			mut state,
+		} = world(edge_length, 4.0);
+		let mut tick = Tick(0);
+
+		group.bench_with_input(
+			BenchmarkId::from_parameter(edge_length),
+			&edge_length,
+			|b, _| {
+				b.iter(|| {
+					tick = tick.next();
+					state.seek_to(&init, black_box(tick));
+				})
+			},
+		);
+	}
+
+	group.finish();
+}
+
+fn bench_animation_lod(c: &mut Criterion) {
+	let mut group = c.benchmark_group("WorldState::update by animation LOD");
+
+	// A big map, where most resources are far away from the player
+	for lod in [None, Some(200.0), Some(50.0)] {
+		let World {
+			mut init,
+			mut state,
+		} = world(128, 4.0);
+		init.animation_lod = lod;
+		let input = Input::default();
+
+		group.bench_with_input(
+			BenchmarkId::from_parameter(format!("{lod:?}")),
+			&lod,
+			|b, _| b.iter(|| black_box(state.update(&init, &input))),
+		);
+	}
+
+	group.finish();
+}
+
+criterion_group!(
+	benches,
+	bench_update_by_size,
+	bench_update_by_density,
+	bench_resource_update,
+	bench_resource_animation,
+	bench_animation_lod
+);
+criterion_main!(benches);
@@ -1,6 +1,7 @@
 //! World generator sub module
 //!
 
+use std::collections::HashSet;
 use std::f32::consts::TAU;
 
 use noise::Seedable;
@@ -9,19 +10,60 @@ use serde::Deserialize;
 use serde::Serialize;
 use strum::IntoEnumIterator;
 
+use crate::race::Race;
+use crate::resource::spawn_resources;
 use crate::resource::ResourcePack;
 use crate::resource::ResourcePackContent;
+use crate::resource::ResourceTable;
+use crate::seeded_rng;
 use crate::state::Harbor;
+use crate::state::Obstacle;
+use crate::state::ObstacleKind;
 use crate::state::WorldState;
+use crate::terrain::Surface;
+use crate::terrain::TileCoord;
 use crate::units::Elevation;
+use crate::units::Location;
 use crate::units::TileType;
 use crate::Terrain;
 use crate::World;
 use crate::WorldInit;
+use crate::HARBOR_EFFECT_SIZE;
+use crate::TILE_SIZE;
+
+
+#[cfg(test)]
+mod test;
 
 
 const PERLIN_NOISE_FACTOR: f64 = 1. / core::f64::consts::PI / 2.;
 
+/// The number of random tiles tried for each harbor, before placing fewer harbors
+const HARBOR_ATTEMPTS: usize = 64;
+/// The number of tiles per obstacle (on average)
+const TILES_PER_OBSTACLE: f32 = 64.;
+/// The minimum distance between an obstacle and a harbor, in meter
+const OBSTACLE_HARBOR_CLEARANCE: f32 = 2. * HARBOR_EFFECT_SIZE;
+/// The probability of an obstacle being a wreck instead of a rock
+const WRECK_PROBABILITY: f64 = 0.25;
+const WRECK_RADIUS: f32 = 1.0;
+const ROCK_MIN_RADIUS: f32 = 0.5;
+const ROCK_MAX_RADIUS: f32 = 1.2;
+
+/// The scale of the noise shaping the ice floes, see [Icy]
+const ICE_NOISE_FACTOR: f64 = 0.25;
+/// The noise level in range [-1,1], above which shallow water freezes, see [Icy]
+const ICE_NOISE_THRESHOLD: f64 = 0.1;
+
+/// The random stream of the course checkpoints, see [crate::seeded_rng]
+const COURSE_RNG_STREAM: u128 = 0x3c9e51a7d2f04b86e7130c5d9a28f46b;
+/// The number of random tiles tried for each checkpoint, before the
+/// spacing is relaxed
+const COURSE_ATTEMPTS: usize = 64;
+/// The preferred spacing of the checkpoints relative to the map size, see
+/// [course_spacing]
+const COURSE_SPACING_FACTOR: f32 = 0.5;
+
 
 /// The basic map output settings
 #[derive(Debug, Clone)]
@@ -32,6 +74,18 @@ pub struct Setting {
 
 	/// Resource density
 	pub resource_density: f32,
+
+	/// The stats of the resource types
+	#[serde(default)]
+	pub resources: ResourceTable,
+}
+impl Setting {
+	/// The amount of resources of the given type to be present on the map
+	pub fn resource_amount(&self, kind: ResourcePackContent) -> usize {
+		let map_area = self.edge_length as f32 * self.edge_length as f32 * self.resource_density;
+
+		(map_area * self.resources[kind].spawn_density) as usize
+	}
 }
 
 /// A world generator
@@ -56,7 +110,11 @@ impl Generator for WhiteNoise {
 			setting.edge_length as f32 * setting.edge_length as f32 * setting.resource_density;
 
 		let resources = (0..(resource_amount as u32))
-			.map(|_| ResourcePack::new(terrain.random_location(&mut rng), rng.gen(), &mut rng))
+			.map(|_| {
+				let loc = terrain.random_location(&mut rng);
+				let kind = rng.gen();
+				ResourcePack::new(&setting.resources, loc, kind, &mut rng)
+			})
 			.collect();
 
 		// One harbour per 128 tiles (on average)
@@ -65,10 +123,10 @@ impl Generator for WhiteNoise {
 
 		let harbors = (0..(harbor_amount as u32))
 			.map(|_| {
-				Harbor {
-					loc: terrain.random_passable_location(&mut rng),
-					orientation: rng.gen::<f32>() * TAU,
-				}
+				Harbor::new(
+					terrain.random_passable_location(&mut rng),
+					rng.gen::<f32>() * TAU,
+				)
 			})
 			.collect();
 
@@ -80,6 +138,8 @@ impl Generator for WhiteNoise {
 				terrain,
 				seed,
 				dbg: Default::default(),
+				physics: Default::default(),
+				animation_lod: None,
 			},
 			state: WorldState {
 				resources,
@@ -112,61 +172,235 @@ impl Generator for PerlinNoise {
 			*tt = Elevation(((value - 0.8) * 10.) as i16);
 		}
 
-		let map_area =
-			setting.edge_length as f32 * setting.edge_length as f32 * setting.resource_density;
-
-
 		// Harbor spawning
 
 		// One harbour per 256 tiles (on average)
 		let harbor_amount =
 			(setting.edge_length as f32 * setting.edge_length as f32 / 256.).max(1.0) as usize;
 
+		// All harbors are placed in the biggest body of water, so each one can
+		// be reached from any other, and thus from the player starting at one.
+		let main_water: HashSet<_> = terrain
+			.water_regions()
+			.into_iter()
+			.max_by_key(Vec::len)
+			.unwrap_or_default()
+			.into_iter()
+			.collect();
+
+		let is_shallow = |elev: Elevation| {
+			TileType::ShallowWater.lowest() <= elev && elev <= TileType::ShallowWater.highest()
+		};
+
 		let mut harbors = Vec::new();
-		// Add all the harbors
-		while harbors.len() < harbor_amount {
+		// Add all the harbors, giving up on maps with hardly any shallow water,
+		// instead of searching forever
+		for _ in 0..(harbor_amount * HARBOR_ATTEMPTS) {
+			if harbors.len() >= harbor_amount {
+				break;
+			}
+
 			let loc = terrain.random_passable_location(&mut rng);
-			let elev = *terrain.get(loc.try_into().unwrap());
+			let tile: TileCoord = loc.try_into().unwrap();
+			if !main_water.contains(&tile) {
+				continue;
+			}
+			let elev = *terrain.get(tile);
 
 			// Ensure a harbor only spawn within shallow water
-			if !(TileType::ShallowWater.lowest() <= elev
-				&& elev <= TileType::ShallowWater.highest())
-			{
+			if !is_shallow(elev) {
 				continue;
 			}
 
-			let harbor = Harbor {
-				loc,
-				orientation: rng.gen::<f32>() * TAU,
-			};
+			let harbor = Harbor::new(loc, rng.gen::<f32>() * TAU);
 			harbors.push(harbor);
 		}
+		// The player starts at the first harbor, thus there has to be one,
+		// preferably in shallow water, otherwise anywhere in the main water
+		if harbors.is_empty() {
+			let in_main_water = || terrain.iter().filter(|(tile, _)| main_water.contains(tile));
+			let tile = in_main_water()
+				.find(|&(_, &elev)| is_shallow(elev))
+				.or_else(|| in_main_water().next())
+				.map(|(tile, _)| tile);
+			if let Some(tile) = tile {
+				harbors.push(Harbor::new(tile.into(), rng.gen::<f32>() * TAU));
+			}
+		}
 
 
 		// Resource spawning
 
 		let mut resources = Vec::new();
 		for cnt in ResourcePackContent::iter() {
-			// One resource per tile (on average)
-			let resource_amount = map_area * cnt.spawn_density;
-
-			resources.extend(cnt.generate(&mut rng, &terrain, resource_amount as usize));
+			let resource_amount = setting.resource_amount(cnt);
+
+			resources.extend(spawn_resources(
+				&setting.resources,
+				cnt,
+				&terrain,
+				resource_amount,
+				&mut rng,
+			));
 		}
 
 		let seed: u64 = rng.gen();
 
+		// Obstacle spawning, drawing from the RNG only after everything else,
+		// so the rest of the world stays the same as without obstacles
+
+		let obstacle_amount =
+			(setting.edge_length as f32 * setting.edge_length as f32 / TILES_PER_OBSTACLE) as usize;
+
+		let mut obstacles = Vec::new();
+		// Give up on crowded maps, instead of searching forever
+		for _ in 0..(obstacle_amount * 16) {
+			if obstacles.len() >= obstacle_amount {
+				break;
+			}
+
+			let loc = terrain.random_location(&mut rng);
+			let tile: TileCoord = loc.try_into().unwrap();
+
+			// Only in open deep water, so no channel gets blocked
+			let open_water = [
+				tile,
+				terrain.east_of(tile),
+				terrain.south_of(tile),
+				terrain.west_of(tile),
+				terrain.north_of(tile),
+			]
+			.into_iter()
+			.all(|tc| terrain.get(tc).classify() == TileType::DeepWater);
+			let near_harbor = harbors.iter().any(|h| {
+				terrain.torus_distance(h.loc, loc).magnitude() < OBSTACLE_HARBOR_CLEARANCE
+			});
+			if !open_water || !main_water.contains(&tile) || near_harbor {
+				continue;
+			}
+
+			let obstacle = if rng.gen_bool(WRECK_PROBABILITY) {
+				Obstacle {
+					loc,
+					radius: WRECK_RADIUS,
+					kind: ObstacleKind::Wreck,
+				}
+			} else {
+				Obstacle {
+					loc,
+					radius: rng.gen_range(ROCK_MIN_RADIUS..ROCK_MAX_RADIUS),
+					kind: ObstacleKind::Rock,
+				}
+			};
+			obstacles.push(obstacle);
+		}
+
 		World {
 			init: WorldInit {
 				terrain,
 				terrain_setting: setting.clone(),
 				seed,
 				dbg: Default::default(),
+				physics: Default::default(),
+				animation_lod: None,
 			},
 			state: WorldState {
 				resources,
 				harbors,
+				obstacles,
 				..Default::default()
 			},
 		}
 	}
 }
+
+
+
+/// A wintry variant of another generator, freezing some of the shallow water
+///
+/// The ice floes are passable, but slow the ship down, see [Surface::Ice].
+/// The ice is drawn from the RNG only after the wrapped generator is done,
+/// so the rest of the world stays the same as without ice.
+pub struct Icy<G>(pub G);
+
+impl<G: Generator> Generator for Icy<G> {
+	fn generate<R: Rng>(&self, setting: &Setting, mut rng: R) -> World {
+		let mut world = self.0.generate(setting, &mut rng);
+		let terrain = &mut world.init.terrain;
+
+		let noise = noise::Perlin::new().set_seed(rng.gen());
+		let frozen: Vec<_> = terrain
+			.iter()
+			.filter(|(_, elev)| elev.classify() == TileType::ShallowWater)
+			.map(|(tc, _)| tc)
+			.filter(|tc| {
+				use noise::NoiseFn;
+
+				let value = noise.get([
+					f64::from(tc.x) * ICE_NOISE_FACTOR,
+					f64::from(tc.y) * ICE_NOISE_FACTOR,
+				]);
+				value > ICE_NOISE_THRESHOLD
+			})
+			.collect();
+		for tc in frozen {
+			terrain.set_surface(tc, Surface::Ice);
+		}
+
+		world
+	}
+}
+
+
+
+/// Places a course of `count` checkpoints, spread across the map
+///
+/// All checkpoints lie in the biggest body of water, just like the harbors,
+/// so each one can be reached from any other. They keep a distance of at
+/// least [course_spacing] to each other, which is relaxed only on crowded
+/// maps. The course is fully determined by the `terrain` and the `seed`.
+pub fn generate_course(terrain: &Terrain, seed: u64, count: usize) -> Vec<Location> {
+	let main_water = terrain
+		.water_regions()
+		.into_iter()
+		.max_by_key(Vec::len)
+		.unwrap_or_default();
+
+	let mut rng = seeded_rng(seed, COURSE_RNG_STREAM);
+	let mut spacing = course_spacing(terrain, count);
+	let mut course: Vec<Location> = Vec::new();
+	// Give up, once not even neighboring tiles may be used anymore
+	while course.len() < count && !main_water.is_empty() && spacing >= TILE_SIZE as f32 {
+		let spaced = (0..COURSE_ATTEMPTS)
+			.map(|_| Location::from(main_water[rng.gen_range(0..main_water.len())]))
+			.find(|&loc| {
+				course
+					.iter()
+					.all(|&other| terrain.torus_distance(other, loc).magnitude() >= spacing)
+			});
+		match spaced {
+			Some(loc) => course.push(loc),
+			// Too crowded, squeeze the remaining checkpoints in
+			None => spacing *= 0.5,
+		}
+	}
+
+	course
+}
+
+/// The preferred distance between the checkpoints of a course, in meter
+///
+/// It shrinks with the number of checkpoints, so they fit onto the map.
+pub fn course_spacing(terrain: &Terrain, count: usize) -> f32 {
+	terrain.map_size() / (count.max(1) as f32).sqrt() * COURSE_SPACING_FACTOR
+}
+
+/// Sets up a race through `count` checkpoints, to be passed within `time_limit` ticks
+///
+/// The checkpoints are a course as given by [generate_course], fully
+/// determined by the world.
+pub fn generate_race(world: &World, count: usize, time_limit: u64) -> Race {
+	let checkpoints = generate_course(&world.init.terrain, world.init.seed, count);
+
+	Race::new(checkpoints, time_limit)
+}
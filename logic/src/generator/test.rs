@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+
+use rand::SeedableRng;
+
+use super::course_spacing;
+use super::generate_course;
+use super::generate_race;
+use super::Generator;
+use super::Icy;
+use super::PerlinNoise;
+use super::Setting;
+use super::OBSTACLE_HARBOR_CLEARANCE;
+use crate::terrain::Surface;
+use crate::terrain::TileCoord;
+use crate::units::TileType;
+use crate::StdRng;
+
+#[test]
+fn all_harbors_share_one_body_of_water() {
+	for seed in 0..8 {
+		// Arrange
+		let setting = Setting {
+			edge_length: 64,
+			resource_density: 0.0,
+			resources: Default::default(),
+		};
+
+		// Act
+		let world = PerlinNoise.generate(&setting, StdRng::seed_from_u64(seed));
+
+		// Assert
+		let terrain = &world.init.terrain;
+		let harbor_tiles: Vec<TileCoord> = world
+			.state
+			.harbors
+			.iter()
+			.map(|h| h.loc.try_into().unwrap())
+			.collect();
+		let region: HashSet<_> = terrain
+			.water_regions()
+			.into_iter()
+			.find(|region| region.contains(&harbor_tiles[0]))
+			.unwrap()
+			.into_iter()
+			.collect();
+		assert!(
+			harbor_tiles.iter().all(|tc| region.contains(tc)),
+			"seed {seed}: {harbor_tiles:?}"
+		);
+	}
+}
+
+#[test]
+fn obstacles_keep_clear_of_harbors() {
+	// Arrange
+	let setting = Setting {
+		edge_length: 64,
+		resource_density: 0.0,
+		resources: Default::default(),
+	};
+
+	// Act
+	let world = PerlinNoise.generate(&setting, StdRng::seed_from_u64(42));
+
+	// Assert
+	let terrain = &world.init.terrain;
+	for obstacle in &world.state.obstacles {
+		let tile: TileCoord = obstacle.loc.try_into().unwrap();
+		assert!(terrain.get(tile).is_passable(), "{obstacle:?}");
+		for harbor in &world.state.harbors {
+			let distance = terrain.torus_distance(harbor.loc, obstacle.loc);
+			assert!(distance.magnitude() >= OBSTACLE_HARBOR_CLEARANCE);
+		}
+	}
+}
+
+#[test]
+fn icy_world_freezes_only_shallow_water() {
+	// Arrange
+	let setting = Setting {
+		edge_length: 64,
+		resource_density: 0.0,
+		resources: Default::default(),
+	};
+
+	// Act
+	let plain = PerlinNoise.generate(&setting, StdRng::seed_from_u64(42));
+	let icy = Icy(PerlinNoise).generate(&setting, StdRng::seed_from_u64(42));
+
+	// Assert
+	let terrain = &icy.init.terrain;
+	assert_eq!(terrain.playground, plain.init.terrain.playground);
+	assert_eq!(icy.state.harbors.len(), plain.state.harbors.len());
+	let ice: Vec<_> = terrain
+		.coords()
+		.filter(|&tc| terrain.surface(tc) == Surface::Ice)
+		.collect();
+	assert!(!ice.is_empty());
+	assert!(ice
+		.iter()
+		.all(|&tc| terrain.get(tc).classify() == TileType::ShallowWater));
+}
+
+#[test]
+fn race_checkpoints_are_reachable_by_the_player() {
+	// Arrange
+	let setting = Setting {
+		edge_length: 64,
+		resource_density: 0.0,
+		resources: Default::default(),
+	};
+	let mut world = PerlinNoise.generate(&setting, StdRng::seed_from_u64(42));
+	world.state.player.vehicle.pos = world.state.harbors[0].loc;
+
+	// Act
+	let race = generate_race(&world, 5, 1000);
+
+	// Assert
+	let terrain = &world.init.terrain;
+	let start: TileCoord = world.state.player.vehicle.pos.try_into().unwrap();
+	let home_water: HashSet<_> = terrain
+		.water_regions()
+		.into_iter()
+		.find(|region| region.contains(&start))
+		.unwrap()
+		.into_iter()
+		.collect();
+	assert_eq!(race.checkpoints.len(), 5);
+	for loc in &race.checkpoints {
+		let tile: TileCoord = (*loc).try_into().unwrap();
+		assert!(home_water.contains(&tile), "{loc:?}");
+	}
+	assert_eq!(race.checkpoints, generate_race(&world, 5, 1000).checkpoints);
+}
+
+#[test]
+fn course_checkpoints_are_reachable_and_spaced() {
+	for seed in 0..8 {
+		// Arrange
+		let setting = Setting {
+			edge_length: 64,
+			resource_density: 0.0,
+			resources: Default::default(),
+		};
+		let world = PerlinNoise.generate(&setting, StdRng::seed_from_u64(seed));
+		let terrain = &world.init.terrain;
+
+		// Act
+		let course = generate_course(terrain, seed, 6);
+
+		// Assert
+		assert_eq!(course.len(), 6, "seed {seed}");
+		let first: TileCoord = course[0].try_into().unwrap();
+		let region: HashSet<_> = terrain
+			.water_regions()
+			.into_iter()
+			.find(|region| region.contains(&first))
+			.unwrap()
+			.into_iter()
+			.collect();
+		let spacing = course_spacing(terrain, 6);
+		for (i, &a) in course.iter().enumerate() {
+			let tile: TileCoord = a.try_into().unwrap();
+			assert!(region.contains(&tile), "seed {seed}: {a:?}");
+			for &b in &course[i + 1..] {
+				let distance = terrain.torus_distance(a, b).magnitude();
+				assert!(distance >= spacing, "seed {seed}: {a:?} {b:?}");
+			}
+		}
+		assert_eq!(course, generate_course(terrain, seed, 6), "seed {seed}");
+	}
+}
+
+#[test]
+fn tiny_map_still_gets_a_harbor() {
+	for seed in 0..8 {
+		// Arrange
+		// Hardly any tiles, let alone shallow ones
+		let setting = Setting {
+			edge_length: 4,
+			resource_density: 0.0,
+			resources: Default::default(),
+		};
+
+		// Act
+		let world = PerlinNoise.generate(&setting, StdRng::seed_from_u64(seed));
+
+		// Assert
+		assert_eq!(world.state.harbors.len(), 1, "seed {seed}");
+	}
+}
@@ -4,10 +4,14 @@ use serde::Serialize;
 
 
 pub mod generator;
+pub mod race;
 pub mod resource;
+pub mod save;
 pub mod state;
 pub mod terrain;
+pub mod trajectory;
 pub mod units;
+pub mod wind_rose;
 
 
 pub use nalgebra_glm as glm;
@@ -15,9 +19,13 @@ use state::Reefing;
 use state::WorldState;
 use terrain::Terrain;
 use units::BiPolarFraction;
+use units::Elevation;
 
 pub type StdRng = rand_pcg::Pcg64;
 
+#[cfg(test)]
+mod test;
+
 
 
 /// The size (edge length) of a terrain tile, in meter
@@ -26,24 +34,80 @@ pub const TILE_SIZE: u32 = 4;
 /// The bounding-box "diameter" of a harbor, in meter
 pub const HARBOR_SIZE: f32 = 3.;
 
-/// The effect "diameter" within which a player an interact with a harbor, in meter
+/// The effect "diameter" within which a player an interact with a common harbor, in meter
+///
+/// See [state::Harbor::effect_size] for the actual one of each harbor.
 pub const HARBOR_EFFECT_SIZE: f32 = 7.;
 
-/// The maximum speed of the player while trading.
+/// The "diameter" of a race checkpoint, within which the ship passes it, in meter
+pub const CHECKPOINT_SIZE: f32 = 6.;
+
+/// The maximum speed of the player while trading at a common harbor.
 pub const HARBOR_MAX_SPEED: f32 = 1.;
 
-/// The maximum speed of the player at which a ship is docked.
+/// The maximum speed of the player at which a ship is docked at a common harbor.
 pub const HARBOR_DOCKING_SPEED: f32 = 0.8;
 
 /// The "diameter" of the player's car.
 pub const VEHICLE_SIZE: f32 = 1.3;
 
+/// The maximum speed of a ship in m/s
+///
+/// It lies far beyond anything the wind can reach, and only guards against
+/// numerical runaways, e.g. from extreme winds or cheats.
+pub const VEHICLE_MAX_SPEED: f32 = 1000.0;
+
 /// The mass of a empty vehicle, in kilogram
 const VEHICLE_DEADWEIGHT: f32 = 100.0;
 
 /// The physical size ("diameter") of a water resource pack.
 pub const RESOURCE_PACK_FISH_SIZE: f32 = 0.8;
 
+/// The base distance between a ship and a resource pack, at which the resource pack gets collected.
+///
+/// See `Vehicle::collection_radius` for the effective distance.
+pub const RESOURCE_PICKUP_DISTANCE: f32 = (VEHICLE_SIZE + RESOURCE_PACK_FISH_SIZE) / 2.;
+
+/// The distance behind the ship, at which cargo thrown back into the sea lands, in meter
+///
+/// It must be far enough, so the ship does not collect the cargo right again.
+pub const THROW_BACK_DISTANCE: f32 = 4.5;
+
+/// The distance from the water, up to which resources lie on the beach, in meter
+///
+/// Ships collect such resources from a bit further away, so they can be
+/// reached from the water.
+pub const BEACHCOMBING_DISTANCE: f32 = 1.0;
+
+/// The interval between resource respawns in seconds
+pub const RESOURCE_RESPAWN_INTERVAL: u16 = 1;
+
+/// The fraction of the missing resources of each type, that respawns per interval
+///
+/// See [RESOURCE_RESPAWN_INTERVAL]
+pub const RESOURCE_RESPAWN_RATE: f32 = 0.1;
+
+/// The fraction of the freshness, that the catch in the hold loses per second
+///
+/// The spoiling fish lower the worth of the cargo, so it pays to sell often.
+pub const FISH_SPOIL_RATE: f32 = 0.005;
+
+/// The least freshness of the catch, i.e. the share of its value it keeps
+///
+/// See [FISH_SPOIL_RATE]
+pub const FISH_MIN_FRESHNESS: f32 = 0.5;
+
+/// The factor by which a deployed trawl net widens the resource collection radius
+pub const NET_COLLECTION_RADIUS_FACTOR: f32 = 3.0;
+
+/// The speed in m/s, at which a magnet pulls resource packs towards the ship
+///
+/// Much slower than the ship sails, so the magnet only helps to catch the
+/// resources nearby, instead of collecting them on its own.
+///
+/// See `Magnet::radius` for its reach.
+pub const MAGNET_PULL_SPEED: f32 = 1.0;
+
 /// Scalar factor influencing the strength of ground based friction.
 ///
 /// This kind of friction gets stronger if the vehicle moves faster over ground.
@@ -52,10 +116,81 @@ pub const FRICTION_GROUND_SPEED_FACTOR: f32 = 0.1;
 /// Scalar factor influencing the strength of gronud based friction when sliding
 pub const FRICTION_CROSS_SPEED_FACTOR: f32 = 0.8;
 
+/// Scalar factor influencing the strength of the additional friction of a deployed trawl net
+pub const FRICTION_NET_FACTOR: f32 = 0.3;
+
+/// Factor of the friction of a ship sailing in deep water
+pub const DEEP_WATER_DRAG_FACTOR: f32 = 1.0;
+
+/// Factor of the friction of a ship sailing in shallow water
+///
+/// It makes the deep channels the faster routes.
+pub const SHALLOW_WATER_DRAG_FACTOR: f32 = 2.0;
+
+/// Factor of the friction of a ship sailing over ice
+///
+/// It applies on top of the drag of the water below, see `Surface::Ice`.
+pub const ICE_DRAG_FACTOR: f32 = 3.0;
+
+/// The lowest elevation, at which a ship runs aground
+///
+/// This is the shallowest passable water, just off the beach.
+pub const GROUNDING_ELEVATION: Elevation = Elevation(-2);
+
+/// Additional factor of the friction of a ship run aground
+pub const GROUNDING_DRAG_FACTOR: f32 = 10.0;
+
+/// The fraction of the hull integrity lost per second while run aground
+pub const GROUNDING_DAMAGE_RATE: f32 = 0.05;
+
+/// Additional factor of the friction of a wrecked hull, which takes on a lot of water
+///
+/// The friction grows with the damage, a hull without damage does not leak.
+pub const HULL_LEAK_DRAG_FACTOR: f32 = 1.0;
+
+/// The price of repairing a hull from wrecked to intact, relative to the value of the hull
+pub const HULL_REPAIR_PRICE_FACTOR: f32 = 0.25;
+
+/// The ticks after a collision, during which more with the same kind of surface go unreported
+///
+/// A ship pressing against the land bounces off and collides again every
+/// few ticks, which only counts as one collision until the ship got clear.
+pub const COLLISION_COOLDOWN_TICKS: u16 = TICKS_PER_SECOND / 2;
+
+/// The speed in m/s, below which a ship at the coast counts as stuck
+pub const STUCK_SPEED: f32 = 0.1;
+
+/// The speed in m/s, at which kedging off nudges a stuck ship free
+pub const KEDGE_SPEED: f32 = 1.0;
+
+/// The apparent wind speed in m/s, which a fully set sail withstands
+///
+/// A reefed sail withstands correspondingly stronger winds, see
+/// [state::Sail::strain].
+pub const SAIL_STRAIN_LIMIT: f32 = 12.0;
+
+/// The fraction of the sail condition lost per second, per strain beyond the limit
+pub const SAIL_WEAR_RATE: f32 = 0.1;
+
+/// The lowest condition of a torn sail, which still draws a bit
+pub const SAIL_MIN_CONDITION: f32 = 0.2;
+
+/// The price of mending a sail from nothing to intact, relative to the value of the sail
+pub const SAIL_REPAIR_PRICE_FACTOR: f32 = 0.5;
+
 /// The maximum steering angle in radians per steering.
 pub const VEHICLE_MAX_STEERING_ANGLE: f32 = core::f32::consts::FRAC_PI_3; // = 60 deg
 
-/// The inner length of the vehicle, it this the distance between the front and back wheels in meter
+/// The minimum turning angle in radians per tick at full rudder deflection.
+///
+/// This allows turning even if the vehicle stands still.
+pub const VEHICLE_MIN_TURNING_ANGLE: f32 = 0.02;
+
+/// The inner length of the vehicle in meter, which defines its turning circle
+///
+/// The name stems from the car physics the ship handling evolved from, it was
+/// the distance between the front and back wheels, now it acts like the
+/// length of the keel.
 pub const VEHICLE_WHEEL_BASE: f32 = 0.9 * VEHICLE_SIZE;
 
 /// Maximum amount of traction
@@ -67,18 +202,48 @@ pub const WIND_CHANGE_INTERVAL: u16 = 10;
 /// The maximum wind speed in m/s
 pub const MAX_WIND_SPEED: f32 = 15.0;
 
+/// The probability of a calm period
+pub const WIND_CALM_PROBABILITY: f64 = 0.1;
+
+/// The duration of a calm period in wind change intervals
+///
+/// See [WIND_CHANGE_INTERVAL]
+pub const WIND_CALM_DURATION: u16 = 3;
+
+/// The factor by which the wind speed is reduced during a calm period
+pub const WIND_CALM_FACTOR: f32 = 0.05;
+
 /// Number of fish variants
 pub const FISH_TYPES: u8 = 8;
 
 /// The base duration of the fish animation in seconds
 pub const FISH_ANIM_BASE_DURATION: u32 = 3;
 
+/// The interval in ticks, in which distant resources are animated
+///
+/// See [WorldInit::animation_lod].
+pub const RESOURCE_LOD_INTERVAL: u64 = 8;
+
 /// Target logical ticks per second
 pub const TICKS_PER_SECOND: u16 = 60;
 
+/// The initial state of all deterministic random number generators
+const RNG_STATE: u128 = 0xcafef00dd15ea5e5;
+
 
 
-#[derive(Debug, Clone, Copy, Default)]
+/// Creates a deterministic random number generator
+///
+/// The generator is fully determined by the `seed` (e.g. the world seed) and
+/// the `stream`, which allows to derive independent generators from the same
+/// seed.
+pub fn seeded_rng(seed: u64, stream: u128) -> StdRng {
+	StdRng::new(RNG_STATE, u128::from(seed) ^ stream)
+}
+
+
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 #[derive(Serialize, Deserialize)]
 pub struct DebuggingConf {
 	/// Give the ship an engine which will propel the ship at a constant speed
@@ -93,8 +258,35 @@ pub struct DebuggingConf {
 }
 
 
+/// Tunable parameters of the ship handling
+///
+/// The defaults give the original arcade-like handling, modders may e.g. tune
+/// a more realistic keel by increasing the `cross_friction`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicsConf {
+	/// See [FRICTION_GROUND_SPEED_FACTOR]
+	pub ground_friction: f32,
+	/// See [FRICTION_CROSS_SPEED_FACTOR]
+	pub cross_friction: f32,
+	/// See [FRICTION_NET_FACTOR]
+	pub net_friction: f32,
+}
+impl Default for PhysicsConf {
+	fn default() -> Self {
+		Self {
+			ground_friction: FRICTION_GROUND_SPEED_FACTOR,
+			cross_friction: FRICTION_CROSS_SPEED_FACTOR,
+			net_friction: FRICTION_NET_FACTOR,
+		}
+	}
+}
+
+
 /// The entire game world
 #[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
 pub struct World {
 	pub init: WorldInit,
 	pub state: WorldState,
@@ -114,6 +306,16 @@ pub struct WorldInit {
 	pub seed: u64,
 	/// Debugging configuration
 	pub dbg: DebuggingConf,
+	/// The parameters of the ship handling
+	#[serde(default)]
+	pub physics: PhysicsConf,
+	/// The distance in meter beyond which resources are animated only every
+	/// [RESOURCE_LOD_INTERVAL] ticks, or `None` to animate all every tick
+	///
+	/// This saves time on big maps. Since the animation only depends on the
+	/// tick, distant resources catch up as soon as they are animated again.
+	#[serde(default)]
+	pub animation_lod: Option<f32>,
 }
 
 
@@ -132,4 +334,7 @@ pub struct Input {
 	/// * `0.0` means neutral, straight ahead
 	/// * `+1.0` means full deflection towards the right
 	pub rudder: BiPolarFraction,
+
+	/// Whether the trawl net should be deployed
+	pub net: bool,
 }
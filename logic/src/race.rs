@@ -0,0 +1,91 @@
+//! The race mode, sailing through a course of checkpoints against the clock
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::state::Event;
+use crate::terrain::Terrain;
+use crate::units::Location;
+use crate::CHECKPOINT_SIZE;
+
+#[cfg(test)]
+mod test;
+
+
+
+/// The final result of a race
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RaceOutcome {
+	/// All checkpoints have been passed, with the elapsed ticks
+	Finished(u64),
+	/// The time ran out, with the number of checkpoints passed until then
+	TimedOut(usize),
+}
+
+/// A race through a sequence of checkpoints within a time limit
+#[derive(Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize)]
+pub struct Race {
+	/// The checkpoints, in the order they have to be passed
+	pub checkpoints: Vec<Location>,
+	/// The number of ticks available to pass all checkpoints
+	pub time_limit: u64,
+	/// The index of the next checkpoint to pass
+	pub next: usize,
+	/// The number of ticks since the start of the race
+	pub elapsed: u64,
+}
+
+impl Race {
+	/// Starts a new race through the `checkpoints`
+	pub fn new(checkpoints: Vec<Location>, time_limit: u64) -> Self {
+		Self {
+			checkpoints,
+			time_limit,
+			next: 0,
+			elapsed: 0,
+		}
+	}
+
+	/// The location of the next checkpoint to pass, if any is left
+	pub fn next_checkpoint(&self) -> Option<Location> {
+		self.checkpoints.get(self.next).copied()
+	}
+
+	/// The number of ticks left until the time runs out
+	pub fn remaining(&self) -> u64 {
+		self.time_limit.saturating_sub(self.elapsed)
+	}
+
+	/// The result of the race, or `None` while it is still running
+	pub fn outcome(&self) -> Option<RaceOutcome> {
+		if self.next >= self.checkpoints.len() {
+			Some(RaceOutcome::Finished(self.elapsed))
+		} else if self.elapsed >= self.time_limit {
+			Some(RaceOutcome::TimedOut(self.next))
+		} else {
+			None
+		}
+	}
+
+	/// Advances the race by one tick, with the ship at `pos`
+	///
+	/// Gives an event, when the ship passes the next checkpoint.
+	pub fn update(&mut self, terrain: &Terrain, pos: Location) -> Option<Event> {
+		if self.outcome().is_some() {
+			return None;
+		}
+
+		self.elapsed += 1;
+
+		let checkpoint = self.next_checkpoint()?;
+		let dist = terrain.torus_distance(pos, checkpoint).magnitude();
+		if dist < CHECKPOINT_SIZE / 2. {
+			self.next += 1;
+			Some(Event::Checkpoint(self.next - 1, checkpoint))
+		} else {
+			None
+		}
+	}
+}
@@ -0,0 +1,48 @@
+use super::Race;
+use super::RaceOutcome;
+use crate::state::Event;
+use crate::terrain::Terrain;
+use crate::units::Location;
+
+#[test]
+fn checkpoints_are_passed_in_order() {
+	// Arrange
+	let terrain = Terrain::new(16);
+	let first = Location::new(10.0, 10.0);
+	let second = Location::new(40.0, 40.0);
+	let mut race = Race::new(vec![first, second], 100);
+
+	// Act
+	let out_of_order = race.update(&terrain, second);
+	let passed_first = race.update(&terrain, first);
+	let lingering = race.update(&terrain, first);
+	let passed_second = race.update(&terrain, second);
+
+	// Assert
+	assert_eq!(out_of_order, None);
+	assert!(matches!(passed_first, Some(Event::Checkpoint(0, _))));
+	assert_eq!(lingering, None);
+	assert!(matches!(passed_second, Some(Event::Checkpoint(1, _))));
+	assert_eq!(race.next_checkpoint(), None);
+	assert_eq!(race.outcome(), Some(RaceOutcome::Finished(4)));
+}
+
+#[test]
+fn race_times_out() {
+	// Arrange
+	let terrain = Terrain::new(16);
+	let checkpoint = Location::new(10.0, 10.0);
+	let mut race = Race::new(vec![checkpoint], 3);
+
+	// Act
+	for _ in 0..3 {
+		assert_eq!(race.outcome(), None);
+		race.update(&terrain, Location::new(40.0, 40.0));
+	}
+	let too_late = race.update(&terrain, checkpoint);
+
+	// Assert
+	assert_eq!(too_late, None);
+	assert_eq!(race.remaining(), 0);
+	assert_eq!(race.outcome(), Some(RaceOutcome::TimedOut(0)));
+}
@@ -1,22 +1,34 @@
+use std::collections::BTreeMap;
 use std::f32::consts::TAU;
+use std::ops::Index;
 use std::ops::Range;
 
 use enum_map::Enum;
+use enum_map::EnumMap;
 use glm::vec2;
 use rand::Rng;
 use serde::Deserialize;
 use serde::Serialize;
+use strum::IntoEnumIterator;
 
 use super::glm;
 use crate::terrain::Terrain;
 use crate::units::Elevation;
 use crate::units::Location;
 use crate::units::Tick;
+use crate::BEACHCOMBING_DISTANCE;
 use crate::FISH_ANIM_BASE_DURATION;
 use crate::TICKS_PER_SECOND;
 
+#[cfg(test)]
+mod test;
 
 
+
+/// The number of ticks of a single harmonic of the fish animation at the
+/// speed factor `100`
+const ANIM_BASE_TICKS: u32 = FISH_ANIM_BASE_DURATION * TICKS_PER_SECOND as u32;
+
 /// Gives the resource type that can be in a resource pack
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[derive(Serialize, Deserialize)]
@@ -41,45 +53,85 @@ pub enum ResourcePackContent {
 	Starfish4,
 	Grass0,
 	Grass1,
+	Driftwood,
 }
 
-impl ResourcePackContent {
-	pub fn generate<R: Rng>(
-		self,
-		mut rng: R,
-		terrain: &Terrain,
-		amount: usize,
-	) -> Vec<ResourcePack> {
-		let mut current_set = Vec::new();
-
-		while current_set.len() < amount {
-			let loc = terrain.random_passable_location(&mut rng);
-			let loc_elev = terrain.get(loc.try_into().unwrap());
-			let school_size = rng.gen_range(self.schooling_size.clone());
-
-			if !self.spawn_location.contains(loc_elev) {
-				continue;
-			}
+/// The number of random locations tried for each resource to spawn, before spawning fewer
+const SPAWN_ATTEMPTS: usize = 64;
 
-			let org = ResourcePack::new(loc, self, &mut rng);
+/// Spawns at least `count` resources of the given `kind` on the `terrain`
+///
+/// The resources are spawned in schools, on tiles whose elevation is within
+/// the `spawn_location` of the `kind`. Resources ashore are only spawned
+/// close enough to the water, to be collectable by a ship.
+///
+/// On maps with hardly any suitable tiles, e.g. without beaches for the
+/// resources ashore, fewer or no resources are spawned, instead of searching
+/// forever.
+pub fn spawn_resources<R: Rng>(
+	table: &ResourceTable,
+	kind: ResourcePackContent,
+	terrain: &Terrain,
+	count: usize,
+	mut rng: R,
+) -> Vec<ResourcePack> {
+	let stats = &table[kind];
+	let is_suitable = |loc: Location| {
+		let elev = terrain.get(loc.try_into().unwrap());
+		stats.spawn_location.contains(elev) && (!stats.is_ashore() || is_near_water(terrain, loc))
+	};
 
-			if org.elevation < *loc_elev {
-				continue;
-			}
+	let mut current_set = Vec::new();
+
+	for _ in 0..count.saturating_mul(SPAWN_ATTEMPTS) {
+		if current_set.len() >= count {
+			break;
+		}
+
+		let loc = if stats.is_ashore() {
+			terrain.random_location(&mut rng)
+		} else {
+			terrain.random_passable_location(&mut rng)
+		};
+		let loc_elev = terrain.get(loc.try_into().unwrap());
+		let school_size = rng.gen_range(stats.schooling_size.clone());
 
-			current_set.extend((0..school_size).map(|_| {
-				let mut clone = org.clone();
-				clone.phase += rng.gen_range(0.0..TAU) / 20.;
-				clone.origin.0 += vec2(rng.gen(), rng.gen()) * 1.;
-				clone
-			}))
+		if !is_suitable(loc) {
+			continue;
 		}
 
-		current_set
+		let org = ResourcePack::new(table, loc, kind, &mut rng);
+
+		if org.elevation < *loc_elev {
+			continue;
+		}
+
+		current_set.extend((0..school_size).filter_map(|_| {
+			let mut clone = org.clone();
+			clone.phase += rng.gen_range(0.0..TAU) / 20.;
+			clone.origin.0 += vec2(rng.gen(), rng.gen()) * 1.;
+			clone.origin = terrain.map_loc_on_torus(clone.origin);
+
+			// Drop the school members that strayed off the suitable waters
+			is_suitable(clone.origin).then(|| clone)
+		}))
 	}
+
+	current_set
 }
 
-#[derive(Debug, Clone)]
+/// Checks whether there is passable water within the [BEACHCOMBING_DISTANCE] of `loc`
+fn is_near_water(terrain: &Terrain, loc: Location) -> bool {
+	[vec2(1., 0.), vec2(-1., 0.), vec2(0., 1.), vec2(0., -1.)]
+		.into_iter()
+		.any(|dir| {
+			let candidate = terrain.map_loc_on_torus(Location(loc.0 + dir * BEACHCOMBING_DISTANCE));
+			terrain.get(candidate.try_into().unwrap()).is_passable()
+		})
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize)]
 pub struct ResourcePackStats {
 	/// The resource weight in kg
 	pub weight: u32,
@@ -99,6 +151,132 @@ pub struct ResourcePackStats {
 	pub speed_factor: Range<u32>,
 }
 
+impl ResourcePackStats {
+	/// Whether this resource lies on the beach instead of in the water
+	pub fn is_ashore(&self) -> bool {
+		self.spawn_location.start >= Elevation::COAST
+	}
+
+	/// Lists the problems of these stats, which would break the game
+	fn problems(&self) -> Vec<String> {
+		let mut problems = Vec::new();
+		let mut check = |ok: bool, problem: &str| {
+			if !ok {
+				problems.push(problem.to_owned());
+			}
+		};
+		let valid_elevations = Elevation::DEEPEST..=Elevation::HIGHEST;
+
+		check(self.weight > 0, "weight must be positive");
+		check(
+			!self.schooling_size.is_empty() && self.schooling_size.start > 0,
+			"schooling size must be a non-empty range of positive numbers",
+		);
+		check(
+			self.spawn_density.is_finite() && self.spawn_density >= 0.0,
+			"spawn density must not be negative",
+		);
+		for (name, range) in [
+			("spawn elevation", &self.spawn_elevation),
+			("spawn location", &self.spawn_location),
+		] {
+			check(
+				!range.is_empty()
+					&& valid_elevations.contains(&range.start)
+					&& valid_elevations.contains(&range.end.lower()),
+				&format!("{name} must be a non-empty range of valid elevations"),
+			);
+		}
+		check(
+			!self.params_range.0.is_empty() && !self.params_range.1.is_empty(),
+			"animation parameters must be non-empty ranges",
+		);
+		check(
+			!self.speed_factor.is_empty() && self.speed_factor.start > 0,
+			"speed factor must be a non-empty range of positive numbers",
+		);
+		check(
+			self.speed_factor.end.saturating_sub(1) <= ANIM_BASE_TICKS * 100,
+			"speed factor must not shorten the animation below a single tick",
+		);
+
+		problems
+	}
+}
+
+/// The stats of all the resource types, e.g. for balancing the game
+///
+/// By default, it contains the built-in stats, which are given by
+/// dereferencing the [ResourcePackContent]. When deserialized, the listed
+/// resource types override the built-in ones, and all the stats are validated.
+#[derive(Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize)]
+#[serde(
+	try_from = "BTreeMap<ResourcePackContent, ResourcePackStats>",
+	into = "BTreeMap<ResourcePackContent, ResourcePackStats>"
+)]
+pub struct ResourceTable(EnumMap<ResourcePackContent, ResourcePackStats>);
+
+impl ResourceTable {
+	/// Checks that the stats of every resource type are usable
+	///
+	/// Returns the list of problems, if any.
+	pub fn validate(&self) -> Result<(), Vec<String>> {
+		let problems = Vec::from_iter(self.0.iter().flat_map(|(kind, stats)| {
+			stats
+				.problems()
+				.into_iter()
+				.map(move |problem| format!("{kind:?}: {problem}"))
+		}));
+
+		if problems.is_empty() {
+			Ok(())
+		} else {
+			Err(problems)
+		}
+	}
+}
+
+impl Default for ResourceTable {
+	fn default() -> Self {
+		Self(EnumMap::from_fn(|kind: ResourcePackContent| {
+			(*kind).clone()
+		}))
+	}
+}
+
+impl Index<ResourcePackContent> for ResourceTable {
+	type Output = ResourcePackStats;
+
+	fn index(&self, kind: ResourcePackContent) -> &Self::Output {
+		&self.0[kind]
+	}
+}
+
+impl TryFrom<BTreeMap<ResourcePackContent, ResourcePackStats>> for ResourceTable {
+	type Error = String;
+
+	fn try_from(
+		overrides: BTreeMap<ResourcePackContent, ResourcePackStats>,
+	) -> Result<Self, Self::Error> {
+		let mut table = Self::default();
+		for (kind, stats) in overrides {
+			table.0[kind] = stats;
+		}
+
+		table.validate().map_err(|problems| problems.join("\n"))?;
+		Ok(table)
+	}
+}
+
+impl From<ResourceTable> for BTreeMap<ResourcePackContent, ResourcePackStats> {
+	fn from(table: ResourceTable) -> Self {
+		ResourcePackContent::iter()
+			.map(|kind| (kind, table.0[kind].clone()))
+			.collect()
+	}
+}
+
 const NO_SCHOOLING: Range<usize> = 1..2;
 
 enumeraties::props! {
@@ -258,8 +436,8 @@ enumeraties::props! {
 			value: 1,
 			schooling_size: NO_SCHOOLING,
 			spawn_density: 0.03,
-			spawn_elevation: Elevation(-1)..Elevation(0),
-			spawn_location: Elevation(-12)..Elevation(0),
+			spawn_elevation: Elevation(0)..Elevation(1),
+			spawn_location: Elevation(0)..Elevation(1),
 			params_range: (0..1,0..1),
 			speed_factor: 1..15,
 		}
@@ -273,6 +451,16 @@ enumeraties::props! {
 			params_range: (0..1,0..1),
 			speed_factor: 1..20,
 		}
+		Self::Driftwood => {
+			weight: 12,
+			value: 3,
+			schooling_size: NO_SCHOOLING,
+			spawn_density: 0.05,
+			spawn_elevation: Elevation(0)..Elevation(1),
+			spawn_location: Elevation(0)..Elevation(1),
+			params_range: (0..1,0..1),
+			speed_factor: 1..2,
+		}
 	}
 }
 
@@ -302,65 +490,79 @@ pub struct ResourcePack {
 	pub backwards: bool,
 }
 impl ResourcePack {
-	pub fn new<R: Rng>(loc: Location, kind: ResourcePackContent, mut rng: R) -> Self {
+	pub fn new<R: Rng>(
+		table: &ResourceTable,
+		loc: Location,
+		kind: ResourcePackContent,
+		mut rng: R,
+	) -> Self {
+		let stats = &table[kind];
 		Self {
 			content: kind,
 			loc: Default::default(),
-			elevation: rng.gen_range(kind.spawn_elevation.clone()),
+			elevation: rng.gen_range(stats.spawn_elevation.clone()),
 			ori: 0.,
 			origin: loc,
 			params: (
-				rng.gen_range(kind.params_range.0.clone()),
-				rng.gen_range(kind.params_range.1.clone()),
+				rng.gen_range(stats.params_range.0.clone()),
+				rng.gen_range(stats.params_range.1.clone()),
 			), // (0,0) for starfish
 			phase: rng.gen_range(0.0..TAU),
-			speed_factor: rng.gen_range(kind.speed_factor.clone()),
+			speed_factor: rng.gen_range(stats.speed_factor.clone()),
 			backwards: rng.gen(),
 		}
 	}
 
+	/// Whether this resource lies on the beach instead of in the water
+	pub fn is_ashore(&self) -> bool {
+		self.elevation >= Elevation::COAST
+	}
+
 	pub fn update(&mut self, current_tick: Tick) {
+		// Resources ashore just lie around
+		if self.is_ashore() {
+			self.loc = self.origin;
+			self.ori = self.phase;
+			return;
+		}
+
 		// Forwardness factor, `1` if forward, `-1` if backwards
 		let forwardness = (1 - 2 * self.backwards as i8) as f32;
 
 		// The total animation cycle duration
-		let duration = u32::from(1 + self.params.0.unsigned_abs() + self.params.1.unsigned_abs())
-			* 100 / self.speed_factor;
-		let duration = duration * (FISH_ANIM_BASE_DURATION * u32::from(TICKS_PER_SECOND));
+		// Scaled before dividing by the speed factor, and at least a single tick
+		let harmonics =
+			1 + u32::from(self.params.0.unsigned_abs()) + u32::from(self.params.1.unsigned_abs());
+		let duration = (harmonics * ANIM_BASE_TICKS * 100 / self.speed_factor).max(1);
 		// The current progress through the animation
 		let progress = forwardness
 			* (self.phase + TAU * (current_tick.0 % u64::from(duration)) as f32 / duration as f32);
 
-		// The position function
-		let base = vec2(progress.sin(), progress.cos());
-		let first = if self.params.0 == 0 {
-			vec2(0., 0.)
-		} else {
-			vec2(
-				(progress * self.params.0 as f32).sin(),
-				(progress * self.params.0 as f32).cos(),
-			)
-		};
-		let second = if self.params.0 == 0 {
-			vec2(0., 0.)
-		} else {
-			vec2(
-				(progress * self.params.1 as f32).sin(),
-				(progress * self.params.1 as f32).cos(),
-			)
+		// A single harmonic of the position function and its derivation.
+		// A frequency of zero is left out entirely, since it would only add a
+		// constant offset to the position.
+		let harmonic = |freq: i8| {
+			if freq == 0 {
+				(vec2(0., 0.), vec2(0., 0.))
+			} else {
+				let freq = f32::from(freq);
+				(
+					vec2((progress * freq).sin(), (progress * freq).cos()),
+					vec2(
+						(progress * freq).cos() * freq,
+						-(progress * freq).sin() * freq,
+					),
+				)
+			}
 		};
+		let (base, d_base) = harmonic(1);
+		let (first, d_first) = harmonic(self.params.0);
+		let (second, d_second) = harmonic(self.params.1);
+
+		// The position function
 		self.loc = Location(self.origin.0 + base + first + second);
 
 		// Derivation of the position function (i.e. the orientation vector)
-		let d_base = vec2(progress.cos(), -progress.sin());
-		let d_first = vec2(
-			(progress * self.params.0 as f32).cos() * self.params.0 as f32,
-			-(progress * self.params.0 as f32).sin() * self.params.0 as f32,
-		);
-		let d_second = vec2(
-			(progress * self.params.1 as f32).cos() * self.params.1 as f32,
-			-(progress * self.params.1 as f32).sin() * self.params.1 as f32,
-		);
 		let d_vec = forwardness * (d_base + d_first + d_second);
 
 		self.ori = f32::atan2(d_vec.y, d_vec.x);
@@ -0,0 +1,325 @@
+use std::collections::BTreeMap;
+use std::f32::consts::PI;
+use std::f32::consts::TAU;
+
+use rand::SeedableRng;
+use strum::IntoEnumIterator;
+
+use super::spawn_resources;
+use super::ResourcePack;
+use super::ResourcePackContent;
+use super::ResourceTable;
+use crate::terrain::Terrain;
+use crate::units::Elevation;
+use crate::units::Location;
+use crate::units::Tick;
+use crate::StdRng;
+use crate::FISH_ANIM_BASE_DURATION;
+use crate::TICKS_PER_SECOND;
+
+fn fish(params: (i8, i8), backwards: bool) -> ResourcePack {
+	ResourcePack {
+		content: ResourcePackContent::Fish0,
+		loc: Location::ORIGIN,
+		ori: 0.0,
+		elevation: Elevation::DEEPEST,
+		origin: Location::new(10.0, 10.0),
+		params,
+		phase: 0.5,
+		speed_factor: 100,
+		backwards,
+	}
+}
+
+fn loc_at(mut fish: ResourcePack, tick: u64) -> Location {
+	fish.update(Tick(tick));
+	fish.loc
+}
+
+/// The number of ticks of a full animation cycle of the given fish
+///
+/// Every harmonic takes the base duration at the speed factor `100`, and the
+/// speed factor scales it inversely. Only use it for speed factors at which
+/// the cycle spans a whole number of ticks.
+fn anim_period(fish: &ResourcePack) -> u64 {
+	let harmonics = 1 + f64::from(fish.params.0.unsigned_abs() + fish.params.1.unsigned_abs());
+	let seconds =
+		harmonics * f64::from(FISH_ANIM_BASE_DURATION) * 100.0 / f64::from(fish.speed_factor);
+	let ticks = seconds * f64::from(TICKS_PER_SECOND);
+	assert_eq!(ticks.fract(), 0.0, "period of {ticks} ticks");
+
+	ticks as u64
+}
+
+/// Checks that the orientation follows the numerical derivation of the location
+fn assert_ori_follows_loc(params: (i8, i8), backwards: bool) {
+	assert_ori_follows_loc_of(&fish(params, backwards));
+}
+
+/// Checks that the orientation follows the numerical derivation of the location
+fn assert_ori_follows_loc_of(fish: &ResourcePack) {
+	let params = fish.params;
+
+	for tick in [17, 100, 250, 333, 421] {
+		// Arrange
+		let before = loc_at(fish.clone(), tick - 1);
+		let after = loc_at(fish.clone(), tick + 1);
+		let numerical = (after - before).0;
+		let expected = f32::atan2(numerical.y, numerical.x);
+
+		// Act
+		let mut actual = fish.clone();
+		actual.update(Tick(tick));
+
+		// Assert
+		let diff = (actual.ori - expected + PI).rem_euclid(TAU) - PI;
+		assert!(
+			diff.abs() < 0.1,
+			"params {params:?} at tick {tick}: ori {} vs. {expected}",
+			actual.ori
+		);
+	}
+}
+
+#[test]
+fn ori_follows_loc_only_second_harmonic() {
+	assert_ori_follows_loc((0, 5), false);
+	assert_ori_follows_loc((0, 7), true);
+}
+
+#[test]
+fn ori_follows_loc_only_first_harmonic() {
+	assert_ori_follows_loc((2, 0), true);
+}
+
+#[test]
+fn ori_follows_loc_both_harmonics() {
+	assert_ori_follows_loc((-3, 4), false);
+	assert_ori_follows_loc((-9, 2), false);
+}
+
+#[test]
+fn ori_follows_loc_at_various_speeds() {
+	for speed_factor in [1, 7, 25, 150] {
+		for params in [(0, 0), (3, 0), (-2, 5)] {
+			let mut fish = fish(params, speed_factor % 2 == 0);
+			fish.speed_factor = speed_factor;
+			assert_ori_follows_loc_of(&fish);
+		}
+	}
+}
+
+#[test]
+fn animation_is_periodic() {
+	for speed_factor in [1, 9, 100, 150] {
+		for params in [(0, 0), (2, 0), (-3, 4), (-9, 2)] {
+			// Arrange
+			let mut fish = fish(params, speed_factor > 50);
+			fish.speed_factor = speed_factor;
+			let period = anim_period(&fish);
+
+			for tick in [0, 42, 1234] {
+				// Act
+				let mut now = fish.clone();
+				now.update(Tick(tick));
+				let mut later = fish.clone();
+				later.update(Tick(tick + period));
+
+				// Assert
+				assert_eq!(now.loc, later.loc, "{params:?} at speed {speed_factor}");
+				assert_eq!(now.ori, later.ori, "{params:?} at speed {speed_factor}");
+			}
+		}
+	}
+}
+
+#[test]
+fn fastest_animation_does_not_panic() {
+	// Arrange
+	let mut fish = fish((0, 0), false);
+	fish.speed_factor = 100 * FISH_ANIM_BASE_DURATION * u32::from(TICKS_PER_SECOND);
+
+	for tick in [0, 1, 42] {
+		// Act
+		let mut now = fish.clone();
+		now.update(Tick(tick));
+
+		// Assert
+		assert!(now.loc.0.x.is_finite() && now.loc.0.y.is_finite());
+	}
+}
+
+#[test]
+fn speed_factor_below_a_tick_is_a_problem() {
+	// Arrange
+	let mut stats = (*ResourcePackContent::Fish0).clone();
+	stats.speed_factor = 1..100 * FISH_ANIM_BASE_DURATION * u32::from(TICKS_PER_SECOND) + 2;
+
+	// Act
+	let table = ResourceTable::try_from(BTreeMap::from([(ResourcePackContent::Fish0, stats)]));
+
+	// Assert
+	let problems = table.unwrap_err();
+	assert!(problems.contains("speed factor"), "{problems}");
+}
+
+#[test]
+fn backwards_reverses_the_path() {
+	for params in [(0, 0), (2, 0), (-3, 4)] {
+		// Arrange
+		let forwards = fish(params, false);
+		let backwards = ResourcePack {
+			phase: -forwards.phase,
+			..fish(params, true)
+		};
+		let period = anim_period(&forwards);
+
+		for tick in [1, 100, 250, 421] {
+			// Act
+			let mut ahead = forwards.clone();
+			ahead.update(Tick(period - tick));
+			let mut back = backwards.clone();
+			back.update(Tick(tick));
+
+			// Assert
+			// The same location, but heading the opposite direction
+			let dist = (ahead.loc - back.loc).magnitude();
+			assert!(dist < 1e-3, "{params:?} at tick {tick}: {dist} m apart");
+			let diff = (ahead.ori - back.ori).rem_euclid(TAU) - PI;
+			assert!(
+				diff.abs() < 1e-2,
+				"{params:?} at tick {tick}: {diff} rad off"
+			);
+		}
+	}
+}
+
+#[test]
+fn no_harmonics_move_in_a_circle() {
+	// Arrange
+	let fish = fish((0, 0), false);
+
+	// Act
+	let loc = loc_at(fish.clone(), 123);
+
+	// Assert
+	let radius = (loc - fish.origin).magnitude();
+	assert!((radius - 1.0).abs() < 1e-4);
+}
+
+#[test]
+fn spawned_resources_are_within_their_spawn_location() {
+	// Arrange
+	// Water of every depth and beaches, so that every resource type finds a place to spawn
+	let mut terrain = Terrain::new(16);
+	for (tc, elev) in terrain.iter_mut() {
+		*elev = Elevation(-18 + ((tc.x + tc.y) % 19) as i16);
+	}
+	let mut rng = StdRng::seed_from_u64(42);
+	let table = ResourceTable::default();
+
+	for kind in ResourcePackContent::iter() {
+		// Act
+		let resources = spawn_resources(&table, kind, &terrain, 20, &mut rng);
+
+		// Assert
+		assert!(resources.len() >= 20);
+		for r in resources {
+			assert_eq!(r.content, kind);
+			let elev = terrain.get(r.origin.try_into().unwrap());
+			assert!(
+				kind.spawn_location.contains(elev),
+				"{kind:?} spawned at {elev:?}"
+			);
+		}
+	}
+}
+
+#[test]
+fn resources_without_suitable_tiles_give_up_spawning() {
+	// Arrange
+	// Only deep water, thus no beaches at all
+	let mut terrain = Terrain::new(16);
+	for (_, elev) in terrain.iter_mut() {
+		*elev = Elevation::DEEPEST;
+	}
+	let mut rng = StdRng::seed_from_u64(42);
+	let table = ResourceTable::default();
+	let ashore =
+		Vec::from_iter(ResourcePackContent::iter().filter(|kind| table[*kind].is_ashore()));
+	assert!(!ashore.is_empty());
+
+	for kind in ashore {
+		// Act
+		let resources = spawn_resources(&table, kind, &terrain, 20, &mut rng);
+
+		// Assert
+		assert!(resources.is_empty(), "{kind:?}");
+	}
+}
+
+#[test]
+fn ashore_resources_lie_still() {
+	// Arrange
+	let mut driftwood = fish((0, 0), false);
+	driftwood.content = ResourcePackContent::Driftwood;
+	driftwood.elevation = Elevation(0);
+
+	for tick in [0, 17, 100] {
+		// Act
+		let loc = loc_at(driftwood.clone(), tick);
+
+		// Assert
+		assert_eq!(loc, driftwood.origin);
+	}
+}
+
+#[test]
+fn default_resource_table_is_valid() {
+	// Act
+	let table = ResourceTable::default();
+
+	// Assert
+	assert_eq!(table.validate(), Ok(()));
+	assert_eq!(
+		table[ResourcePackContent::Fish0],
+		*ResourcePackContent::Fish0
+	);
+}
+
+#[test]
+fn resource_table_overrides_the_given_kinds() {
+	// Arrange
+	let mut stats = (*ResourcePackContent::Grass0).clone();
+	stats.value = 7;
+
+	// Act
+	let table = ResourceTable::try_from(BTreeMap::from([(
+		ResourcePackContent::Grass0,
+		stats.clone(),
+	)]));
+
+	// Assert
+	let table = table.unwrap();
+	assert_eq!(table[ResourcePackContent::Grass0], stats);
+	assert_eq!(
+		table[ResourcePackContent::Grass1],
+		*ResourcePackContent::Grass1
+	);
+}
+
+#[test]
+fn resource_table_rejects_empty_ranges() {
+	// Arrange
+	let mut stats = (*ResourcePackContent::Fish0).clone();
+	stats.schooling_size = 3..3;
+	stats.spawn_location = Elevation(-5)..Elevation(-12);
+
+	// Act
+	let table = ResourceTable::try_from(BTreeMap::from([(ResourcePackContent::Fish0, stats)]));
+
+	// Assert
+	let problems = table.unwrap_err();
+	assert!(problems.contains("schooling size"), "{problems}");
+	assert!(problems.contains("spawn location"), "{problems}");
+}
@@ -0,0 +1,151 @@
+//! Compact binary save files of entire worlds
+//!
+//! A save file consists of [SAVE_MAGIC], followed by the format version as
+//! little-endian `u16`, followed by the `bincode` encoded [World].
+//!
+//! Saves of older versions are decoded into the layout of their version and
+//! then upgraded step by step to the current layout, see [migrate].
+//!
+
+use std::fmt;
+
+use crate::World;
+
+#[cfg(test)]
+mod test;
+mod v1;
+mod v2;
+mod v3;
+mod v4;
+mod v5;
+mod v6;
+mod v7;
+mod v8;
+
+
+
+/// The first bytes of every save file
+pub const SAVE_MAGIC: [u8; 4] = *b"PoFS";
+
+/// The version of the save format written by this build
+///
+/// Increment it with every change of the layout of [World], and add a
+/// migration from the previous version to [migrate].
+pub const SAVE_VERSION: u16 = 9;
+
+/// The oldest version of the save format, which can still be loaded
+pub const OLDEST_SAVE_VERSION: u16 = 1;
+
+
+/// Represents the reason for the failure of loading a save file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+	/// The file does not start with [SAVE_MAGIC]
+	NotASave,
+	/// The file was saved in a format version not between
+	/// [OLDEST_SAVE_VERSION] and [SAVE_VERSION]
+	UnsupportedVersion(u16),
+	/// The content could not be decoded
+	Corrupted(String),
+}
+impl fmt::Display for LoadError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NotASave => write!(f, "Not a save file"),
+			Self::UnsupportedVersion(version) if *version < OLDEST_SAVE_VERSION => {
+				write!(
+					f,
+					"The save file has the outdated version {version}, but only versions \
+					 {OLDEST_SAVE_VERSION} to {SAVE_VERSION} are supported"
+				)
+			},
+			Self::UnsupportedVersion(version) => {
+				write!(
+					f,
+					"The save file has the version {version} of a newer game, but only versions \
+					 {OLDEST_SAVE_VERSION} to {SAVE_VERSION} are supported"
+				)
+			},
+			Self::Corrupted(err) => write!(f, "The save file is corrupted: {err}"),
+		}
+	}
+}
+impl std::error::Error for LoadError {}
+
+
+impl World {
+	/// Encodes the world as a save file of the current [SAVE_VERSION]
+	pub fn save_versioned(&self) -> Vec<u8> {
+		let mut bytes = Vec::from(SAVE_MAGIC);
+		bytes.extend_from_slice(&SAVE_VERSION.to_le_bytes());
+		bincode::serialize_into(&mut bytes, self).expect("Failed to encode the world");
+		bytes
+	}
+
+	/// Decodes a world from a save file, as written by [Self::save_versioned]
+	pub fn load_versioned(bytes: &[u8]) -> Result<Self, LoadError> {
+		let content = bytes.strip_prefix(&SAVE_MAGIC).ok_or(LoadError::NotASave)?;
+		let (version, content) = match content {
+			[lo, hi, content @ ..] => (u16::from_le_bytes([*lo, *hi]), content),
+			_ => return Err(LoadError::NotASave),
+		};
+
+		migrate(version, content)
+	}
+}
+
+
+fn decode<'a, T: serde::Deserialize<'a>>(content: &'a [u8]) -> Result<T, LoadError> {
+	bincode::deserialize(content).map_err(|err| LoadError::Corrupted(err.to_string()))
+}
+
+/// Decodes the `content` of a save file of the given format `version`
+///
+/// Old versions are upgraded to the current layout one version at a time,
+/// so every version only needs a migration to its successor.
+pub fn migrate(version: u16, content: &[u8]) -> Result<World, LoadError> {
+	match version {
+		1 => decode(content).map(from_v1),
+		2 => decode(content).map(from_v2),
+		3 => decode(content).map(from_v3),
+		4 => decode(content).map(from_v4),
+		5 => decode(content).map(from_v5),
+		6 => decode(content).map(from_v6),
+		7 => decode(content).map(from_v7),
+		8 => decode(content).map(from_v8),
+		SAVE_VERSION => decode(content),
+		_ => Err(LoadError::UnsupportedVersion(version)),
+	}
+}
+
+fn from_v1(world: v1::World) -> World {
+	from_v2(world.into())
+}
+
+fn from_v2(world: v2::World) -> World {
+	from_v3(world.into())
+}
+
+fn from_v3(world: v3::World) -> World {
+	from_v4(world.into())
+}
+
+fn from_v4(world: v4::World) -> World {
+	from_v5(world.into())
+}
+
+fn from_v5(world: v5::World) -> World {
+	from_v6(world.into())
+}
+
+fn from_v6(world: v6::World) -> World {
+	from_v7(world.into())
+}
+
+fn from_v7(world: v7::World) -> World {
+	from_v8(world.into())
+}
+
+fn from_v8(world: v8::World) -> World {
+	world.into()
+}
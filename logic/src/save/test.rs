@@ -0,0 +1,706 @@
+use rand::SeedableRng;
+
+use super::v1;
+use super::v2;
+use super::v3;
+use super::v4;
+use super::v5;
+use super::v6;
+use super::v7;
+use super::v8;
+use super::LoadError;
+use super::SAVE_MAGIC;
+use super::SAVE_VERSION;
+use crate::generator::Generator;
+use crate::generator::PerlinNoise;
+use crate::generator::Setting;
+use crate::state::CollisionSurface;
+use crate::state::Magnet;
+use crate::terrain::Surface;
+use crate::terrain::TileCoord;
+use crate::units::Fraction;
+use crate::units::Tick;
+use crate::DebuggingConf;
+use crate::StdRng;
+use crate::World;
+use crate::HARBOR_EFFECT_SIZE;
+
+fn world() -> World {
+	let setting = Setting {
+		edge_length: 16,
+		resource_density: 1.0,
+		resources: Default::default(),
+	};
+
+	PerlinNoise.generate(&setting, StdRng::seed_from_u64(42))
+}
+
+/// The static part of the `world` in the layout of version 8
+fn v8_init(world: &World) -> v8::WorldInit {
+	let init = world.init.clone();
+	v8::WorldInit {
+		terrain_setting: init.terrain_setting,
+		terrain: v8::Terrain {
+			edge_length: init.terrain.edge_length,
+			playground: init.terrain.playground,
+		},
+		seed: init.seed,
+		dbg: init.dbg,
+		physics: init.physics,
+		animation_lod: init.animation_lod,
+	}
+}
+
+/// The harbors of the `world` in the layout of version 4
+fn v4_harbors(world: &World) -> Vec<v4::Harbor> {
+	world
+		.state
+		.harbors
+		.iter()
+		.map(|h| {
+			v4::Harbor {
+				loc: h.loc,
+				orientation: h.orientation,
+			}
+		})
+		.collect()
+}
+
+/// The player of the `world` in the layout of version 7
+fn v7_player(world: &World) -> v7::Player {
+	let crate::state::Vehicle {
+		hull,
+		magnet,
+		pos,
+		velocity,
+		heading,
+		prev_pos,
+		prev_heading,
+		angle_of_list,
+		rudder,
+		sail,
+		net_deployed,
+		resource_weight,
+		resource_value,
+		cargo,
+		freshness: _,
+		hull_integrity,
+		grounded,
+		anchored,
+		collision_cooldown,
+	} = world.state.player.vehicle;
+
+	v7::Player {
+		vehicle: v7::Vehicle {
+			hull,
+			magnet,
+			pos,
+			velocity,
+			heading,
+			prev_pos,
+			prev_heading,
+			angle_of_list,
+			rudder,
+			sail,
+			net_deployed,
+			resource_weight,
+			resource_value,
+			cargo,
+			hull_integrity,
+			grounded,
+			anchored,
+			collision_cooldown,
+		},
+		money: world.state.player.money,
+	}
+}
+
+/// The player of the `world` in the layout of version 6
+fn v6_player(world: &World) -> v6::Player {
+	let crate::state::Vehicle {
+		hull,
+		magnet: _,
+		pos,
+		velocity,
+		heading,
+		prev_pos,
+		prev_heading,
+		angle_of_list,
+		rudder,
+		sail,
+		net_deployed,
+		resource_weight,
+		resource_value,
+		cargo,
+		freshness: _,
+		hull_integrity,
+		grounded,
+		anchored,
+		collision_cooldown,
+	} = world.state.player.vehicle;
+
+	v6::Player {
+		vehicle: v6::Vehicle {
+			hull,
+			pos,
+			velocity,
+			heading,
+			prev_pos,
+			prev_heading,
+			angle_of_list,
+			rudder,
+			sail,
+			net_deployed,
+			resource_weight,
+			resource_value,
+			cargo,
+			hull_integrity,
+			grounded,
+			anchored,
+			collision_cooldown,
+		},
+		money: world.state.player.money,
+	}
+}
+
+/// The player of the `world` in the layout of version 5
+fn v5_player(world: &World) -> v5::Player {
+	let crate::state::Vehicle {
+		hull,
+		magnet: _,
+		pos,
+		velocity,
+		heading,
+		prev_pos,
+		prev_heading,
+		angle_of_list,
+		rudder,
+		sail,
+		net_deployed,
+		resource_weight,
+		resource_value,
+		cargo,
+		freshness: _,
+		hull_integrity,
+		grounded,
+		anchored,
+		collision_cooldown: _,
+	} = world.state.player.vehicle;
+
+	v5::Player {
+		vehicle: v5::Vehicle {
+			hull,
+			pos,
+			velocity,
+			heading,
+			prev_pos,
+			prev_heading,
+			angle_of_list,
+			rudder,
+			sail,
+			net_deployed,
+			resource_weight,
+			resource_value,
+			cargo,
+			hull_integrity,
+			grounded,
+			anchored,
+		},
+		money: world.state.player.money,
+	}
+}
+
+/// The player of the `world` in the layout of version 3
+fn v3_player(world: &World) -> v3::Player {
+	let crate::state::Vehicle {
+		hull,
+		magnet: _,
+		pos,
+		velocity,
+		heading,
+		prev_pos,
+		prev_heading,
+		angle_of_list,
+		rudder,
+		sail,
+		net_deployed,
+		resource_weight,
+		resource_value,
+		cargo,
+		freshness: _,
+		hull_integrity,
+		grounded,
+		anchored,
+		collision_cooldown: _,
+	} = world.state.player.vehicle;
+
+	v3::Player {
+		vehicle: v3::Vehicle {
+			hull,
+			pos,
+			velocity,
+			heading,
+			prev_pos,
+			prev_heading,
+			angle_of_list,
+			rudder,
+			sail: v3::Sail {
+				kind: sail.kind,
+				condition: Default::default(),
+				reefing: sail.reefing,
+				orientation_rectangle: sail.orientation_rectangle,
+				orientation_triangle: sail.orientation_triangle,
+			},
+			net_deployed,
+			resource_weight,
+			resource_value,
+			cargo,
+			hull_integrity,
+			grounded,
+			anchored,
+		},
+		money: world.state.player.money,
+	}
+}
+
+#[test]
+fn save_round_trips() {
+	// Arrange
+	let mut world = world();
+	world.state.player.money = 1234;
+
+	// Act
+	let bytes = world.save_versioned();
+	let loaded = World::load_versioned(&bytes).unwrap();
+
+	// Assert
+	assert!(bytes.starts_with(&SAVE_MAGIC));
+	assert_eq!(loaded.init.seed, world.init.seed);
+	assert_eq!(loaded.state.player.money, 1234);
+	assert_eq!(loaded.state.resources.len(), world.state.resources.len());
+	assert_eq!(loaded.save_versioned(), bytes);
+}
+
+#[test]
+fn sail_condition_round_trips() {
+	// Arrange
+	let mut world = world();
+	world.state.player.vehicle.sail.condition = 0.375;
+
+	// Act
+	let loaded = World::load_versioned(&world.save_versioned()).unwrap();
+
+	// Assert
+	assert_eq!(loaded.state.player.vehicle.sail.condition, 0.375);
+}
+
+#[test]
+fn version_3_sail_leftover_is_not_taken_as_wear() {
+	// Arrange
+	let world = world();
+	let mut player = v3_player(&world);
+	// The unused left-over of the car physics
+	player.vehicle.sail.condition = Fraction(96);
+	let old = v3::World {
+		init: v8_init(&world),
+		state: v3::WorldState {
+			timestamp: Tick(0),
+			player,
+			resources: Vec::new(),
+			harbors: v4_harbors(&world),
+			obstacles: Vec::new(),
+			wind: world.state.wind,
+			race: None,
+		},
+	};
+	let mut bytes = Vec::from(SAVE_MAGIC);
+	bytes.extend_from_slice(&3_u16.to_le_bytes());
+	bytes.extend(bincode::serialize(&old).unwrap());
+
+	// Act
+	let migrated = World::load_versioned(&bytes).unwrap();
+
+	// Assert
+	assert_eq!(migrated.state.player.vehicle.sail.condition, 1.0);
+	assert_eq!(
+		migrated.state.player.vehicle.sail.sail_area(),
+		world.state.player.vehicle.sail.sail_area()
+	);
+}
+
+#[test]
+fn debugging_conf_round_trips() {
+	// Arrange
+	let mut world = world();
+	world.init.dbg = DebuggingConf {
+		ship_engine: true,
+		wind_turning: false,
+		fixed_wind_direction: Some(1.5),
+	};
+
+	// Act
+	let loaded = World::load_versioned(&world.save_versioned()).unwrap();
+
+	// Assert
+	assert_eq!(loaded.init.dbg, world.init.dbg);
+}
+
+#[test]
+fn old_version_is_rejected() {
+	// Arrange
+	let mut bytes = world().save_versioned();
+	bytes[SAVE_MAGIC.len()..][..2].copy_from_slice(&0_u16.to_le_bytes());
+
+	// Act
+	let err = World::load_versioned(&bytes).unwrap_err();
+
+	// Assert
+	assert_eq!(err, LoadError::UnsupportedVersion(0));
+	let msg = err.to_string();
+	assert!(msg.contains("outdated version 0"), "{msg}");
+	assert!(msg.contains(&format!("to {SAVE_VERSION}")), "{msg}");
+}
+
+#[test]
+fn newer_version_is_rejected() {
+	// Arrange
+	let mut bytes = world().save_versioned();
+	bytes[SAVE_MAGIC.len()..][..2].copy_from_slice(&(SAVE_VERSION + 1).to_le_bytes());
+
+	// Act
+	let err = World::load_versioned(&bytes).unwrap_err();
+
+	// Assert
+	assert_eq!(err, LoadError::UnsupportedVersion(SAVE_VERSION + 1));
+	assert!(err.to_string().contains("newer game"), "{err}");
+}
+
+#[test]
+fn version_1_save_is_migrated() {
+	// Arrange
+	let world = world();
+	let v3::Vehicle {
+		hull,
+		pos,
+		velocity,
+		heading,
+		prev_pos,
+		prev_heading,
+		angle_of_list,
+		rudder,
+		sail,
+		net_deployed,
+		resource_weight,
+		resource_value,
+		cargo,
+		hull_integrity: _,
+		grounded,
+		anchored: _,
+	} = v3_player(&world).vehicle;
+	let old = v1::World {
+		init: v8_init(&world),
+		state: v1::WorldState {
+			timestamp: Tick(1234),
+			player: v1::Player {
+				vehicle: v1::Vehicle {
+					hull,
+					pos,
+					velocity,
+					heading,
+					prev_pos,
+					prev_heading,
+					angle_of_list,
+					rudder,
+					sail,
+					net_deployed,
+					resource_weight,
+					resource_value,
+					cargo,
+					hull_integrity: 0.5,
+					grounded,
+				},
+				money: 42,
+			},
+			resources: world.state.resources.clone(),
+			harbors: v4_harbors(&world),
+			obstacles: world.state.obstacles.clone(),
+			wind: world.state.wind,
+		},
+	};
+	let mut bytes = Vec::from(SAVE_MAGIC);
+	bytes.extend_from_slice(&1_u16.to_le_bytes());
+	bytes.extend(bincode::serialize(&old).unwrap());
+
+	// Act
+	let migrated = World::load_versioned(&bytes).unwrap();
+
+	// Assert
+	assert_eq!(migrated.state.timestamp, Tick(1234));
+	assert_eq!(migrated.state.player.money, 42);
+	assert_eq!(migrated.state.player.vehicle.hull_integrity, 0.5);
+	assert!(!migrated.state.player.vehicle.anchored);
+	assert_eq!(migrated.state.resources.len(), world.state.resources.len());
+}
+
+#[test]
+fn version_2_save_is_migrated() {
+	// Arrange
+	let world = world();
+	let old = v2::World {
+		init: v8_init(&world),
+		state: v2::WorldState {
+			timestamp: Tick(1234),
+			player: v3_player(&world),
+			resources: world.state.resources.clone(),
+			harbors: v4_harbors(&world),
+			obstacles: world.state.obstacles.clone(),
+			wind: world.state.wind,
+		},
+	};
+	let mut bytes = Vec::from(SAVE_MAGIC);
+	bytes.extend_from_slice(&2_u16.to_le_bytes());
+	bytes.extend(bincode::serialize(&old).unwrap());
+
+	// Act
+	let migrated = World::load_versioned(&bytes).unwrap();
+
+	// Assert
+	assert_eq!(migrated.state.timestamp, Tick(1234));
+	assert!(migrated.state.race.is_none());
+	assert_eq!(migrated.state.harbors.len(), world.state.harbors.len());
+}
+
+#[test]
+fn version_3_save_is_migrated() {
+	// Arrange
+	let world = world();
+	let mut player = v3_player(&world);
+	player.money = 42;
+	let old = v3::World {
+		init: v8_init(&world),
+		state: v3::WorldState {
+			timestamp: Tick(1234),
+			player,
+			resources: world.state.resources.clone(),
+			harbors: v4_harbors(&world),
+			obstacles: world.state.obstacles.clone(),
+			wind: world.state.wind,
+			race: None,
+		},
+	};
+	let mut bytes = Vec::from(SAVE_MAGIC);
+	bytes.extend_from_slice(&3_u16.to_le_bytes());
+	bytes.extend(bincode::serialize(&old).unwrap());
+
+	// Act
+	let migrated = World::load_versioned(&bytes).unwrap();
+
+	// Assert
+	assert_eq!(migrated.state.player.money, 42);
+	assert_eq!(migrated.state.player.vehicle.sail.condition, 1.0);
+	assert_eq!(
+		migrated.state.player.vehicle.sail.kind,
+		world.state.player.vehicle.sail.kind
+	);
+}
+
+#[test]
+fn version_4_save_is_migrated() {
+	// Arrange
+	let world = world();
+	let old = v4::World {
+		init: v8_init(&world),
+		state: v4::WorldState {
+			timestamp: Tick(1234),
+			player: v5_player(&world),
+			resources: world.state.resources.clone(),
+			harbors: v4_harbors(&world),
+			obstacles: world.state.obstacles.clone(),
+			wind: world.state.wind,
+			race: None,
+		},
+	};
+	let mut bytes = Vec::from(SAVE_MAGIC);
+	bytes.extend_from_slice(&4_u16.to_le_bytes());
+	bytes.extend(bincode::serialize(&old).unwrap());
+
+	// Act
+	let migrated = World::load_versioned(&bytes).unwrap();
+
+	// Assert
+	assert_eq!(migrated.state.timestamp, Tick(1234));
+	assert_eq!(migrated.state.harbors.len(), world.state.harbors.len());
+	for (harbor, old) in migrated.state.harbors.iter().zip(&old.state.harbors) {
+		assert_eq!(harbor.loc, old.loc);
+		assert_eq!(harbor.effect_size, HARBOR_EFFECT_SIZE);
+	}
+}
+
+#[test]
+fn version_5_save_is_migrated() {
+	// Arrange
+	let world = world();
+	let mut player = v5_player(&world);
+	player.vehicle.hull_integrity = 0.5;
+	let old = v5::World {
+		init: v8_init(&world),
+		state: v5::WorldState {
+			timestamp: Tick(1234),
+			player,
+			resources: world.state.resources.clone(),
+			harbors: world.state.harbors.clone(),
+			obstacles: world.state.obstacles.clone(),
+			wind: world.state.wind,
+			race: None,
+		},
+	};
+	let mut bytes = Vec::from(SAVE_MAGIC);
+	bytes.extend_from_slice(&5_u16.to_le_bytes());
+	bytes.extend(bincode::serialize(&old).unwrap());
+
+	// Act
+	let migrated = World::load_versioned(&bytes).unwrap();
+
+	// Assert
+	assert_eq!(migrated.state.timestamp, Tick(1234));
+	assert_eq!(migrated.state.player.vehicle.hull_integrity, 0.5);
+	assert!(migrated
+		.state
+		.player
+		.vehicle
+		.collision_cooldown
+		.values()
+		.all(|&cooldown| cooldown == 0));
+}
+
+#[test]
+fn version_6_save_is_migrated() {
+	// Arrange
+	let world = world();
+	let mut player = v6_player(&world);
+	player.vehicle.collision_cooldown[CollisionSurface::Land] = 3;
+	let old = v6::World {
+		init: v8_init(&world),
+		state: v6::WorldState {
+			timestamp: Tick(1234),
+			player,
+			resources: world.state.resources.clone(),
+			harbors: world.state.harbors.clone(),
+			obstacles: world.state.obstacles.clone(),
+			wind: world.state.wind,
+			race: None,
+		},
+	};
+	let mut bytes = Vec::from(SAVE_MAGIC);
+	bytes.extend_from_slice(&6_u16.to_le_bytes());
+	bytes.extend(bincode::serialize(&old).unwrap());
+
+	// Act
+	let migrated = World::load_versioned(&bytes).unwrap();
+
+	// Assert
+	assert_eq!(migrated.state.timestamp, Tick(1234));
+	assert_eq!(migrated.state.player.vehicle.magnet, Magnet::Absent);
+	assert_eq!(
+		migrated.state.player.vehicle.collision_cooldown[CollisionSurface::Land],
+		3
+	);
+}
+
+#[test]
+fn version_7_save_is_migrated() {
+	// Arrange
+	let world = world();
+	let mut player = v7_player(&world);
+	player.vehicle.magnet = Magnet::Lodestone;
+	let old = v7::World {
+		init: v8_init(&world),
+		state: v7::WorldState {
+			timestamp: Tick(1234),
+			player,
+			resources: world.state.resources.clone(),
+			harbors: world.state.harbors.clone(),
+			obstacles: world.state.obstacles.clone(),
+			wind: world.state.wind,
+			race: None,
+		},
+	};
+	let mut bytes = Vec::from(SAVE_MAGIC);
+	bytes.extend_from_slice(&7_u16.to_le_bytes());
+	bytes.extend(bincode::serialize(&old).unwrap());
+
+	// Act
+	let migrated = World::load_versioned(&bytes).unwrap();
+
+	// Assert
+	assert_eq!(migrated.state.timestamp, Tick(1234));
+	assert_eq!(migrated.state.player.vehicle.magnet, Magnet::Lodestone);
+	assert_eq!(migrated.state.player.vehicle.freshness, 1.0);
+}
+
+#[test]
+fn version_8_save_is_migrated() {
+	// Arrange
+	let world = world();
+	let old = v8::World {
+		init: v8_init(&world),
+		state: world.state.clone(),
+	};
+	let mut bytes = Vec::from(SAVE_MAGIC);
+	bytes.extend_from_slice(&8_u16.to_le_bytes());
+	bytes.extend(bincode::serialize(&old).unwrap());
+
+	// Act
+	let migrated = World::load_versioned(&bytes).unwrap();
+
+	// Assert
+	let terrain = &migrated.init.terrain;
+	assert_eq!(terrain.playground, world.init.terrain.playground);
+	assert!(terrain
+		.coords()
+		.all(|tc| terrain.surface(tc) == Surface::Open));
+}
+
+#[test]
+fn icy_terrain_round_trips() {
+	// Arrange
+	let mut world = world();
+	let tile = TileCoord::new(3, 4);
+	world.init.terrain.set_surface(tile, Surface::Ice);
+
+	// Act
+	let loaded = World::load_versioned(&world.save_versioned()).unwrap();
+
+	// Assert
+	assert_eq!(loaded.init.terrain.surface(tile), Surface::Ice);
+	assert_eq!(
+		loaded.init.terrain.surface(TileCoord::new(4, 3)),
+		Surface::Open
+	);
+}
+
+#[test]
+fn garbage_is_not_a_save() {
+	// Act & Assert
+	assert_eq!(World::load_versioned(b"").unwrap_err(), LoadError::NotASave);
+	assert_eq!(
+		World::load_versioned(b"{\"json\": true}").unwrap_err(),
+		LoadError::NotASave
+	);
+	assert_eq!(
+		World::load_versioned(&SAVE_MAGIC).unwrap_err(),
+		LoadError::NotASave
+	);
+}
+
+#[test]
+fn truncated_save_is_corrupted() {
+	// Arrange
+	let bytes = world().save_versioned();
+
+	// Act
+	let err = World::load_versioned(&bytes[..bytes.len() / 2]).unwrap_err();
+
+	// Assert
+	assert!(matches!(err, LoadError::Corrupted(_)), "{err:?}");
+}
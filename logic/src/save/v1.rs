@@ -0,0 +1,138 @@
+//! The layout of the save format version 1
+//!
+//! Only the types which changed since are replicated here, all others are
+//! shared with the current layout.
+//!
+
+use enum_map::EnumMap;
+use nalgebra_glm::Vec2;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::v3::Sail;
+use super::v4::Harbor;
+use super::v8::WorldInit;
+use crate::resource::ResourcePack;
+use crate::resource::ResourcePackContent;
+use crate::state::Obstacle;
+use crate::state::ShipHull;
+use crate::units::BiPolarFraction;
+use crate::units::Location;
+use crate::units::Tick;
+use crate::units::Wind;
+
+
+
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct World {
+	pub init: WorldInit,
+	pub state: WorldState,
+}
+
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct WorldState {
+	pub timestamp: Tick,
+	pub player: Player,
+	pub resources: Vec<ResourcePack>,
+	pub harbors: Vec<Harbor>,
+	pub obstacles: Vec<Obstacle>,
+	pub wind: Wind,
+}
+
+#[derive(Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct Player {
+	pub vehicle: Vehicle,
+	pub money: u64,
+}
+
+/// The vehicle, before ships could be anchored
+#[derive(Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct Vehicle {
+	pub hull: ShipHull,
+	pub pos: Location,
+	pub velocity: Vec2,
+	pub heading: f32,
+	pub prev_pos: Location,
+	pub prev_heading: f32,
+	pub angle_of_list: f32,
+	pub rudder: BiPolarFraction,
+	pub sail: Sail,
+	pub net_deployed: bool,
+	pub resource_weight: u32,
+	pub resource_value: u64,
+	pub cargo: EnumMap<ResourcePackContent, u32>,
+	pub hull_integrity: f32,
+	pub grounded: bool,
+}
+
+/// Upgrades to version 2, which added `Vehicle::anchored`
+impl From<World> for super::v2::World {
+	fn from(world: World) -> Self {
+		let World {
+			init,
+			state,
+		} = world;
+		let WorldState {
+			timestamp,
+			player,
+			resources,
+			harbors,
+			obstacles,
+			wind,
+		} = state;
+		let Vehicle {
+			hull,
+			pos,
+			velocity,
+			heading,
+			prev_pos,
+			prev_heading,
+			angle_of_list,
+			rudder,
+			sail,
+			net_deployed,
+			resource_weight,
+			resource_value,
+			cargo,
+			hull_integrity,
+			grounded,
+		} = player.vehicle;
+
+		Self {
+			init,
+			state: super::v2::WorldState {
+				timestamp,
+				player: super::v3::Player {
+					vehicle: super::v3::Vehicle {
+						hull,
+						pos,
+						velocity,
+						heading,
+						prev_pos,
+						prev_heading,
+						angle_of_list,
+						rudder,
+						sail,
+						net_deployed,
+						resource_weight,
+						resource_value,
+						cargo,
+						hull_integrity,
+						grounded,
+						// Old saves were never anchored
+						anchored: false,
+					},
+					money: player.money,
+				},
+				resources,
+				harbors,
+				obstacles,
+				wind,
+			},
+		}
+	}
+}
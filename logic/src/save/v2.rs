@@ -0,0 +1,69 @@
+//! The layout of the save format version 2
+//!
+//! Only the types which changed since are replicated here, all others are
+//! shared with the current layout.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::v3::Player;
+use super::v4::Harbor;
+use super::v8::WorldInit;
+use crate::resource::ResourcePack;
+use crate::state::Obstacle;
+use crate::units::Tick;
+use crate::units::Wind;
+
+
+
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct World {
+	pub init: WorldInit,
+	pub state: WorldState,
+}
+
+/// The world state, before races could be sailed
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct WorldState {
+	pub timestamp: Tick,
+	pub player: Player,
+	pub resources: Vec<ResourcePack>,
+	pub harbors: Vec<Harbor>,
+	pub obstacles: Vec<Obstacle>,
+	pub wind: Wind,
+}
+
+/// Upgrades to version 3, which added `WorldState::race`
+impl From<World> for super::v3::World {
+	fn from(world: World) -> Self {
+		let World {
+			init,
+			state,
+		} = world;
+		let WorldState {
+			timestamp,
+			player,
+			resources,
+			harbors,
+			obstacles,
+			wind,
+		} = state;
+
+		Self {
+			init,
+			state: super::v3::WorldState {
+				timestamp,
+				player,
+				resources,
+				harbors,
+				obstacles,
+				wind,
+				// Old saves were never racing
+				race: None,
+			},
+		}
+	}
+}
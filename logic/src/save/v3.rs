@@ -0,0 +1,164 @@
+//! The layout of the save format version 3
+//!
+//! Only the types which changed since are replicated here, all others are
+//! shared with the current layout.
+//!
+
+use enum_map::EnumMap;
+use nalgebra_glm::Vec2;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::v4::Harbor;
+use super::v8::WorldInit;
+use crate::race::Race;
+use crate::resource::ResourcePack;
+use crate::resource::ResourcePackContent;
+use crate::state::Obstacle;
+use crate::state::Reefing;
+use crate::state::SailKind;
+use crate::state::ShipHull;
+use crate::units::BiPolarFraction;
+use crate::units::Fraction;
+use crate::units::Location;
+use crate::units::Tick;
+use crate::units::Wind;
+
+
+
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct World {
+	pub init: WorldInit,
+	pub state: WorldState,
+}
+
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct WorldState {
+	pub timestamp: Tick,
+	pub player: Player,
+	pub resources: Vec<ResourcePack>,
+	pub harbors: Vec<Harbor>,
+	pub obstacles: Vec<Obstacle>,
+	pub wind: Wind,
+	pub race: Option<Race>,
+}
+
+#[derive(Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct Player {
+	pub vehicle: Vehicle,
+	pub money: u64,
+}
+
+#[derive(Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct Vehicle {
+	pub hull: ShipHull,
+	pub pos: Location,
+	pub velocity: Vec2,
+	pub heading: f32,
+	pub prev_pos: Location,
+	pub prev_heading: f32,
+	pub angle_of_list: f32,
+	pub rudder: BiPolarFraction,
+	pub sail: Sail,
+	pub net_deployed: bool,
+	pub resource_weight: u32,
+	pub resource_value: u64,
+	pub cargo: EnumMap<ResourcePackContent, u32>,
+	pub hull_integrity: f32,
+	pub grounded: bool,
+	pub anchored: bool,
+}
+
+/// The sail, before it could be torn
+///
+/// Its `condition` was an unused left-over of the car physics.
+#[derive(Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct Sail {
+	pub kind: SailKind,
+	pub condition: Fraction,
+	pub reefing: Reefing,
+	pub orientation_rectangle: f32,
+	pub orientation_triangle: f32,
+}
+
+/// Upgrades to version 4, which turned `Sail::condition` into the wear of the sail
+impl From<World> for super::v4::World {
+	fn from(world: World) -> Self {
+		let World {
+			init,
+			state,
+		} = world;
+		let WorldState {
+			timestamp,
+			player,
+			resources,
+			harbors,
+			obstacles,
+			wind,
+			race,
+		} = state;
+		let Vehicle {
+			hull,
+			pos,
+			velocity,
+			heading,
+			prev_pos,
+			prev_heading,
+			angle_of_list,
+			rudder,
+			sail,
+			net_deployed,
+			resource_weight,
+			resource_value,
+			cargo,
+			hull_integrity,
+			grounded,
+			anchored,
+		} = player.vehicle;
+
+		Self {
+			init,
+			state: super::v4::WorldState {
+				timestamp,
+				player: super::v5::Player {
+					vehicle: super::v5::Vehicle {
+						hull,
+						pos,
+						velocity,
+						heading,
+						prev_pos,
+						prev_heading,
+						angle_of_list,
+						rudder,
+						sail: crate::state::Sail {
+							kind: sail.kind,
+							// Old sails were never torn
+							condition: 1.0,
+							reefing: sail.reefing,
+							orientation_rectangle: sail.orientation_rectangle,
+							orientation_triangle: sail.orientation_triangle,
+						},
+						net_deployed,
+						resource_weight,
+						resource_value,
+						cargo,
+						hull_integrity,
+						grounded,
+						anchored,
+					},
+					money: player.money,
+				},
+				resources,
+				harbors,
+				obstacles,
+				wind,
+				race,
+			},
+		}
+	}
+}
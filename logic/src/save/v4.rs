@@ -0,0 +1,82 @@
+//! The layout of the save format version 4
+//!
+//! Only the types which changed since are replicated here, all others are
+//! shared with the current layout.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::v5::Player;
+use super::v8::WorldInit;
+use crate::race::Race;
+use crate::resource::ResourcePack;
+use crate::state::Obstacle;
+use crate::units::Location;
+use crate::units::Tick;
+use crate::units::Wind;
+
+
+
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct World {
+	pub init: WorldInit,
+	pub state: WorldState,
+}
+
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct WorldState {
+	pub timestamp: Tick,
+	pub player: Player,
+	pub resources: Vec<ResourcePack>,
+	pub harbors: Vec<Harbor>,
+	pub obstacles: Vec<Obstacle>,
+	pub wind: Wind,
+	pub race: Option<Race>,
+}
+
+/// The harbor, before harbors came in different sizes
+#[derive(Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct Harbor {
+	pub loc: Location,
+	pub orientation: f32,
+}
+
+/// Upgrades to version 5, which added the size and speeds to `Harbor`
+impl From<World> for super::v5::World {
+	fn from(world: World) -> Self {
+		let World {
+			init,
+			state,
+		} = world;
+		let WorldState {
+			timestamp,
+			player,
+			resources,
+			harbors,
+			obstacles,
+			wind,
+			race,
+		} = state;
+
+		Self {
+			init,
+			state: super::v5::WorldState {
+				timestamp,
+				player,
+				resources,
+				// Old harbors were all of the common size
+				harbors: harbors
+					.into_iter()
+					.map(|h| crate::state::Harbor::new(h.loc, h.orientation))
+					.collect(),
+				obstacles,
+				wind,
+				race,
+			},
+		}
+	}
+}
@@ -0,0 +1,150 @@
+//! The layout of the save format version 6
+//!
+//! Only the types which changed since are replicated here, all others are
+//! shared with the current layout.
+//!
+
+use enum_map::EnumMap;
+use nalgebra_glm::Vec2;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::v8::WorldInit;
+use crate::race::Race;
+use crate::resource::ResourcePack;
+use crate::resource::ResourcePackContent;
+use crate::state::CollisionSurface;
+use crate::state::Harbor;
+use crate::state::Magnet;
+use crate::state::Obstacle;
+use crate::state::Sail;
+use crate::state::ShipHull;
+use crate::units::BiPolarFraction;
+use crate::units::Location;
+use crate::units::Tick;
+use crate::units::Wind;
+
+
+
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct World {
+	pub init: WorldInit,
+	pub state: WorldState,
+}
+
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct WorldState {
+	pub timestamp: Tick,
+	pub player: Player,
+	pub resources: Vec<ResourcePack>,
+	pub harbors: Vec<Harbor>,
+	pub obstacles: Vec<Obstacle>,
+	pub wind: Wind,
+	pub race: Option<Race>,
+}
+
+#[derive(Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct Player {
+	pub vehicle: Vehicle,
+	pub money: u64,
+}
+
+/// The vehicle, before it could carry a magnet
+#[derive(Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct Vehicle {
+	pub hull: ShipHull,
+	pub pos: Location,
+	pub velocity: Vec2,
+	pub heading: f32,
+	pub prev_pos: Location,
+	pub prev_heading: f32,
+	pub angle_of_list: f32,
+	pub rudder: BiPolarFraction,
+	pub sail: Sail,
+	pub net_deployed: bool,
+	pub resource_weight: u32,
+	pub resource_value: u64,
+	pub cargo: EnumMap<ResourcePackContent, u32>,
+	pub hull_integrity: f32,
+	pub grounded: bool,
+	pub anchored: bool,
+	pub collision_cooldown: EnumMap<CollisionSurface, u16>,
+}
+
+/// Upgrades to version 7, which added `Vehicle::magnet`
+impl From<World> for super::v7::World {
+	fn from(world: World) -> Self {
+		let World {
+			init,
+			state,
+		} = world;
+		let WorldState {
+			timestamp,
+			player,
+			resources,
+			harbors,
+			obstacles,
+			wind,
+			race,
+		} = state;
+		let Vehicle {
+			hull,
+			pos,
+			velocity,
+			heading,
+			prev_pos,
+			prev_heading,
+			angle_of_list,
+			rudder,
+			sail,
+			net_deployed,
+			resource_weight,
+			resource_value,
+			cargo,
+			hull_integrity,
+			grounded,
+			anchored,
+			collision_cooldown,
+		} = player.vehicle;
+
+		Self {
+			init,
+			state: super::v7::WorldState {
+				timestamp,
+				player: super::v7::Player {
+					vehicle: super::v7::Vehicle {
+						hull,
+						// Old saves had no magnet yet
+						magnet: Magnet::Absent,
+						pos,
+						velocity,
+						heading,
+						prev_pos,
+						prev_heading,
+						angle_of_list,
+						rudder,
+						sail,
+						net_deployed,
+						resource_weight,
+						resource_value,
+						cargo,
+						hull_integrity,
+						grounded,
+						anchored,
+						collision_cooldown,
+					},
+					money: player.money,
+				},
+				resources,
+				harbors,
+				obstacles,
+				wind,
+				race,
+			},
+		}
+	}
+}
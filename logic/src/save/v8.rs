@@ -0,0 +1,77 @@
+//! The layout of the save format version 8
+//!
+//! Only the types which changed since are replicated here, all others are
+//! shared with the current layout.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::generator::Setting;
+use crate::state::WorldState;
+use crate::units::Elevation;
+use crate::DebuggingConf;
+use crate::PhysicsConf;
+
+
+
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct World {
+	pub init: WorldInit,
+	pub state: WorldState,
+}
+
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct WorldInit {
+	pub terrain_setting: Setting,
+	pub terrain: Terrain,
+	pub seed: u64,
+	pub dbg: DebuggingConf,
+	pub physics: PhysicsConf,
+	pub animation_lod: Option<f32>,
+}
+
+/// The terrain, before it had any surfaces such as ice
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct Terrain {
+	pub edge_length: u16,
+	pub playground: Vec<Elevation>,
+}
+
+/// Upgrades to version 9, which added `Terrain::surface`
+impl From<World> for crate::World {
+	fn from(world: World) -> Self {
+		let World {
+			init,
+			state,
+		} = world;
+		let WorldInit {
+			terrain_setting,
+			terrain,
+			seed,
+			dbg,
+			physics,
+			animation_lod,
+		} = init;
+
+		Self {
+			init: crate::WorldInit {
+				terrain_setting,
+				terrain: crate::terrain::Terrain {
+					edge_length: terrain.edge_length,
+					playground: terrain.playground,
+					// Old saves were never icy
+					surface: Vec::new(),
+				},
+				seed,
+				dbg,
+				physics,
+				animation_lod,
+			},
+			state,
+		}
+	}
+}
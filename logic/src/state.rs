@@ -12,36 +12,76 @@ use rand::SeedableRng;
 use rand_distr::Beta;
 use serde::Deserialize;
 use serde::Serialize;
+use strum::IntoEnumIterator;
 
+use crate::race::Race;
+use crate::resource::spawn_resources;
 use crate::resource::ResourcePack;
 use crate::resource::ResourcePackContent;
+use crate::resource::ResourceTable;
+use crate::seeded_rng;
+use crate::terrain::Terrain;
 use crate::terrain::TileCoord;
+use crate::terrain::TileDirection;
 use crate::units::BiPolarFraction;
-use crate::units::Fraction;
+use crate::units::Distance;
 use crate::units::Location;
 use crate::units::Tick;
 use crate::units::Wind;
 use crate::Input;
+use crate::PhysicsConf;
 use crate::StdRng;
 use crate::WorldInit;
-use crate::FRICTION_CROSS_SPEED_FACTOR;
-use crate::FRICTION_GROUND_SPEED_FACTOR;
+use crate::BEACHCOMBING_DISTANCE;
+use crate::COLLISION_COOLDOWN_TICKS;
+use crate::FISH_MIN_FRESHNESS;
+use crate::FISH_SPOIL_RATE;
+use crate::GROUNDING_DAMAGE_RATE;
+use crate::GROUNDING_DRAG_FACTOR;
+use crate::GROUNDING_ELEVATION;
 use crate::HARBOR_DOCKING_SPEED;
 use crate::HARBOR_EFFECT_SIZE;
 use crate::HARBOR_MAX_SPEED;
 use crate::HARBOR_SIZE;
+use crate::HULL_LEAK_DRAG_FACTOR;
+use crate::HULL_REPAIR_PRICE_FACTOR;
+use crate::KEDGE_SPEED;
+use crate::MAGNET_PULL_SPEED;
 use crate::MAX_TRACTION;
 use crate::MAX_WIND_SPEED;
-use crate::RESOURCE_PACK_FISH_SIZE;
+use crate::NET_COLLECTION_RADIUS_FACTOR;
+use crate::RESOURCE_LOD_INTERVAL;
+use crate::RESOURCE_PICKUP_DISTANCE;
+use crate::RESOURCE_RESPAWN_INTERVAL;
+use crate::RESOURCE_RESPAWN_RATE;
+use crate::SAIL_MIN_CONDITION;
+use crate::SAIL_REPAIR_PRICE_FACTOR;
+use crate::SAIL_STRAIN_LIMIT;
+use crate::SAIL_WEAR_RATE;
+use crate::STUCK_SPEED;
+use crate::THROW_BACK_DISTANCE;
 use crate::TICKS_PER_SECOND;
 use crate::VEHICLE_DEADWEIGHT;
+use crate::VEHICLE_MAX_SPEED;
 use crate::VEHICLE_SIZE;
+use crate::WIND_CALM_DURATION;
+use crate::WIND_CALM_FACTOR;
+use crate::WIND_CALM_PROBABILITY;
 use crate::WIND_CHANGE_INTERVAL;
 
+#[cfg(test)]
+mod test;
+
 
 
 const DELTA: f32 = 1_f32 / TICKS_PER_SECOND as f32;
 
+/// The random stream of the wind, to be combined with the wind interval index
+const WIND_RNG_STREAM: u128 = 0xa02bdbf7bb3c0a7ac28fa16a64abf96;
+
+/// The random stream of the calm periods, to be combined with the calm period index
+const WIND_CALM_RNG_STREAM: u128 = 0x5d1c4b2f8e3a6f0917c0ffee4d0d1e5;
+
 
 /// Normalize an angle in positive range [0,2π)
 fn normalize_angle_pos(angle: f32) -> f32 {
@@ -49,7 +89,7 @@ fn normalize_angle_pos(angle: f32) -> f32 {
 }
 
 /// Normalize an angle in range [-π,π)
-fn normalize_angle_rel(angle: f32) -> f32 {
+pub(crate) fn normalize_angle_rel(angle: f32) -> f32 {
 	let pos = normalize_angle_pos(angle);
 	if pos > PI {
 		pos - TAU
@@ -60,14 +100,54 @@ fn normalize_angle_rel(angle: f32) -> f32 {
 
 
 /// Events that can happen between ticks
-#[derive(Debug, Clone)]
+///
+/// Each event carries the location where it happened.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Event {
-	Fishy,
-	Starfish,
-	Shoe,
-	Grass,
-	TileCollision(f32),
-	HarborCollision(f32),
+	Fishy(Location),
+	Starfish(Location),
+	Shoe(Location),
+	Grass(Location),
+	/// Cargo has been thrown back into the sea, with the weight in kg
+	Splash(u32, Location),
+	/// The ship hit the land, with the speed in m/s
+	TileCollision(f32, Location),
+	/// The ship hit a harbor, with the speed in m/s
+	HarborCollision(f32, Location),
+	/// The ship hit an obstacle, with the speed in m/s
+	ObstacleCollision(f32, Location),
+	/// The ship ran aground in very shallow water
+	Grounding(Location),
+	/// The ship passed the race checkpoint with the given index
+	Checkpoint(usize, Location),
+}
+
+/// The kinds of surfaces a ship can collide with
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Enum)]
+#[derive(Serialize, Deserialize)]
+pub enum CollisionSurface {
+	Land,
+	Harbor,
+	Obstacle,
+}
+
+impl Event {
+	/// The location where this event happened
+	pub fn loc(&self) -> Location {
+		match *self {
+			Self::Fishy(loc)
+			| Self::Starfish(loc)
+			| Self::Shoe(loc)
+			| Self::Grass(loc)
+			| Self::Splash(_, loc)
+			| Self::TileCollision(_, loc)
+			| Self::HarborCollision(_, loc)
+			| Self::ObstacleCollision(_, loc)
+			| Self::Grounding(loc)
+			| Self::Checkpoint(_, loc) => loc,
+		}
+	}
 }
 
 
@@ -84,11 +164,204 @@ pub struct WorldState {
 	pub resources: Vec<ResourcePack>,
 	/// The full list of harbors
 	pub harbors: Vec<Harbor>,
+	/// The rocks and wrecks in the water
+	#[serde(default)]
+	pub obstacles: Vec<Obstacle>,
 	/// The currently prevailing wind condition
 	pub wind: Wind,
+	/// The race against the clock, if one is sailed
+	#[serde(default)]
+	pub race: Option<Race>,
+}
+
+/// Gives the animation of a single resource at `tick`
+///
+/// See [WorldState::animate_resources]
+fn resource_animation(
+	init: &WorldInit,
+	player_pos: Location,
+	tick: Tick,
+	lod: bool,
+) -> impl Fn(&mut ResourcePack) + Send + Sync + '_ {
+	// Every few ticks, even the distant resources catch up
+	let lod_distance = init
+		.animation_lod
+		.filter(|_| lod && tick.0 % RESOURCE_LOD_INTERVAL != 0);
+
+	move |r| {
+		let distant = lod_distance.map_or(false, |lod| {
+			init.terrain.torus_distance(player_pos, r.loc).magnitude() > lod
+		});
+		if !distant {
+			r.update(tick);
+		}
+	}
 }
 
 impl WorldState {
+	/// Advances the deterministic parts of the state to the given `tick`.
+	///
+	/// This sets the timestamp and reconstructs the resource animations and the
+	/// wind as they are at `tick`, without simulating any physics. Thus, the
+	/// player is left untouched.
+	pub fn seek_to(&mut self, init: &WorldInit, tick: Tick) {
+		self.timestamp = tick;
+
+		self.animate_resources(init, false);
+
+		self.wind = Self::wind_at(init, tick);
+	}
+
+	/// Pulls the resource packs near the ship a bit towards it, see [Magnet]
+	///
+	/// The pull moves the `origin` of the packs, around which they keep
+	/// swimming. Packs lying ashore stay where they are, and fish are never
+	/// dragged into waters shallower than their depth.
+	fn apply_magnet(&mut self, init: &WorldInit) {
+		let radius = self.player.vehicle.magnet.radius();
+		if radius <= 0.0 {
+			return;
+		}
+
+		let terrain = &init.terrain;
+		let ship = self.player.vehicle.pos;
+		for r in &mut self.resources {
+			if r.is_ashore() {
+				continue;
+			}
+
+			let offset = terrain.torus_distance(r.origin, ship);
+			let dist = offset.magnitude();
+			if dist >= radius || dist <= 0.0 {
+				continue;
+			}
+
+			let step = (MAGNET_PULL_SPEED * DELTA).min(dist);
+			let origin = terrain.map_loc_on_torus(Location(r.origin.0 + offset.0 * (step / dist)));
+			let deep_enough = TileCoord::try_from(origin)
+				.map(|tile| *terrain.get(tile) <= r.elevation)
+				.unwrap_or(false);
+			if deep_enough {
+				r.origin = origin;
+			}
+		}
+	}
+
+	/// Animates the resources for the current timestamp
+	///
+	/// With `lod`, distant resources are skipped as configured by
+	/// [WorldInit::animation_lod]. With the `parallel` feature, the resources
+	/// are animated on all cores.
+	fn animate_resources(&mut self, init: &WorldInit, lod: bool) {
+		#[cfg(feature = "parallel")]
+		self.animate_resources_parallel(init, lod);
+		#[cfg(not(feature = "parallel"))]
+		self.animate_resources_serial(init, lod);
+	}
+
+	#[cfg(any(test, not(feature = "parallel")))]
+	fn animate_resources_serial(&mut self, init: &WorldInit, lod: bool) {
+		let animate = resource_animation(init, self.player.vehicle.pos, self.timestamp, lod);
+		self.resources.iter_mut().for_each(animate);
+	}
+
+	#[cfg(feature = "parallel")]
+	fn animate_resources_parallel(&mut self, init: &WorldInit, lod: bool) {
+		use rayon::prelude::*;
+
+		let animate = resource_animation(init, self.player.vehicle.pos, self.timestamp, lod);
+		self.resources.par_iter_mut().for_each(animate);
+	}
+
+	/// Returns whether the wind sample at the beginning of the wind interval
+	/// `idx` is calm
+	///
+	/// Calms come in periods of `WIND_CALM_DURATION` wind intervals, and never
+	/// happen with the debugging wind modes.
+	fn is_calm_sample(init: &WorldInit, idx: u64) -> bool {
+		if init.dbg.wind_turning || init.dbg.fixed_wind_direction.is_some() {
+			return false;
+		}
+
+		let period = idx / u64::from(WIND_CALM_DURATION);
+		let mut rng = seeded_rng(init.seed, WIND_CALM_RNG_STREAM ^ u128::from(period));
+		rng.gen_bool(WIND_CALM_PROBABILITY)
+	}
+
+	/// Returns when the wind calms down the next time, looking at most
+	/// `horizon` ticks ahead of `tick`
+	///
+	/// Returns `tick` itself, if the wind is already calm at `tick`.
+	pub fn next_calm(init: &WorldInit, tick: Tick, horizon: u64) -> Option<Tick> {
+		let interval = u64::from(TICKS_PER_SECOND) * u64::from(WIND_CHANGE_INTERVAL);
+		let first = tick.0 / interval;
+		let last = (tick.0 + horizon) / interval;
+
+		(first..=last)
+			.find(|&idx| Self::is_calm_sample(init, idx))
+			.map(|idx| Tick((idx * interval).max(tick.0)))
+	}
+
+	/// Returns the wind that prevails at the given `tick`
+	pub fn wind_at(init: &WorldInit, tick: Tick) -> Wind {
+		if init.dbg.wind_turning {
+			// Turning wind
+			Wind::from_polar(
+				(tick.0 % (u64::from(TICKS_PER_SECOND) * u64::from(WIND_CHANGE_INTERVAL))) as f32
+					/ (u64::from(TICKS_PER_SECOND) * u64::from(WIND_CHANGE_INTERVAL)) as f32
+					* std::f32::consts::TAU,
+				MAX_WIND_SPEED,
+			)
+		} else if let Some(dir) = init.dbg.fixed_wind_direction {
+			// Fixed wind
+			Wind::from_polar(dir, MAX_WIND_SPEED)
+		} else {
+			// Normal randomized wind
+
+			// Using a beta distribution with α=5, β=2 for the Magnitude
+			let beta = Beta::new(5.0, 2.0).unwrap();
+
+			let interval = u64::from(TICKS_PER_SECOND) * u64::from(WIND_CHANGE_INTERVAL);
+			let earlier = tick.0 / interval;
+			let later = earlier + 1;
+			let offset = tick.0 - earlier * interval;
+
+			// The wind sample at the beginning of the given wind interval
+			let sample = |idx: u64| {
+				let mut rng = seeded_rng(init.seed, WIND_RNG_STREAM ^ u128::from(idx));
+
+				let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+				let mut magnitude = beta.sample(&mut rng) * MAX_WIND_SPEED;
+				if Self::is_calm_sample(init, idx) {
+					magnitude *= WIND_CALM_FACTOR;
+				}
+				Wind::from_polar(angle, magnitude)
+			};
+			let early = sample(earlier);
+			let late = sample(later);
+
+			Wind::lerp_polar(early, late, offset as f32 / interval as f32)
+		}
+	}
+
+	/// Forecasts the wind at each of the next `intervals` wind interval boundaries
+	///
+	/// The wind only changes its course at these boundaries, in between it
+	/// is interpolated, see [Self::wind_at]. Thus, the forecast is fully
+	/// determined by the seed, for e.g. planning a route ahead.
+	pub fn forecast(&self, init: &WorldInit, intervals: usize) -> Vec<(Tick, Wind)> {
+		let interval = u64::from(TICKS_PER_SECOND) * u64::from(WIND_CHANGE_INTERVAL);
+		let next = self.timestamp.0 / interval + 1;
+
+		(next..)
+			.take(intervals)
+			.map(|idx| {
+				let tick = Tick(idx * interval);
+				(tick, Self::wind_at(init, tick))
+			})
+			.collect()
+	}
+
 	pub fn update(&mut self, init: &WorldInit, inputs: &Input) -> Vec<Event> {
 		let mut events = Vec::new();
 
@@ -98,66 +371,27 @@ impl WorldState {
 		// Apply user inputs
 		self.player.vehicle.apply_input(*inputs);
 
-		// Update fishies
-		for r in &mut self.resources {
-			r.update(self.timestamp);
-		}
+		// Let the catch of the previous ticks spoil a bit
+		self.player.vehicle.spoil();
 
-		// Update wind
-		self.wind = {
-			if init.dbg.wind_turning {
-				// Turning wind
-				Wind::from_polar(
-					(self.timestamp.0
-						% (u64::from(TICKS_PER_SECOND) * u64::from(WIND_CHANGE_INTERVAL))) as f32
-						/ (u64::from(TICKS_PER_SECOND) * u64::from(WIND_CHANGE_INTERVAL)) as f32
-						* std::f32::consts::TAU,
-					MAX_WIND_SPEED,
-				)
-			} else if let Some(dir) = init.dbg.fixed_wind_direction {
-				// Fixed wind
-				Wind::from_polar(dir, MAX_WIND_SPEED)
-			} else {
-				// Normal randomized wind
-
-				// Using a beta distribution with α=5, β=2 for the Magnitude
-				let beta = Beta::new(5.0, 2.0).unwrap();
-
-				let interval = u64::from(TICKS_PER_SECOND) * u64::from(WIND_CHANGE_INTERVAL);
-				let earlier = self.timestamp.0 / interval;
-				let later = earlier + 1;
-				let offset = self.timestamp.0 - earlier * interval;
-
-				let early = {
-					let mut rng = StdRng::new(
-						0xcafef00dd15ea5e5,
-						0xa02bdbf7bb3c0a7ac28fa16a64abf96
-							^ u128::from(init.seed) ^ u128::from(earlier),
-					);
-
-					let angle = rng.gen::<f32>() * std::f32::consts::TAU;
-					let magnitude = beta.sample(&mut rng) * MAX_WIND_SPEED;
-					Wind::from_polar(angle, magnitude)
-				};
-				let late = {
-					let mut rng = StdRng::new(
-						0xcafef00dd15ea5e5,
-						0xa02bdbf7bb3c0a7ac28fa16a64abf96
-							^ u128::from(init.seed) ^ u128::from(later),
-					);
-
-					let angle = rng.gen::<f32>() * std::f32::consts::TAU;
-					let magnitude = beta.sample(&mut rng) * MAX_WIND_SPEED;
-					Wind::from_polar(angle, magnitude)
-				};
+		// Let the magnet pull the nearby fishies, before they move on
+		self.apply_magnet(init);
 
-				let lerpy = nalgebra_glm::lerp(&early.0, &late.0, offset as f32 / interval as f32);
-				Wind(lerpy)
-			}
-		};
+		// Update fishies, the distant ones only every few ticks if so configured
+		self.animate_resources(init, true);
+
+		// Update wind
+		self.wind = Self::wind_at(init, self.timestamp);
 
 		//let water_consumption = crate::WATER_CONSUMPTION * DELTA;
 
+		// The position of the player before this tick
+		let player_start_pos = self.player.vehicle.pos;
+
+		// Remember the previous pose, for interpolated rendering
+		self.player.vehicle.prev_pos = self.player.vehicle.pos;
+		self.player.vehicle.prev_heading = self.player.vehicle.heading;
+
 		{
 			let p = &mut self.player;
 
@@ -183,8 +417,7 @@ impl WorldState {
 
 			// in m/s²
 			let acceleration = {
-				let true_wind = self.wind.0;
-				let apparent_wind = true_wind - p.vehicle.velocity;
+				let apparent_wind = p.vehicle.apparent_wind(self.wind);
 				let ship_angle = p.vehicle.heading;
 
 				let local_wind_angle = {
@@ -202,20 +435,7 @@ impl WorldState {
 				p.vehicle.sail.orientation_rectangle = local_square_sail_angle + ship_angle;
 
 
-				let sail_drag_ness = 1.
-					- p.vehicle
-						.sail
-						.orientation_triangle_vec()
-						.dot(&apparent_wind.normalize())
-						.abs();
-
-				let sail_drag = apparent_wind * sail_drag_ness;
-
-
-				let static_ship_area = 1.;
-				let sail_area = p.vehicle.sail.sail_area();
-
-				let prop = sail_drag * sail_area + apparent_wind * static_ship_area;
+				let prop = p.vehicle.propulsion(self.wind, p.vehicle.sail.reefing);
 
 				let direction = apparent_wind.normalize();
 
@@ -247,12 +467,50 @@ impl WorldState {
 			);
 			*/
 
-			let friction = p.vehicle.friction_deacceleration();
+			// Shallow water drags more than deep water, and the shallowest
+			// water even grounds the ship, slowing it down hard. Ice drags
+			// even more on top of that.
+			let tile = p.vehicle.pos.try_into().expect("Player is out of bounds");
+			let elevation = *init.terrain.get(tile);
+			let grounded = elevation >= GROUNDING_ELEVATION;
+			let water_drag = if grounded {
+				elevation.classify().drag_factor() * GROUNDING_DRAG_FACTOR
+			} else {
+				elevation.classify().drag_factor()
+			};
+			let drag = water_drag * init.terrain.surface(tile).drag_factor();
+			let friction = p.vehicle.friction_deacceleration(&init.physics) * drag;
+
+			// Grounding damages the hull, as long as the ship stays aground
+			if grounded {
+				p.vehicle.hull_integrity =
+					(p.vehicle.hull_integrity - GROUNDING_DAMAGE_RATE * duration).max(0.0);
+				if !p.vehicle.grounded {
+					events.push(Event::Grounding(p.vehicle.pos));
+				}
+			}
+			p.vehicle.grounded = grounded;
+
+			// Carrying too much sail in a strong wind tears the sail
+			let strain = p.vehicle.sail.strain(p.vehicle.apparent_wind(self.wind));
+			if strain > SAIL_STRAIN_LIMIT {
+				let wear = SAIL_WEAR_RATE * (strain / SAIL_STRAIN_LIMIT - 1.) * duration;
+				p.vehicle.sail.condition =
+					(p.vehicle.sail.condition - wear).max(SAIL_MIN_CONDITION);
+			}
 
+			// An anchored ship holds its position, regardless of the wind
+			if p.vehicle.anchored {
+				p.vehicle.velocity = Vec2::zeros();
+			}
 
 			let vel_0 = p.vehicle.velocity;
 
-			let acc = acceleration + friction;
+			let acc = if p.vehicle.anchored {
+				Vec2::zeros()
+			} else {
+				acceleration + friction
+			};
 
 			// Save the old tile and position
 			let old_tile: TileCoord = p.vehicle.pos.try_into().expect("Player is out of bounds");
@@ -261,38 +519,62 @@ impl WorldState {
 
 			// Move according to acceleration & velocity
 			p.vehicle.velocity += acc * duration;
-			let distance = duration * (vel_0 + duration * acc);
+			let mut distance = duration * (vel_0 + duration * acc);
+
+			// Recover from numerical runaways, instead of losing the ship off the torus
+			let is_finite = |v: &Vec2| v.iter().all(|c| c.is_finite());
+			if !is_finite(&p.vehicle.velocity) || !is_finite(&distance) {
+				eprintln!(
+					"Non-finite ship motion, velocity: {:?}, distance: {:?}",
+					p.vehicle.velocity, distance
+				);
+				p.vehicle.velocity = Vec2::zeros();
+				distance = Vec2::zeros();
+			}
+			p.vehicle.velocity = p.vehicle.velocity.cap_magnitude(VEHICLE_MAX_SPEED);
+			let distance = distance.cap_magnitude(VEHICLE_MAX_SPEED * duration);
 			p.vehicle.pos.0 += distance;
 
 			// Keep the player on the Torus-world
 			p.vehicle.pos = init.terrain.map_loc_on_torus(p.vehicle.pos);
 
+			// Let the collisions of the previous ticks fade
+			for cooldown in p.vehicle.collision_cooldown.values_mut() {
+				*cooldown = cooldown.saturating_sub(1);
+			}
+
 			// Terrain interaction
-			// First check whether the player is still on the map, and if so
-			// retrieve its new tile.
-			if let Ok(new_tile) = TileCoord::try_from(p.vehicle.pos) {
+			// First check whether the player is still on the map.
+			if TileCoord::try_from(p.vehicle.pos).is_ok() {
 				// Only check collisions if the player is in passable water.
 				// So the player is free to move around if he glitched into terrain, to get out
 				if Some(true) == init.terrain.try_get(old_tile).map(|t| t.is_passable()) {
-					// Check if the player tries to go into impassable terrain
-					if Some(true) != init.terrain.try_get(new_tile).map(|t| t.is_passable()) {
+					// Check if the player tries to go into impassable terrain,
+					// anywhere along the path of this tick, so that a fast
+					// ship can not tunnel through thin land.
+					if let Some((last_tile, blocked_tile)) = init
+						.terrain
+						.first_impassable_on_path(Location(old_pos), Distance(distance))
+					{
 						// TODO: maybe we want to handle this differently
 						// Ship bounce off land
 						p.vehicle.pos.0 = old_pos;
 
 						p.vehicle.velocity *= -0.5;
 
-						if old_tile.x == new_tile.x {
+						if last_tile.x == blocked_tile.x {
 							// restore x component sign
 							p.vehicle.velocity.x *= -1.;
 						}
-						if old_tile.y == new_tile.y {
+						if last_tile.y == blocked_tile.y {
 							// restore y component sign
 							p.vehicle.velocity.y *= -1.;
 						}
 
-						// Add event about collision
-						events.push(Event::TileCollision(old_velo.norm()));
+						// Add event about collision, unless still scraping along the land
+						if p.vehicle.note_collision(CollisionSurface::Land) {
+							events.push(Event::TileCollision(old_velo.norm(), p.vehicle.pos));
+						}
 					}
 				}
 			} else {
@@ -319,35 +601,81 @@ impl WorldState {
 					.torus_distance(Location(old_pos), harbor.loc)
 					.0
 					.norm();
+				// The closest the player came to the harbor within this tick
+				let path_distance = init.terrain.torus_distance_to_segment(
+					Location(old_pos),
+					p.vehicle.pos,
+					harbor.loc,
+				);
 				// Only check if the player isn't inside yet
 				if old_distance >= coll_dist {
-					// Check if the player went inside
-					if distance < coll_dist {
+					// Check if the player went inside, anywhere along its path,
+					// so a fast ship can not pass through the harbor.
+					if path_distance < coll_dist {
 						// Reset player pos
 						p.vehicle.pos.0 = old_pos;
 
 						// Bounce off away from the harbor
-						let head = (old_pos - harbor.loc.0).normalize();
+						let head = init
+							.terrain
+							.torus_distance(harbor.loc, Location(old_pos))
+							.0
+							.normalize();
 						//let turn = Rotation2::new(PI / 2.);
 						//let tang = turn * head;
 
 						let head_speed = p.vehicle.velocity.dot(&head);
 						p.vehicle.velocity -= head * head_speed * 1.5;
 
-						// Add event about collision
-						events.push(Event::HarborCollision(old_velo.norm()));
+						// Add event about collision, unless still scraping along a harbor
+						if p.vehicle.note_collision(CollisionSurface::Harbor) {
+							events.push(Event::HarborCollision(old_velo.norm(), p.vehicle.pos));
+						}
 					}
 				}
 				// Make a ship docked, if within harbor range, without a sail, slow enough
-				if distance < HARBOR_EFFECT_SIZE
+				if distance < harbor.effect_size
 					&& p.vehicle.sail.reefing == Reefing(0)
-					&& p.vehicle.velocity.norm() <= HARBOR_DOCKING_SPEED
+					&& p.vehicle.velocity.norm() <= harbor.docking_speed
 				{
 					// Dock the ship
 					p.vehicle.velocity = vec2(0., 0.);
 				}
 			}
 
+			// Obstacle collision, just like with the harbors
+			for obstacle in &self.obstacles {
+				let coll_dist = obstacle.radius + VEHICLE_SIZE * 0.5;
+				let old_distance = init
+					.terrain
+					.torus_distance(Location(old_pos), obstacle.loc)
+					.0
+					.norm();
+				let path_distance = init.terrain.torus_distance_to_segment(
+					Location(old_pos),
+					p.vehicle.pos,
+					obstacle.loc,
+				);
+				if old_distance >= coll_dist && path_distance < coll_dist {
+					// Reset player pos
+					p.vehicle.pos.0 = old_pos;
+
+					// Bounce off away from the obstacle
+					let head = init
+						.terrain
+						.torus_distance(obstacle.loc, Location(old_pos))
+						.0
+						.normalize();
+					let head_speed = p.vehicle.velocity.dot(&head);
+					p.vehicle.velocity -= head * head_speed * 1.5;
+
+					// Add event about collision, unless still scraping along an obstacle
+					if p.vehicle.note_collision(CollisionSurface::Obstacle) {
+						events.push(Event::ObstacleCollision(old_velo.norm(), p.vehicle.pos));
+					}
+				}
+			}
+
 			/* TODO: how about a shore-based breaking
 			 * Tho we would need a (too) shallow water visualization
 			// Apply breaking
@@ -362,23 +690,28 @@ impl WorldState {
 
 			// distance traveled by rolling wheels
 			let distance_norm = distance.dot(&p.vehicle.heading_vec());
+			// The rudder deflection, zero means straight ahead
+			let rudder = p.vehicle.rudder.to_f32();
 			// steering angle relative to the current roll direction (i.e. relative to the heading)
-			let steering_angle = p.vehicle.ruder.to_f32().abs() * crate::VEHICLE_MAX_STEERING_ANGLE;
-			let turning_circle_radius = crate::VEHICLE_WHEEL_BASE / steering_angle.sin();
+			let steering_angle = rudder.abs() * crate::VEHICLE_MAX_STEERING_ANGLE;
 
 			// Turning angle
-			let angle = distance_norm / turning_circle_radius;
+			// This is the `distance_norm` over the turning circle radius, which
+			// is `VEHICLE_WHEEL_BASE / steering_angle.sin()`. However, we use the
+			// inverse radius here, which is well defined for a neutral rudder,
+			// too (a straight line is a circle of infinite radius).
+			let angle = distance_norm * steering_angle.sin() / crate::VEHICLE_WHEEL_BASE;
 
-			let angle = angle.max(0.02);
+			// Allow turning (e.g. while docked) according to the rudder deflection
+			let angle = angle.max(crate::VEHICLE_MIN_TURNING_ANGLE * rudder.abs());
 
-			if p.vehicle.ruder.to_f32().abs() > 0.01 {
-				p.vehicle.heading += angle * p.vehicle.ruder.to_f32().signum();
-			}
+			// Notice, a neutral rudder results in an `angle` of zero
+			p.vehicle.heading += angle * rudder.signum();
 
 			// Turning by traction
 
-			let head_speed = p.vehicle.wheel_speed();
-			let cross_speed = p.vehicle.cross_speed() * 0.5;
+			let head_speed = p.vehicle.forward_speed();
+			let cross_speed = p.vehicle.drift_speed() * 0.5;
 
 			p.vehicle.angle_of_list = (-(cross_speed / MAX_TRACTION / 2.) * PI).clamp(-PI, PI);
 
@@ -406,36 +739,43 @@ impl WorldState {
 		{
 			let p = player;
 
-			let mut taken_types: EnumMap<ResourcePackContent, bool> = EnumMap::default();
 			let mut remaining_fish: EnumMap<ResourcePackContent, usize> = EnumMap::default();
 
+			let collection_radius = p.vehicle.collection_radius();
+
 			resources.retain(|r| {
-				let dist = VEHICLE_SIZE / 2. + RESOURCE_PACK_FISH_SIZE / 2.;
-				let tor_dist = init.terrain.torus_distance(r.loc, p.vehicle.pos);
+				// Check the entire path traveled within this tick, so fast
+				// ships do not skip over resources.
+				let path_dist =
+					init.terrain
+						.torus_distance_to_segment(player_start_pos, p.vehicle.pos, r.loc);
+
+				// Resources ashore can be reached from the water
+				let reach = if r.is_ashore() {
+					collection_radius + BEACHCOMBING_DISTANCE
+				} else {
+					collection_radius
+				};
 
-				if tor_dist.0.norm() < dist {
+				if path_dist < reach {
 					// Store the fish in the ship
-					p.vehicle.resource_weight += r.content.weight;
-					p.vehicle.resource_value += r.content.value;
-
-					// Mark resource type as taken
-					taken_types[r.content] = true;
+					p.vehicle.load(&init.terrain_setting.resources, r.content);
 
 					// Emit event for sound effects
 					{
 						use ResourcePackContent::*;
 						match r.content {
 							Fish0 | Fish1 | Fish2 | Fish3 | Fish4 | Fish5 | Fish6 | Fish7 => {
-								events.push(Event::Fishy)
+								events.push(Event::Fishy(r.loc))
 							},
 							Starfish0 | Starfish1 | Starfish2 | Starfish3 | Starfish4 => {
-								events.push(Event::Starfish);
+								events.push(Event::Starfish(r.loc));
 							},
-							Shoe0 | Shoe1 => {
-								events.push(Event::Shoe);
+							Shoe0 | Shoe1 | Driftwood => {
+								events.push(Event::Shoe(r.loc));
 							},
 							Grass0 | Grass1 => {
-								events.push(Event::Grass);
+								events.push(Event::Grass(r.loc));
 							},
 						}
 					}
@@ -451,35 +791,155 @@ impl WorldState {
 			});
 
 			// Process resource respawning
+			// Once in a while, each resource type recovers a fraction of its
+			// deficit towards the amount that was initially generated.
+			let interval = u64::from(TICKS_PER_SECOND) * u64::from(RESOURCE_RESPAWN_INTERVAL);
+			if self.timestamp.0 % interval == 0 {
+				for ty in ResourcePackContent::iter() {
+					let expected_amount = init.terrain_setting.resource_amount(ty);
+
+					if remaining_fish[ty] < expected_amount {
+						let deficit = expected_amount - remaining_fish[ty];
+						let needed = (deficit as f32 * RESOURCE_RESPAWN_RATE).ceil() as usize;
+
+						let start = self.resources.len();
+						self.resources.extend(spawn_resources(
+							&init.terrain_setting.resources,
+							ty,
+							&init.terrain,
+							needed,
+							&mut rng,
+						));
+
+						// Place the new resources according to the current tick
+						for r in &mut self.resources[start..] {
+							r.update(self.timestamp);
+						}
+					}
+				}
+			}
+		}
 
-			let map_area = init.terrain_setting.edge_length as f32
-				* init.terrain_setting.edge_length as f32
-				* init.terrain_setting.resource_density;
+		// Check the passage of the next race checkpoint
+		if let Some(race) = &mut self.race {
+			events.extend(race.update(&init.terrain, self.player.vehicle.pos));
+		}
 
-			for ty in taken_types
-				.iter()
-				.filter_map(|(ty, take)| if *take { Some(ty) } else { None })
-			{
-				let expected_amount = (map_area * ty.spawn_density) as usize;
+		events
+	}
 
-				if remaining_fish[ty] < expected_amount {
-					let needed = expected_amount - remaining_fish[ty];
+	/// Whether the ship is on land, e.g. after glitching into it, or right next to it
+	fn is_at_land(&self, init: &WorldInit) -> bool {
+		let terrain = &init.terrain;
+		let tile: TileCoord = self
+			.player
+			.vehicle
+			.pos
+			.try_into()
+			.expect("Player is out of bounds");
+
+		!terrain.get(tile).is_passable()
+			|| TileDirection::iter().any(|dir| {
+				!terrain
+					.get(terrain.tile_in_direction(dir, tile))
+					.is_passable()
+			})
+	}
 
-					self.resources
-						.extend(ty.generate(&mut rng, &init.terrain, needed));
-				}
+	/// Whether the ship is stuck on or against impassable terrain
+	///
+	/// That is, it hardly moves while being on land or right next to it.
+	/// Lying at anchor or at a harbor, e.g. for trading, is no reason to worry.
+	pub fn is_stuck(&self, init: &WorldInit) -> bool {
+		let vehicle = &self.player.vehicle;
+
+		vehicle.ground_speed() < STUCK_SPEED
+			&& !vehicle.anchored
+			&& !self.is_at_harbor(init)
+			&& self.is_at_land(init)
+	}
+
+	/// Whether the ship is within the effect area of any harbor
+	fn is_at_harbor(&self, init: &WorldInit) -> bool {
+		self.harbors.iter().any(|harbor| {
+			init.terrain
+				.torus_distance(self.player.vehicle.pos, harbor.loc)
+				.magnitude() < harbor.effect_size
+		})
+	}
+
+	/// Nudges the ship slowly towards the open water, for this tick
+	///
+	/// On land, the ship heads for the nearest passable tile, otherwise away
+	/// from the adjacent land. Call it every tick until the ship is free,
+	/// which is when it returns `false`.
+	pub fn kedge_off(&mut self, init: &WorldInit) -> bool {
+		if !self.is_at_land(init) {
+			return false;
+		}
+
+		let terrain = &init.terrain;
+		let vehicle = &mut self.player.vehicle;
+		let tile: TileCoord = vehicle.pos.try_into().expect("Player is out of bounds");
+
+		let direction = if terrain.get(tile).is_passable() {
+			// Away from all the adjacent land, or towards the center of
+			// the tile, if the land is all around
+			let away: Vec2 = TileDirection::iter()
+				.filter(|&dir| {
+					!terrain
+						.get(terrain.tile_in_direction(dir, tile))
+						.is_passable()
+				})
+				.map(|dir| {
+					let (x, y) = dir.tile_offsets();
+					-vec2(f32::from(x), f32::from(y))
+				})
+				.sum();
+			if away.norm() > f32::EPSILON {
+				away
+			} else {
+				terrain.torus_distance(vehicle.pos, tile.into()).0
 			}
+		} else {
+			match terrain.nearest_passable(tile) {
+				Some(target) => terrain.torus_distance(vehicle.pos, target.into()).0,
+				// All land, no way out
+				None => return false,
+			}
+		};
+		if direction.norm() <= f32::EPSILON {
+			return false;
 		}
 
-		events
+		let direction = direction.normalize();
+		vehicle.heading = f32::atan2(direction.y, direction.x);
+		vehicle.velocity = direction * KEDGE_SPEED;
+
+		true
 	}
 
 	pub fn rng_for_tick(&self, init: &WorldInit) -> impl Rng {
 		StdRng::seed_from_u64(init.seed ^ self.timestamp.0)
 	}
 
+	/// Lists all harbors with their distance from the player and their fish price
+	///
+	/// Each entry consists of the harbor index, the torus distance from the
+	/// player to the harbor, and the harbor's current price for fish.
+	pub fn market_report(&self, init: &WorldInit) -> Vec<(usize, Distance, u64)> {
+		self.harbors
+			.iter()
+			.enumerate()
+			.map(|(idx, h)| {
+				let dist = init.terrain.torus_distance(self.player.vehicle.pos, h.loc);
+				(idx, dist, h.fish_price())
+			})
+			.collect()
+	}
+
 	/// Get options for trading
-	pub fn get_trading(&mut self, init: &WorldInit) -> Option<TradeOption> {
+	pub fn get_trading<'a>(&'a mut self, init: &'a WorldInit) -> Option<TradeOption<'a>> {
 		let mut min_dist_n_idx: Option<(f32, usize)> = None;
 		for (idx, h) in self.harbors.iter().enumerate() {
 			let dist = init
@@ -487,7 +947,7 @@ impl WorldState {
 				.torus_distance(self.player.vehicle.pos, h.loc)
 				.0
 				.norm();
-			if dist < HARBOR_EFFECT_SIZE {
+			if dist < h.effect_size {
 				match min_dist_n_idx {
 					None => {
 						min_dist_n_idx = Some((dist, idx));
@@ -502,7 +962,43 @@ impl WorldState {
 
 		min_dist_n_idx
 			.map(|(_d, idx)| idx)
-			.map(|idx| TradeOption::new(self, idx))
+			.map(|idx| TradeOption::new(self, &init.terrain_setting.resources, idx))
+	}
+
+	/// Throws half of the least valuable cargo back into the sea
+	///
+	/// The cargo with the lowest value per kg is released as resource packs
+	/// behind the player's ship.
+	///
+	/// Returns `None`, if there is no cargo on board.
+	pub fn throw_back_cargo(&mut self, init: &WorldInit) -> Option<Event> {
+		let mut rng = self.rng_for_tick(init);
+		let table = &init.terrain_setting.resources;
+		let vehicle = &mut self.player.vehicle;
+
+		// Compare the value per kg without rounding
+		let kind = ResourcePackContent::iter()
+			.filter(|&kind| vehicle.cargo[kind] > 0)
+			.min_by(|&a, &b| {
+				(table[a].value * u64::from(table[b].weight))
+					.cmp(&(table[b].value * u64::from(table[a].weight)))
+			})?;
+		let count = (vehicle.cargo[kind] + 1) / 2;
+		for _ in 0..count {
+			vehicle.unload(table, kind);
+		}
+
+		let origin = init
+			.terrain
+			.map_loc_on_torus(vehicle.pos - Distance(vehicle.heading_vec() * THROW_BACK_DISTANCE));
+		let start = self.resources.len();
+		self.resources
+			.extend((0..count).map(|_| ResourcePack::new(table, origin, kind, &mut rng)));
+		for r in &mut self.resources[start..] {
+			r.update(self.timestamp);
+		}
+
+		Some(Event::Splash(count * table[kind].weight, origin))
 	}
 }
 
@@ -512,6 +1008,8 @@ impl WorldState {
 pub struct TradeOption<'a> {
 	/// The world state
 	state: &'a mut WorldState,
+	/// The stats of the resource types
+	resources: &'a ResourceTable,
 	/// The harbor in question
 	///
 	/// This is an index into the `harbors` field on the above `state`.
@@ -522,11 +1020,13 @@ pub struct TradeOption<'a> {
 	traded_fish_amount: u32,
 }
 impl<'a> TradeOption<'a> {
-	fn new(state: &'a mut WorldState, harbor_idx: usize) -> Self {
+	fn new(state: &'a mut WorldState, resources: &'a ResourceTable, harbor_idx: usize) -> Self {
+		let base_price = state.harbors[harbor_idx].fish_price();
 		Self {
 			state,
+			resources,
 			harbor_idx,
-			base_price: 1,
+			base_price,
 			traded_fish_amount: 0,
 		}
 	}
@@ -563,6 +1063,108 @@ impl TradeOption<'_> {
 		self.state.player.vehicle.hull.upgrade().map(|s| s.value())
 	}
 
+	/// Returns the price for repairing the hull (if damaged)
+	///
+	/// Returns `None` if the hull is intact
+	pub fn get_price_of_hull_repair(&self) -> Option<u64> {
+		let vehicle = &self.state.player.vehicle;
+		let damage = 1.0 - vehicle.hull_integrity;
+
+		if damage > 0.0 {
+			let price = damage * HULL_REPAIR_PRICE_FACTOR * vehicle.hull.value() as f32;
+			Some((price.ceil() as u64).max(1))
+		} else {
+			None
+		}
+	}
+
+	/// Returns the price for upgrading the magnet to the next level (if any)
+	///
+	/// Returns `None` if already at max level
+	pub fn get_price_of_magnet_upgrade(&self) -> Option<u64> {
+		self.state
+			.player
+			.vehicle
+			.magnet
+			.upgrade()
+			.map(|m| m.value())
+	}
+
+	/// Returns the price for mending the sail (if torn)
+	///
+	/// Returns `None` if the sail is intact
+	pub fn get_price_of_sail_repair(&self) -> Option<u64> {
+		let sail = &self.state.player.vehicle.sail;
+		let damage = intact_sail_condition() - sail.condition;
+
+		if damage > 0.0 {
+			let price = damage * SAIL_REPAIR_PRICE_FACTOR * sail.kind.value() as f32;
+			Some((price.ceil() as u64).max(1))
+		} else {
+			None
+		}
+	}
+
+	/// Try to mend the sail, restoring it to intact
+	///
+	/// This function, if successful, will restore the sail's condition, and
+	/// reduce the players money accordingly.
+	///
+	/// Returns `Ok` if successful.
+	pub fn repair_sail(&mut self) -> Result<(), UpgradeError> {
+		// Do not trade if the player is too fast
+		if !self.has_player_valid_speed() {
+			// Player not docked
+			return Err(UpgradeError::NotDocked);
+		}
+
+		if let Some(repair_cost) = self.get_price_of_sail_repair() {
+			let money = &mut self.state.player.money;
+			if *money >= repair_cost {
+				*money -= repair_cost;
+				self.state.player.vehicle.sail.condition = intact_sail_condition();
+
+				Ok(())
+			} else {
+				// Insufficient funds
+				Err(UpgradeError::InsufficientFunds)
+			}
+		} else {
+			// Nothing to mend
+			Err(UpgradeError::Intact)
+		}
+	}
+
+	/// Try to repair the hull, restoring it to intact
+	///
+	/// This function, if successful, will restore the hull's integrity, and
+	/// reduce the players money accordingly.
+	///
+	/// Returns `Ok` if successful.
+	pub fn repair_hull(&mut self) -> Result<(), UpgradeError> {
+		// Do not trade if the player is too fast
+		if !self.has_player_valid_speed() {
+			// Player not docked
+			return Err(UpgradeError::NotDocked);
+		}
+
+		if let Some(repair_cost) = self.get_price_of_hull_repair() {
+			let money = &mut self.state.player.money;
+			if *money >= repair_cost {
+				*money -= repair_cost;
+				self.state.player.vehicle.hull_integrity = 1.0;
+
+				Ok(())
+			} else {
+				// Insufficient funds
+				Err(UpgradeError::InsufficientFunds)
+			}
+		} else {
+			// Nothing to repair
+			Err(UpgradeError::Intact)
+		}
+	}
+
 	/// Try to upgrade the sail to the next level (if any)
 	///
 	/// This function, if successful, will advance the ships sail level, and
@@ -576,8 +1178,8 @@ impl TradeOption<'_> {
 			return Err(UpgradeError::NotDocked);
 		}
 
-		let sail = &mut self.state.player.vehicle.sail.kind;
-		let upgrade_opt = sail.upgrade();
+		let sail = &mut self.state.player.vehicle.sail;
+		let upgrade_opt = sail.kind.upgrade();
 
 		if let Some(upgrade) = upgrade_opt {
 			let upgrade_cost = upgrade.value();
@@ -585,7 +1187,9 @@ impl TradeOption<'_> {
 			let money = &mut self.state.player.money;
 			if *money >= upgrade_cost {
 				*money -= upgrade_cost;
-				*sail = upgrade;
+				// The new sail comes brand-new
+				sail.kind = upgrade;
+				sail.condition = intact_sail_condition();
 
 				Ok(())
 			} else {
@@ -633,6 +1237,41 @@ impl TradeOption<'_> {
 		}
 	}
 
+	/// Try to upgrade the magnet to the next level (if any)
+	///
+	/// This function, if successful, will advance the ships magnet level, and
+	/// reduce the players money accordingly.
+	///
+	/// Returns `Ok` if successful.
+	pub fn upgrade_magnet(&mut self) -> Result<(), UpgradeError> {
+		// Do not trade if the player is too fast
+		if !self.has_player_valid_speed() {
+			// Player not docked
+			return Err(UpgradeError::NotDocked);
+		}
+
+		let magnet = &mut self.state.player.vehicle.magnet;
+		let upgrade_opt = magnet.upgrade();
+
+		if let Some(upgrade) = upgrade_opt {
+			let upgrade_cost = upgrade.value();
+
+			let money = &mut self.state.player.money;
+			if *money >= upgrade_cost {
+				*money -= upgrade_cost;
+				*magnet = upgrade;
+
+				Ok(())
+			} else {
+				// Insufficient funds
+				Err(UpgradeError::InsufficientFunds)
+			}
+		} else {
+			// Already at max level
+			Err(UpgradeError::MaxLevel)
+		}
+	}
+
 	/// The monetary volume traded so far, in money
 	pub fn get_traded_volume(&self) -> u64 {
 		u64::from(self.traded_fish_amount) * self.base_price
@@ -640,7 +1279,7 @@ impl TradeOption<'_> {
 
 	/// Check whether the player has a proper speed for trading
 	pub fn has_player_valid_speed(&self) -> bool {
-		self.state.player.vehicle.ground_speed() <= HARBOR_MAX_SPEED
+		self.state.player.vehicle.ground_speed() <= self.state.harbors[self.harbor_idx].max_speed
 	}
 
 	/// Returns the amount of fish the player has left
@@ -648,37 +1287,34 @@ impl TradeOption<'_> {
 		self.state.player.vehicle.resource_weight
 	}
 
-	/// Sell `amount` (in kg) of fish, returns the proceeds
+	/// Sell `amount` (in kg) of fish, returns the sold weight
+	///
+	/// The fish is sold in whole resource packs, thus slightly more than
+	/// `amount` might be sold.
 	pub fn sell_fish(&mut self, amount: u32) -> Option<u32> {
 		// Do not trade if the player is too fast
 		if !self.has_player_valid_speed() {
 			return None;
 		}
 
-		// Find the actual amount sellable
-		let (weight, value) = {
-			if amount >= self.state.player.vehicle.resource_weight {
-				(
-					self.state.player.vehicle.resource_weight,
-					self.state.player.vehicle.resource_value,
-				)
-			} else {
-				(
-					amount,
-					u64::from(amount) * self.state.player.vehicle.resource_value
-						/ u64::from(self.state.player.vehicle.resource_weight),
-				)
+		// Remove the fish from the player, pack by pack
+		let vehicle = &mut self.state.player.vehicle;
+		let value_before = vehicle.current_value();
+		let mut weight = 0;
+		for kind in ResourcePackContent::iter() {
+			let stats = &self.resources[kind];
+			while weight < amount && vehicle.unload(self.resources, kind) {
+				weight += stats.weight;
 			}
-		};
+		}
 
-		// Calculate the generated proceeds
+		// Calculate the generated proceeds, the spoiled fish sell for less.
+		// Selling the difference of the rounded value of the whole cargo keeps
+		// the odd fractions in the hold, so selling in steps earns just as much
+		// as selling all at once.
+		let value = value_before.saturating_sub(vehicle.current_value());
 		let proceeds = value * self.base_price;
 
-		// Remove the fish from the player
-		// This must not underflow, because we checked above
-		self.state.player.vehicle.resource_weight -= weight;
-		self.state.player.vehicle.resource_value -= value;
-
 		// Deposit proceeds into the player's account
 		// If the player manages to get 2^64 money, we just keep it that way
 		self.state.player.money = self.state.player.money.saturating_add(proceeds);
@@ -698,18 +1334,41 @@ pub enum UpgradeError {
 	NotDocked,
 	InsufficientFunds,
 	MaxLevel,
+	Intact,
 }
 impl fmt::Display for UpgradeError {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		let msg = match self {
 			Self::NotDocked => "Not docked at harbor",
 			Self::InsufficientFunds => "Insufficient funds",
-			Self::MaxLevel => "Already at max sail level",
+			Self::MaxLevel => "Already at max level",
+			Self::Intact => "Nothing to repair",
 		};
 		write!(f, "{}", msg)
 	}
 }
 
+/// The kinds of obstacles in the water
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize)]
+pub enum ObstacleKind {
+	Rock,
+	Wreck,
+}
+
+/// A small impassable object in the water, e.g. a rock
+///
+/// Unlike land, it does not fill an entire tile, thus ships can sail around it.
+#[derive(Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct Obstacle {
+	/// Absolute position of the center in meters
+	pub loc: Location,
+	/// The radius of the collision circle in meters
+	pub radius: f32,
+	pub kind: ObstacleKind,
+}
+
 /// Represents the car of a player
 #[derive(Debug, Copy, Clone)]
 #[derive(Serialize, Deserialize)]
@@ -718,6 +1377,31 @@ pub struct Harbor {
 	pub loc: Location,
 	/// Orientation in radians, zero is world x
 	pub orientation: f32,
+	/// The distance in meters, within which a player can interact with the harbor
+	///
+	/// Big trade hubs reach further out than tiny jetties.
+	pub effect_size: f32,
+	/// The maximum speed of the player while trading, in m/s
+	pub max_speed: f32,
+	/// The maximum speed of the player at which a ship is docked, in m/s
+	pub docking_speed: f32,
+}
+impl Harbor {
+	/// A harbor of the common size and speeds, see [HARBOR_EFFECT_SIZE]
+	pub fn new(loc: Location, orientation: f32) -> Self {
+		Self {
+			loc,
+			orientation,
+			effect_size: HARBOR_EFFECT_SIZE,
+			max_speed: HARBOR_MAX_SPEED,
+			docking_speed: HARBOR_DOCKING_SPEED,
+		}
+	}
+
+	/// The price this harbor pays for fish, in money per value
+	pub fn fish_price(&self) -> u64 {
+		1
+	}
 }
 
 
@@ -727,6 +1411,8 @@ pub struct Harbor {
 pub struct Vehicle {
 	/// The ship hull type
 	pub hull: ShipHull,
+	/// The resource magnet, pulling nearby resource packs towards the ship
+	pub magnet: Magnet,
 	/// Absolute position in meters
 	pub pos: Location,
 	/// Current movement in m/s
@@ -737,6 +1423,14 @@ pub struct Vehicle {
 	pub velocity: Vec2,
 	/// Current heading in radians, zero is world x
 	pub heading: f32,
+	/// Absolute position in meters as of the previous tick
+	///
+	/// Only used to interpolate the rendering between ticks.
+	pub prev_pos: Location,
+	/// Heading in radians as of the previous tick
+	///
+	/// Only used to interpolate the rendering between ticks.
+	pub prev_heading: f32,
 	/// Current angle of list in radians, zero in upright
 	///
 	/// A negative values means a tilt to the left, positive values tilt to the right.
@@ -746,13 +1440,40 @@ pub struct Vehicle {
 	/// Steering is always relative to `heading`.
 	///
 	/// See [Input::rudder]
-	pub ruder: BiPolarFraction,
+	#[serde(alias = "ruder")]
+	pub rudder: BiPolarFraction,
 	/// State of the engine
 	pub sail: Sail,
+	/// Whether the trawl net is deployed
+	///
+	/// A deployed net widens the collection radius, but slows the ship down.
+	pub net_deployed: bool,
 	//// Amount of fish and stuff on board in kg
 	pub resource_weight: u32,
 	//// Amount of fish and stuff on board in money
 	pub resource_value: u64,
+	/// Number of resource packs of each type on board
+	///
+	/// The totals are given by `resource_weight` and `resource_value`.
+	pub cargo: EnumMap<ResourcePackContent, u32>,
+	/// The freshness of the cargo, in range [FISH_MIN_FRESHNESS,1]
+	///
+	/// The cargo spoils in the hold, so it sells for only this fraction of
+	/// its `resource_value`, see [Vehicle::current_value].
+	pub freshness: f32,
+	/// The remaining integrity of the hull, in range [0,1]
+	///
+	/// The hull takes damage while run aground. A damaged hull leaks, adding
+	/// to the friction, until it is repaired at a harbor.
+	pub hull_integrity: f32,
+	/// Whether the ship has run aground in the last tick
+	pub grounded: bool,
+	/// Whether the ship lies at anchor, holding it in place
+	pub anchored: bool,
+	/// The remaining ticks per surface, during which collisions with it go unreported
+	///
+	/// See [COLLISION_COOLDOWN_TICKS].
+	pub collision_cooldown: EnumMap<CollisionSurface, u16>,
 }
 impl Vehicle {
 	/// Ground speed in m/s
@@ -762,6 +1483,106 @@ impl Vehicle {
 		self.velocity.magnitude()
 	}
 
+	/// Position interpolated between the previous and the current tick
+	///
+	/// `alpha` is the progress from the previous tick (`0`) to the current tick (`1`).
+	pub fn interpolated_pos(&self, terrain: &Terrain, alpha: f32) -> Location {
+		let step = terrain.torus_distance(self.prev_pos, self.pos);
+		terrain.map_loc_on_torus(self.prev_pos + step * alpha)
+	}
+
+	/// Heading interpolated between the previous and the current tick
+	///
+	/// `alpha` is the progress from the previous tick (`0`) to the current tick (`1`).
+	pub fn interpolated_heading(&self, alpha: f32) -> f32 {
+		self.prev_heading + normalize_angle_rel(self.heading - self.prev_heading) * alpha
+	}
+
+	/// Sets the position and heading without any interpolation from the previous pose
+	pub fn teleport(&mut self, pos: Location, heading: f32) {
+		self.pos = pos;
+		self.prev_pos = pos;
+		self.heading = heading;
+		self.prev_heading = heading;
+	}
+
+	/// Notes a collision with the `surface`, returning whether it is a new one
+	///
+	/// A collision is new, if the ship has not collided with the same kind
+	/// of surface during the last [COLLISION_COOLDOWN_TICKS].
+	fn note_collision(&mut self, surface: CollisionSurface) -> bool {
+		let is_new = self.collision_cooldown[surface] == 0;
+		self.collision_cooldown[surface] = COLLISION_COOLDOWN_TICKS;
+		is_new
+	}
+
+	/// The distance in meters between the ship and a resource pack, at which
+	/// the resource pack gets collected
+	pub fn collection_radius(&self) -> f32 {
+		if self.net_deployed {
+			RESOURCE_PICKUP_DISTANCE * NET_COLLECTION_RADIUS_FACTOR
+		} else {
+			RESOURCE_PICKUP_DISTANCE
+		}
+	}
+
+	/// The wind as experienced on board in m/s
+	///
+	/// This is the true `wind` minus the ship's own `velocity`.
+	pub fn apparent_wind(&self, wind: Wind) -> Vec2 {
+		wind.0 - self.velocity
+	}
+
+	/// The force of the `wind` propelling the ship, if it had the given `reefing`
+	///
+	/// It acts in the direction of the apparent wind, pushing the sails
+	/// as well as the hull.
+	pub fn propulsion(&self, wind: Wind, reefing: Reefing) -> Vec2 {
+		let apparent_wind = self.apparent_wind(wind);
+		let sail = Sail {
+			reefing,
+			..self.sail
+		};
+
+		let sail_drag_ness = sail.efficiency(apparent_wind);
+
+		let sail_drag = apparent_wind * sail_drag_ness;
+
+
+		let static_ship_area = 1.;
+		let sail_area = sail.sail_area();
+
+		sail_drag * sail_area + apparent_wind * static_ship_area
+	}
+
+	/// The reefing, which drives the ship forward the most in the `wind`
+	///
+	/// This evaluates the [Self::propulsion] along the heading for every
+	/// reefing level the sail supports, skipping those which would tear the
+	/// sail. It is what the auto-reef assist sets.
+	pub fn auto_reefing(&self, wind: Wind) -> Reefing {
+		let heading = self.heading_vec();
+		let apparent_wind = self.apparent_wind(wind);
+		let drive = |reefing: &Reefing| self.propulsion(wind, *reefing).dot(&heading);
+		let withstands = |reefing: &Reefing| {
+			let sail = Sail {
+				reefing: *reefing,
+				..self.sail
+			};
+			sail.strain(apparent_wind) <= SAIL_STRAIN_LIMIT
+		};
+
+		(0..=self.sail.kind.max_reefing().0)
+			.map(Reefing)
+			.filter(withstands)
+			.max_by(|a, b| {
+				drive(a)
+					.partial_cmp(&drive(b))
+					.unwrap_or(std::cmp::Ordering::Equal)
+			})
+			.unwrap_or_default()
+	}
+
 	/// Heading as unit vector.
 	pub fn heading_vec(&self) -> Vec2 {
 		Vec2::new(self.heading.cos(), self.heading.sin())
@@ -773,38 +1594,67 @@ impl Vehicle {
 		Vec2::new(tangent.cos(), tangent.sin())
 	}
 
-	/// The speed covered by the wheels.
+	/// The speed through the water along the keel.
 	///
 	/// Notice this gives the "signed" speed in the direction of `heading`.
-	pub fn wheel_speed(&self) -> f32 {
+	pub fn forward_speed(&self) -> f32 {
 		self.velocity.dot(&self.heading_vec())
 	}
 
-	/// The speed orthogonal to the wheels
+	/// The leeway, i.e. the speed orthogonal to the keel
 	///
 	/// Notice this is the "signed" speed in the direction of the tangent (i.e. the orthogonal of `heading`).
-	pub fn cross_speed(&self) -> f32 {
+	pub fn drift_speed(&self) -> f32 {
 		self.velocity.dot(&self.tangent_vec())
 	}
 
+	/// The speed covered by the wheels, a relic of the car physics
+	#[deprecated = "use `forward_speed` instead"]
+	pub fn wheel_speed(&self) -> f32 {
+		self.forward_speed()
+	}
+
+	/// The speed orthogonal to the wheels, a relic of the car physics
+	#[deprecated = "use `drift_speed` instead"]
+	pub fn cross_speed(&self) -> f32 {
+		self.drift_speed()
+	}
+
+	/// The current steering, misspelled
+	#[deprecated = "use the `rudder` field instead"]
+	pub fn ruder(&self) -> BiPolarFraction {
+		self.rudder
+	}
+
 	/// The acceleration caused by friction in m/s
 	///
 	/// This acceleration is vectorial thus it can be just added to the `velocity`.
-	pub fn friction_deacceleration(&self) -> Vec2 {
+	pub fn friction_deacceleration(&self, physics: &PhysicsConf) -> Vec2 {
+		// A damaged hull takes on water, making the ship sluggish
+		let leak = 1.0 + HULL_LEAK_DRAG_FACTOR * (1.0 - self.hull_integrity);
+
 		let rolling_friction =
-			-self.wheel_speed() * FRICTION_GROUND_SPEED_FACTOR * self.heading_vec();
+			-self.forward_speed() * physics.ground_friction * leak * self.heading_vec();
 
 		let sliding_friction =
-			-self.cross_speed() * FRICTION_CROSS_SPEED_FACTOR * self.tangent_vec();
+			-self.drift_speed() * physics.cross_friction * leak * self.tangent_vec();
+
+		// A deployed net drags along in any direction
+		let net_friction = if self.net_deployed {
+			-self.velocity * physics.net_friction
+		} else {
+			vec2(0., 0.)
+		};
 
-		rolling_friction + sliding_friction
+		rolling_friction + sliding_friction + net_friction
 	}
 
 	/// Apply the given `input` to this vehicle
 	pub fn apply_input(&mut self, input: Input) {
 		Input {
 			reefing: self.sail.reefing,
-			rudder: self.ruder,
+			rudder: self.rudder,
+			net: self.net_deployed,
 		} = input;
 	}
 
@@ -812,28 +1662,81 @@ impl Vehicle {
 	pub fn mass(&self) -> f32 {
 		VEHICLE_DEADWEIGHT + self.resource_weight as f32
 	}
+
+	/// The value of the cargo as is, after spoiling, in money
+	pub fn current_value(&self) -> u64 {
+		(self.resource_value as f32 * self.freshness).floor() as u64
+	}
+
+	/// Lets the cargo spoil for one tick, see [FISH_SPOIL_RATE]
+	fn spoil(&mut self) {
+		self.freshness = (self.freshness - FISH_SPOIL_RATE * DELTA).max(FISH_MIN_FRESHNESS);
+	}
+
+	/// Puts a resource pack of the given `kind` on board
+	///
+	/// The fresh pack raises the `freshness` of the cargo by its share of the value.
+	pub fn load(&mut self, table: &ResourceTable, kind: ResourcePackContent) {
+		let old = self.resource_value as f32;
+		let new = table[kind].value as f32;
+		if old + new > 0.0 {
+			self.freshness = (self.freshness * old + new) / (old + new);
+		}
+
+		self.cargo[kind] += 1;
+		self.resource_weight += table[kind].weight;
+		self.resource_value += table[kind].value;
+	}
+
+	/// Takes a resource pack of the given `kind` off board
+	///
+	/// Returns `false`, if there is no such resource pack on board.
+	pub fn unload(&mut self, table: &ResourceTable, kind: ResourcePackContent) -> bool {
+		if self.cargo[kind] == 0 {
+			return false;
+		}
+
+		self.cargo[kind] -= 1;
+		self.resource_weight -= table[kind].weight;
+		self.resource_value -= table[kind].value;
+		true
+	}
 }
 
 impl Default for Vehicle {
 	fn default() -> Self {
 		Self {
 			hull: Default::default(),
+			magnet: Default::default(),
 			pos: Default::default(),
 			sail: Default::default(),
 			heading: Default::default(),
-			ruder: Default::default(),
+			prev_pos: Default::default(),
+			prev_heading: Default::default(),
+			rudder: Default::default(),
 			velocity: Default::default(),
+			net_deployed: false,
 			resource_weight: 0,
 			resource_value: 0,
+			cargo: EnumMap::default(),
+			freshness: 1.0,
 			angle_of_list: 0.0,
+			hull_integrity: 1.0,
+			grounded: false,
+			anchored: false,
+			collision_cooldown: EnumMap::default(),
 		}
 	}
 }
 
 
-/// Represents the type or upgrade level of the sail
+/// Represents the type or upgrade level of the hull
+///
+/// The variants are ordered by upgrade level, i.e. each hull can be upgraded
+/// to the following one.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[derive(Enum)]
+#[derive(strum::EnumIter)]
 #[derive(Serialize, Deserialize)]
 pub enum ShipHull {
 	Small,
@@ -846,12 +1749,9 @@ impl Default for ShipHull {
 	}
 }
 impl ShipHull {
+	/// Gives the next better hull, if any
 	pub fn upgrade(self) -> Option<Self> {
-		use ShipHull::*;
-		match self {
-			Small => Some(Bigger),
-			Bigger => None,
-		}
+		Self::iter().skip_while(|&hull| hull != self).nth(1)
 	}
 
 	pub fn value(self) -> u64 {
@@ -863,13 +1763,65 @@ impl ShipHull {
 	}
 }
 
+/// Represents the upgrade level of the resource magnet
+///
+/// The variants are ordered by upgrade level, i.e. each magnet can be upgraded
+/// to the following one. New ships come without any magnet.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Enum)]
+#[derive(strum::EnumIter)]
+#[derive(Serialize, Deserialize)]
+pub enum Magnet {
+	Absent,
+	Lodestone,
+	Electromagnet,
+}
+// TODO: use the `#[default]` attribute one day instead
+impl Default for Magnet {
+	fn default() -> Self {
+		Self::Absent
+	}
+}
+impl Magnet {
+	/// Gives the next better magnet, if any
+	pub fn upgrade(self) -> Option<Self> {
+		Self::iter().skip_while(|&magnet| magnet != self).nth(1)
+	}
+
+	/// Returns the nominal value of this magnet (i.e. purchase cost)
+	pub fn value(self) -> u64 {
+		use Magnet::*;
+		match self {
+			Absent => 0,
+			Lodestone => 3_000,
+			Electromagnet => 6_000,
+		}
+	}
+
+	/// Returns the distance in meters, within which this magnet pulls resource packs
+	///
+	/// They are pulled at the [MAGNET_PULL_SPEED].
+	pub fn radius(self) -> f32 {
+		match self {
+			Self::Absent => 0.,
+			Self::Lodestone => 8.,
+			Self::Electromagnet => 16.,
+		}
+	}
+}
+
 /// Represents the type or upgrade level of the sail
+///
+/// The variants are ordered by upgrade level, i.e. each sail can be upgraded
+/// to the following one.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[derive(Enum)]
+#[derive(strum::EnumIter)]
 #[derive(Serialize, Deserialize)]
 pub enum SailKind {
 	Cog,
 	Bermuda,
+	Gaff,
 	Schooner,
 }
 // TODO: use the `#[default]` attribute one day instead
@@ -881,12 +1833,7 @@ impl Default for SailKind {
 impl SailKind {
 	/// Gives the next better sail kind, if any
 	pub fn upgrade(self) -> Option<Self> {
-		use SailKind::*;
-		match self {
-			Cog => Some(Bermuda),
-			Bermuda => Some(Schooner),
-			Schooner => None,
-		}
+		Self::iter().skip_while(|&kind| kind != self).nth(1)
 	}
 
 	/// Returns the nominal value of this sail (i.e. purchase cost)
@@ -895,6 +1842,7 @@ impl SailKind {
 		match self {
 			Cog => 500,
 			Bermuda => 1_000,
+			Gaff => 1_500,
 			Schooner => 2_000,
 		}
 	}
@@ -904,6 +1852,7 @@ impl SailKind {
 		let reefs = match self {
 			Self::Cog => 3,
 			Self::Bermuda => 4,
+			Self::Gaff => 5,
 			Self::Schooner => 7,
 		};
 		Reefing(reefs)
@@ -915,19 +1864,37 @@ impl SailKind {
 			// TODO: Maybe use 300, once lift-based sailing comes around
 			Self::Cog => 100.,
 			Self::Bermuda => 200.,
+			Self::Gaff => 300.,
 			Self::Schooner => 500.,
 		}
 	}
+
+	/// Returns the least fraction of the wind this sail captures, regardless
+	/// of the point of sail.
+	///
+	/// The peaked yard of the gaff rig keeps drawing even when pointing high
+	/// into the wind.
+	pub fn min_efficiency(self) -> f32 {
+		match self {
+			Self::Gaff => 0.2,
+			Self::Cog | Self::Bermuda | Self::Schooner => 0.0,
+		}
+	}
 }
 
 /// Represents the sail of the ship
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Copy, Clone)]
 #[derive(Serialize, Deserialize)]
 pub struct Sail {
 	/// The sail type
 	pub kind: SailKind,
-	/// Current engagement of the break pedal (1.0 is full breaking, 0.0 is no-breaking)
-	pub condition: Fraction,
+	/// The remaining condition of the sail, in range [0,1]
+	///
+	/// A torn sail captures less wind, see [Self::sail_area]. It wears down
+	/// under too much strain, see [Self::strain], and is mended at a harbor.
+	///
+	/// Saves older than version 4 had no wear, their sails load intact.
+	pub condition: f32,
 	/// Current state of the gear box.
 	pub reefing: Reefing,
 	/// Absolute sail orientation for rectangle-rigged sails in radians, zero is word-X.
@@ -935,6 +1902,21 @@ pub struct Sail {
 	/// Absolute sail orientation for triangle-rigged sails in radians, zero is word-X.
 	pub orientation_triangle: f32,
 }
+/// The condition of a brand-new sail
+fn intact_sail_condition() -> f32 {
+	1.0
+}
+impl Default for Sail {
+	fn default() -> Self {
+		Self {
+			kind: Default::default(),
+			condition: intact_sail_condition(),
+			reefing: Default::default(),
+			orientation_rectangle: 0.0,
+			orientation_triangle: 0.0,
+		}
+	}
+}
 impl Sail {
 	/// Square rigged orientation as unit vector.
 	pub fn orientation_rectangle_vec(&self) -> Vec2 {
@@ -952,12 +1934,46 @@ impl Sail {
 		)
 	}
 
+	/// The fraction of the given apparent wind captured by the sail.
+	///
+	/// This depends on the point of sail, i.e. the angle between the
+	/// sail and the wind, and ranges from `0.0` (sail parallel to the wind,
+	/// or no wind at all) to `1.0` (sail perpendicular to the wind).
+	/// Some sail kinds have a higher minimum, see [SailKind::min_efficiency].
+	pub fn efficiency(&self, apparent_wind: Vec2) -> f32 {
+		if apparent_wind.norm_squared() <= f32::EPSILON {
+			return 0.0;
+		}
+
+		let captured = 1.
+			- self
+				.orientation_triangle_vec()
+				.dot(&apparent_wind.normalize())
+				.abs();
+
+		captured.max(self.kind.min_efficiency())
+	}
+
+	/// The fraction of the sail currently set, ranging from `0.0` (struck) to `1.0` (full sail)
+	pub fn deployment(self) -> f32 {
+		(f32::from(self.reefing.0) / f32::from(self.kind.max_reefing().0)).min(1.0)
+	}
+
 	/// The currently deployed area of the sail.
+	///
+	/// A torn sail has holes in it, reducing the effective area by its `condition`.
 	pub fn sail_area(self) -> f32 {
 		let max_area = self.kind.max_area();
-		let rel_sail = (f32::from(self.reefing.0) / f32::from(self.kind.max_reefing().0)).min(1.0);
 
-		max_area * rel_sail.powi(2)
+		max_area * self.deployment().powi(2) * self.condition
+	}
+
+	/// The strain on the sail in the `apparent_wind`, in m/s
+	///
+	/// This is the apparent wind speed scaled by the [Self::deployment], thus
+	/// reefing relieves the sail. Beyond [SAIL_STRAIN_LIMIT] the sail tears.
+	pub fn strain(self, apparent_wind: Vec2) -> f32 {
+		apparent_wind.magnitude() * self.deployment()
 	}
 }
 
@@ -0,0 +1,1370 @@
+use std::f32::consts::FRAC_PI_2;
+
+use strum::IntoEnumIterator;
+
+use super::Event;
+use super::Harbor;
+use super::Magnet;
+use super::Obstacle;
+use super::ObstacleKind;
+use super::Reefing;
+use super::Sail;
+use super::SailKind;
+use super::ShipHull;
+use super::UpgradeError;
+use super::WorldState;
+use crate::generator::Setting;
+use crate::glm::vec2;
+use crate::race::Race;
+use crate::resource::ResourcePack;
+use crate::resource::ResourcePackContent;
+use crate::terrain::Surface;
+use crate::terrain::Terrain;
+use crate::terrain::TileCoord;
+use crate::units::BiPolarFraction;
+use crate::units::Distance;
+use crate::units::Elevation;
+use crate::units::Location;
+use crate::units::Tick;
+use crate::units::TileType;
+use crate::units::Wind;
+use crate::DebuggingConf;
+use crate::Input;
+use crate::WorldInit;
+use crate::COLLISION_COOLDOWN_TICKS;
+use crate::FISH_MIN_FRESHNESS;
+use crate::GROUNDING_ELEVATION;
+use crate::HARBOR_SIZE;
+use crate::MAGNET_PULL_SPEED;
+use crate::MAX_WIND_SPEED;
+use crate::RESOURCE_LOD_INTERVAL;
+use crate::SAIL_MIN_CONDITION;
+use crate::SAIL_STRAIN_LIMIT;
+use crate::TICKS_PER_SECOND;
+use crate::VEHICLE_MAX_SPEED;
+use crate::VEHICLE_SIZE;
+use crate::WIND_CALM_FACTOR;
+use crate::WIND_CHANGE_INTERVAL;
+
+/// Creates a world consisting of deep water only, with the player in the middle
+fn open_sea() -> (WorldInit, WorldState) {
+	let edge_length = 16;
+	let mut terrain = Terrain::new(edge_length);
+	for (_, elev) in terrain.iter_mut() {
+		*elev = Elevation::DEEPEST;
+	}
+
+	let init = WorldInit {
+		terrain_setting: Setting {
+			edge_length,
+			resource_density: 0.0,
+			resources: Default::default(),
+		},
+		seed: 42,
+		dbg: DebuggingConf {
+			fixed_wind_direction: Some(0.3),
+			..Default::default()
+		},
+		physics: Default::default(),
+		animation_lod: None,
+		terrain,
+	};
+
+	let mut state = WorldState::default();
+	state.player.vehicle.pos = Location::new(32.0, 32.0);
+
+	(init, state)
+}
+
+#[test]
+fn neutral_rudder_keeps_heading() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.player.vehicle.heading = 1.0;
+	let input = Input {
+		reefing: Reefing(3),
+		rudder: BiPolarFraction(0),
+		net: false,
+	};
+
+	// Act
+	for _ in 0..1000 {
+		state.update(&init, &input);
+	}
+
+	// Assert
+	assert!(state.player.vehicle.ground_speed() > 0.0);
+	assert_eq!(state.player.vehicle.heading, 1.0);
+}
+
+#[test]
+fn small_rudder_turns_less() {
+	// Arrange
+	let (init, state) = open_sea();
+	let turn_with = |rudder: f32| {
+		let mut state = state.clone();
+		let input = Input {
+			reefing: Reefing(3),
+			rudder: BiPolarFraction::from_f32(rudder).unwrap(),
+			net: false,
+		};
+
+		for _ in 0..100 {
+			state.update(&init, &input);
+		}
+
+		state.player.vehicle.heading
+	};
+
+	// Act
+	let small = turn_with(0.1);
+	let full = turn_with(1.0);
+	let small_left = turn_with(-0.1);
+
+	// Assert
+	assert!(small > 0.0);
+	assert!(small < full);
+	assert!(small_left < 0.0);
+}
+
+/// A resource pack, that is exactly at `loc` in the first tick
+fn resource_at(loc: Location) -> ResourcePack {
+	let mut resource = ResourcePack {
+		content: ResourcePackContent::Fish0,
+		loc,
+		ori: 0.0,
+		elevation: Elevation::DEEPEST,
+		origin: loc,
+		params: (0, 0),
+		phase: 0.0,
+		speed_factor: 100,
+		backwards: false,
+	};
+
+	// Compensate for the animation offset
+	let mut animated = resource.clone();
+	animated.update(Tick(1));
+	resource.origin = Location(loc.0 - (animated.loc - loc).0);
+
+	resource
+}
+
+#[test]
+fn fast_ship_collects_resource_along_its_path() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	let fish_loc = Location::new(20.0, 20.0);
+	state.resources.push(resource_at(fish_loc));
+	// At 180 m/s the ship travels about 3 m per tick, so the resource is
+	// neither within reach at the start nor at the end of this tick.
+	state.player.vehicle.pos = fish_loc - Distance::new(1.5, 0.0);
+	state.player.vehicle.velocity = vec2(180.0, 0.0);
+	state.player.vehicle.heading = 0.0;
+
+	// Act
+	state.update(&init, &Input::default());
+
+	// Assert
+	assert!(
+		init.terrain
+			.torus_distance(fish_loc, state.player.vehicle.pos)
+			.magnitude()
+			> 1.2
+	);
+	assert!(state.resources.is_empty());
+	assert_eq!(
+		state.player.vehicle.resource_weight,
+		ResourcePackContent::Fish0.weight
+	);
+}
+
+#[test]
+fn pickup_event_is_located_at_the_resource() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	let fish_loc = Location::new(20.0, 20.0);
+	state.resources.push(resource_at(fish_loc));
+	state.player.vehicle.pos = fish_loc;
+
+	// Act
+	let events = state.update(&init, &Input::default());
+
+	// Assert
+	let loc = events
+		.iter()
+		.find(|ev| matches!(ev, Event::Fishy(_)))
+		.map(Event::loc)
+		.unwrap();
+	assert!(init.terrain.torus_distance(fish_loc, loc).magnitude() < 0.01);
+}
+
+#[test]
+fn fast_ship_bounces_off_thin_wall() {
+	// Arrange
+	let (mut init, mut state) = open_sea();
+	// A wall of one tile thickness, spanning from 32 m to 36 m along x
+	for y in 0..init.terrain.edge_length {
+		*init.terrain.get_mut(TileCoord::new(8, y)) = Elevation(1);
+	}
+	// At 600 m/s, the ship would travel past the wall within a single tick
+	state.player.vehicle.pos = Location::new(30.0, 20.0);
+	state.player.vehicle.velocity = vec2(600.0, 0.0);
+	state.player.vehicle.heading = 0.0;
+
+	// Act
+	let events = state.update(&init, &Input::default());
+
+	// Assert
+	assert!(state.player.vehicle.pos.0.x < 32.0);
+	assert!(state.player.vehicle.velocity.x < 0.0);
+	assert!(events
+		.iter()
+		.any(|ev| matches!(ev, Event::TileCollision(_, _))));
+}
+
+#[test]
+fn fast_ship_bounces_off_harbor() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state
+		.harbors
+		.push(Harbor::new(Location::new(40.0, 20.0), 0.0));
+	// At 600 m/s, the ship would pass through the harbor within a single tick
+	state.player.vehicle.pos = Location::new(34.0, 20.0);
+	state.player.vehicle.velocity = vec2(600.0, 0.0);
+	state.player.vehicle.heading = 0.0;
+
+	// Act
+	let events = state.update(&init, &Input::default());
+
+	// Assert
+	assert!(state.player.vehicle.pos.0.x < 40.0 - (HARBOR_SIZE + VEHICLE_SIZE) * 0.5);
+	assert!(state.player.vehicle.velocity.x < 0.0);
+	assert!(events
+		.iter()
+		.any(|ev| matches!(ev, Event::HarborCollision(_, _))));
+}
+
+#[test]
+fn fast_ship_bounces_off_obstacle() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.obstacles.push(Obstacle {
+		loc: Location::new(40.0, 20.0),
+		radius: 1.0,
+		kind: ObstacleKind::Rock,
+	});
+	// At 600 m/s, the ship would pass through the rock within a single tick
+	state.player.vehicle.pos = Location::new(34.0, 20.0);
+	state.player.vehicle.velocity = vec2(600.0, 0.0);
+	state.player.vehicle.heading = 0.0;
+
+	// Act
+	let events = state.update(&init, &Input::default());
+
+	// Assert
+	assert!(state.player.vehicle.pos.0.x < 40.0 - 1.0 - VEHICLE_SIZE * 0.5);
+	assert!(state.player.vehicle.velocity.x < 0.0);
+	assert!(events
+		.iter()
+		.any(|ev| matches!(ev, Event::ObstacleCollision(_, _))));
+}
+
+#[test]
+fn seek_to_matches_stepping() {
+	// Arrange
+	let (mut init, mut state) = open_sea();
+	init.dbg.fixed_wind_direction = None;
+	let mut fish = resource_at(Location::new(20.0, 20.0));
+	fish.params = (2, -3);
+	state.resources.push(fish);
+	let mut stepped = state.clone();
+	let mut seeked = state;
+
+	// Act
+	for _ in 0..500 {
+		stepped.update(&init, &Input::default());
+	}
+	seeked.seek_to(&init, Tick(499));
+	seeked.update(&init, &Input::default());
+
+	// Assert
+	assert_eq!(seeked.timestamp, stepped.timestamp);
+	assert_eq!(seeked.wind, stepped.wind);
+	assert_eq!(seeked.resources[0].loc, stepped.resources[0].loc);
+	assert_eq!(seeked.resources[0].ori, stepped.resources[0].ori);
+}
+
+#[test]
+fn animation_lod_animates_distant_resources_less_often() {
+	// Arrange
+	let (mut init, mut state) = open_sea();
+	let mut near = resource_at(Location::new(40.0, 32.0));
+	near.params = (2, -3);
+	let mut distant = resource_at(Location::new(4.0, 4.0));
+	distant.params = (2, -3);
+	state.resources = vec![near, distant];
+	// Start off the first tick, where the resources are placed exactly
+	state.timestamp = Tick(RESOURCE_LOD_INTERVAL);
+	let mut full = state.clone();
+	init.animation_lod = Some(16.0);
+	let full_init = WorldInit {
+		animation_lod: None,
+		..init.clone()
+	};
+
+	// Act & Assert
+	for _ in 0..RESOURCE_LOD_INTERVAL {
+		state.update(&init, &Input::default());
+		full.update(&full_init, &Input::default());
+
+		assert_eq!(state.resources[0].loc, full.resources[0].loc);
+		if state.timestamp.0 % RESOURCE_LOD_INTERVAL == 0 {
+			assert_eq!(state.resources[1].loc, full.resources[1].loc);
+		} else {
+			assert_ne!(state.resources[1].loc, full.resources[1].loc);
+		}
+	}
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_resource_animation_matches_serial() {
+	use rand::SeedableRng;
+
+	use crate::generator::Generator;
+	use crate::generator::PerlinNoise;
+	use crate::StdRng;
+
+	// Arrange
+	let setting = Setting {
+		edge_length: 64,
+		resource_density: 1.0,
+		resources: Default::default(),
+	};
+	let crate::World {
+		mut init,
+		state,
+	} = PerlinNoise.generate(&setting, StdRng::seed_from_u64(42));
+	init.animation_lod = Some(32.0);
+	let mut serial = state.clone();
+	let mut parallel = state;
+
+	// Act & Assert
+	for tick in 1..=2 * RESOURCE_LOD_INTERVAL {
+		serial.timestamp = Tick(tick);
+		parallel.timestamp = Tick(tick);
+		serial.animate_resources_serial(&init, true);
+		parallel.animate_resources_parallel(&init, true);
+
+		for (s, p) in serial.resources.iter().zip(&parallel.resources) {
+			assert_eq!(s.loc, p.loc);
+			assert_eq!(s.ori, p.ori);
+		}
+	}
+}
+
+#[test]
+fn ship_glitched_into_land_kedges_off_into_water() {
+	// Arrange
+	let (mut init, mut state) = open_sea();
+	// Land all around the ship, except for water two tiles east of it
+	let ship_tile = TileCoord::new(8, 8);
+	for x in 6..=9 {
+		for y in 6..=10 {
+			*init.terrain.get_mut(TileCoord::new(x, y)) = Elevation::HIGHEST;
+		}
+	}
+	state.player.vehicle.pos = ship_tile.into();
+
+	// Act
+	let stuck = state.is_stuck(&init);
+	let mut ticks = 0;
+	while state.kedge_off(&init) && ticks < 20 * TICKS_PER_SECOND {
+		state.update(&init, &Input::default());
+		ticks += 1;
+	}
+
+	// Assert
+	assert!(stuck);
+	assert!(ticks > 0);
+	assert!(!state.is_stuck(&init));
+	let tile: TileCoord = state.player.vehicle.pos.try_into().unwrap();
+	assert!(init.terrain.get(tile).is_passable(), "{tile:?}");
+}
+
+#[test]
+fn ship_in_open_water_is_not_stuck() {
+	// Arrange
+	let (init, mut state) = open_sea();
+
+	// Act & Assert
+	assert!(!state.is_stuck(&init));
+	assert!(!state.kedge_off(&init));
+	assert_eq!(state.player.vehicle.ground_speed(), 0.0);
+}
+
+/// Puts the ship right next to a strip of land, east of it
+fn next_to_land() -> (WorldInit, WorldState) {
+	let (mut init, mut state) = open_sea();
+	let ship_tile = TileCoord::new(8, 8);
+	for y in 0..16 {
+		*init.terrain.get_mut(TileCoord::new(9, y)) = Elevation::HIGHEST;
+	}
+	state.player.vehicle.pos = ship_tile.into();
+
+	(init, state)
+}
+
+#[test]
+fn ship_next_to_land_is_stuck() {
+	// Arrange
+	let (init, state) = next_to_land();
+
+	// Act & Assert
+	assert!(state.is_stuck(&init));
+}
+
+#[test]
+fn ship_at_anchor_next_to_land_is_not_stuck() {
+	// Arrange
+	let (init, mut state) = next_to_land();
+
+	// Act
+	state.player.vehicle.anchored = true;
+
+	// Assert
+	assert!(!state.is_stuck(&init));
+}
+
+#[test]
+fn ship_docked_next_to_land_is_not_stuck() {
+	// Arrange
+	let (init, mut state) = next_to_land();
+
+	// Act
+	let pos = state.player.vehicle.pos;
+	state.harbors.push(Harbor::new(pos, 0.0));
+
+	// Assert
+	assert!(state.get_trading(&init).is_some());
+	assert!(!state.is_stuck(&init));
+}
+
+#[test]
+fn net_widens_collection_radius_and_slows_down() {
+	// Arrange
+	let (init, state) = open_sea();
+	let sail_with = |net: bool| {
+		let mut state = state.clone();
+		let input = Input {
+			reefing: Reefing(3),
+			rudder: BiPolarFraction(0),
+			net,
+		};
+
+		for _ in 0..600 {
+			state.update(&init, &input);
+		}
+
+		state.player.vehicle
+	};
+
+	// Act
+	let without_net = sail_with(false);
+	let with_net = sail_with(true);
+
+	// Assert
+	assert!(with_net.net_deployed);
+	assert!(with_net.collection_radius() > without_net.collection_radius());
+	assert!(with_net.ground_speed() < without_net.ground_speed());
+}
+
+#[test]
+fn resources_recover_after_mass_collection() {
+	// Arrange
+	let (mut init, mut state) = open_sea();
+	// Provide water of every depth, so that every resource type finds a place to spawn
+	for (tc, elev) in init.terrain.iter_mut() {
+		*elev = Elevation(-18 + ((tc.x + tc.y) % 18) as i16);
+	}
+	init.terrain_setting.resource_density = 0.1;
+	let target: usize = ResourcePackContent::iter()
+		.map(|ty| init.terrain_setting.resource_amount(ty))
+		.sum();
+	// As if the player had collected everything
+	state.resources.clear();
+
+	// Act
+	for _ in 0..(60 * u64::from(TICKS_PER_SECOND)) {
+		state.update(&init, &Input::default());
+	}
+
+	// Assert
+	assert!(target > 0);
+	assert!(state.resources.len() as f32 >= 0.9 * target as f32);
+}
+
+#[test]
+fn market_report_lists_all_harbors() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.player.vehicle.pos = Location::new(4.0, 32.0);
+	for loc in [Location::new(12.0, 32.0), Location::new(60.0, 32.0)] {
+		state.harbors.push(Harbor::new(loc, 0.0));
+	}
+
+	// Act
+	let report = state.market_report(&init);
+
+	// Assert
+	assert_eq!(report.len(), 2);
+	assert_eq!(report[0].0, 0);
+	assert_eq!(report[0].1, Distance::new(8.0, 0.0));
+	assert_eq!(report[1].0, 1);
+	// The second harbor is closer across the map edge
+	assert_eq!(report[1].1, Distance::new(-8.0, 0.0));
+	for (idx, _, price) in report {
+		assert_eq!(price, state.harbors[idx].fish_price());
+	}
+}
+
+#[test]
+fn calm_wind_is_weak() {
+	// Arrange
+	let (mut init, _) = open_sea();
+	init.dbg.fixed_wind_direction = None;
+	let interval = u64::from(TICKS_PER_SECOND) * u64::from(WIND_CHANGE_INTERVAL);
+
+	// Act
+	let calm = WorldState::next_calm(&init, Tick(0), 1000 * interval);
+
+	// Assert
+	let calm = calm.expect("no calm within a thousand wind intervals");
+	assert_eq!(calm.0 % interval, 0);
+	let wind = WorldState::wind_at(&init, calm);
+	assert!(wind.magnitude() <= MAX_WIND_SPEED * WIND_CALM_FACTOR + 1e-4);
+}
+
+#[test]
+fn fixed_wind_is_never_calm() {
+	// Arrange
+	let (init, _) = open_sea();
+	let interval = u64::from(TICKS_PER_SECOND) * u64::from(WIND_CHANGE_INTERVAL);
+
+	// Act
+	let calm = WorldState::next_calm(&init, Tick(0), 1000 * interval);
+
+	// Assert
+	assert_eq!(calm, None);
+}
+
+#[test]
+fn fixed_wind_forecast_is_constant() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.timestamp = Tick(1234);
+
+	// Act
+	let forecast = state.forecast(&init, 5);
+
+	// Assert
+	assert_eq!(forecast.len(), 5);
+	let now = WorldState::wind_at(&init, state.timestamp);
+	for (_, wind) in forecast {
+		assert_eq!(wind, now);
+	}
+}
+
+#[test]
+fn forecast_starts_at_the_end_of_the_current_interval() {
+	// Arrange
+	let (mut init, mut state) = open_sea();
+	init.dbg = Default::default();
+	let interval = u64::from(TICKS_PER_SECOND) * u64::from(WIND_CHANGE_INTERVAL);
+	state.timestamp = Tick(3 * interval + 17);
+
+	// Act
+	let forecast = state.forecast(&init, 3);
+	while state.timestamp < forecast[0].0 {
+		state.update(&init, &Input::default());
+	}
+
+	// Assert
+	let ticks: Vec<_> = forecast.iter().map(|(tick, _)| *tick).collect();
+	assert_eq!(
+		ticks,
+		vec![Tick(4 * interval), Tick(5 * interval), Tick(6 * interval)]
+	);
+	// The forecast holds, once the simulation gets there
+	assert_eq!(state.timestamp, Tick(4 * interval));
+	assert_eq!(state.wind, forecast[0].1);
+}
+
+#[test]
+fn hull_upgrades_follow_the_declaration_order() {
+	// Arrange
+	let mut hull = ShipHull::default();
+	let mut chain = vec![hull];
+
+	// Act
+	while let Some(next) = hull.upgrade() {
+		hull = next;
+		chain.push(hull);
+	}
+
+	// Assert
+	assert_eq!(chain, Vec::from_iter(ShipHull::iter()));
+	assert!(chain.windows(2).all(|w| w[0].value() < w[1].value()));
+}
+
+#[test]
+fn magnet_upgrades_follow_the_declaration_order() {
+	// Arrange
+	let mut magnet = Magnet::default();
+	let mut chain = vec![magnet];
+
+	// Act
+	while let Some(next) = magnet.upgrade() {
+		magnet = next;
+		chain.push(magnet);
+	}
+
+	// Assert
+	assert_eq!(chain, Vec::from_iter(Magnet::iter()));
+	assert!(chain.windows(2).all(|w| w[0].radius() < w[1].radius()));
+}
+
+#[test]
+fn sail_upgrades_follow_the_declaration_order() {
+	// Arrange
+	let mut kind = SailKind::default();
+	let mut chain = vec![kind];
+
+	// Act
+	while let Some(next) = kind.upgrade() {
+		kind = next;
+		chain.push(kind);
+	}
+
+	// Assert
+	assert_eq!(chain, Vec::from_iter(SailKind::iter()));
+	assert!(chain.windows(2).all(|w| w[0].value() < w[1].value()));
+	assert!(chain.windows(2).all(|w| w[0].max_area() < w[1].max_area()));
+}
+
+#[test]
+fn gaff_sail_draws_when_pointing_into_the_wind() {
+	// Arrange
+	let sail_of = |kind| {
+		Sail {
+			kind,
+			orientation_triangle: 0.0,
+			..Default::default()
+		}
+	};
+	// Blowing along the sail
+	let wind = vec2(1.0, 0.0);
+
+	// Act
+	let bermuda = sail_of(SailKind::Bermuda).efficiency(wind);
+	let gaff = sail_of(SailKind::Gaff).efficiency(wind);
+
+	// Assert
+	assert_eq!(bermuda, 0.0);
+	assert_eq!(gaff, SailKind::Gaff.min_efficiency());
+}
+
+/// Sails with a fixed heading for the given number of ticks, returning the final ground speed
+fn sail_straight(init: &WorldInit, state: &mut WorldState, reefing: Reefing, ticks: u64) -> f32 {
+	let input = Input {
+		reefing,
+		rudder: BiPolarFraction(0),
+		net: false,
+	};
+	for _ in 0..ticks {
+		state.update(init, &input);
+	}
+	state.player.vehicle.ground_speed()
+}
+
+#[test]
+fn full_sail_accelerates_downwind() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	// Heading where the wind blows to
+	state.player.vehicle.heading = init.dbg.fixed_wind_direction.unwrap();
+	let mut furled = state.clone();
+	let full_reefing = state.player.vehicle.sail.kind.max_reefing();
+	let ticks = u64::from(TICKS_PER_SECOND);
+
+	// Act
+	let full_speed = sail_straight(&init, &mut state, full_reefing, ticks);
+	let furled_speed = sail_straight(&init, &mut furled, Reefing(0), ticks);
+
+	// Assert
+	assert!(furled_speed > 0.0);
+	assert!(full_speed > furled_speed);
+	assert!(
+		state
+			.player
+			.vehicle
+			.velocity
+			.dot(&state.player.vehicle.heading_vec())
+			> 0.0
+	);
+}
+
+#[test]
+fn ship_decelerates_head_to_wind() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	// Heading where the wind comes from
+	state.player.vehicle.heading = init.dbg.fixed_wind_direction.unwrap() + std::f32::consts::PI;
+	state.player.vehicle.velocity = state.player.vehicle.heading_vec() * 5.0;
+	let initial_speed = state.player.vehicle.ground_speed();
+
+	// Act
+	let speed = sail_straight(&init, &mut state, Reefing(0), u64::from(TICKS_PER_SECOND));
+
+	// Assert
+	assert!(speed < initial_speed);
+	assert!(state.player.vehicle.forward_speed() < initial_speed);
+}
+
+#[test]
+fn ship_reaches_terminal_speed_downwind() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.player.vehicle.heading = init.dbg.fixed_wind_direction.unwrap();
+	let full_reefing = state.player.vehicle.sail.kind.max_reefing();
+	let second = u64::from(TICKS_PER_SECOND);
+
+	// Act
+	let settled = sail_straight(&init, &mut state, full_reefing, 60 * second);
+	let later = sail_straight(&init, &mut state, full_reefing, 10 * second);
+
+	// Assert
+	assert!(settled > 0.0);
+	// The apparent wind vanishes when sailing as fast as the wind
+	assert!(later < MAX_WIND_SPEED);
+	assert!((later - settled).abs() < 0.01 * settled);
+}
+
+#[test]
+fn ship_collects_driftwood_from_the_water() {
+	// Arrange
+	let (mut init, mut state) = open_sea();
+	// The shore is at x = 36
+	*init.terrain.get_mut(TileCoord::new(9, 8)) = Elevation(0);
+	let mut driftwood = resource_at(Location::new(36.8, 32.0));
+	driftwood.content = ResourcePackContent::Driftwood;
+	driftwood.elevation = Elevation(0);
+	driftwood.origin = driftwood.loc;
+	state.resources.push(driftwood);
+	state.player.vehicle.pos = Location::new(35.0, 32.0);
+
+	// Act
+	state.update(&init, &Input::default());
+
+	// Assert
+	assert!(state.resources.is_empty());
+	assert_eq!(
+		state.player.vehicle.resource_weight,
+		ResourcePackContent::Driftwood.weight
+	);
+}
+
+#[test]
+fn throw_back_releases_the_cheapest_cargo() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	for kind in [
+		ResourcePackContent::Fish0,
+		ResourcePackContent::Grass0,
+		ResourcePackContent::Grass0,
+		ResourcePackContent::Grass0,
+	] {
+		state
+			.player
+			.vehicle
+			.load(&init.terrain_setting.resources, kind);
+	}
+
+	// Act
+	let event = state.throw_back_cargo(&init);
+
+	// Assert
+	let grass = ResourcePackContent::Grass0;
+	assert!(matches!(event, Some(Event::Splash(weight, _)) if weight == 2 * grass.weight));
+	assert_eq!(state.player.vehicle.cargo[grass], 1);
+	assert_eq!(state.player.vehicle.cargo[ResourcePackContent::Fish0], 1);
+	assert_eq!(
+		state.player.vehicle.resource_weight,
+		grass.weight + ResourcePackContent::Fish0.weight
+	);
+	assert_eq!(state.resources.len(), 2);
+	assert!(state.resources.iter().all(|r| r.content == grass));
+}
+
+#[test]
+fn thrown_back_cargo_is_not_collected_again() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.player.vehicle.load(
+		&init.terrain_setting.resources,
+		ResourcePackContent::Starfish0,
+	);
+	state.throw_back_cargo(&init);
+
+	// Act
+	for _ in 0..10 {
+		state.update(&init, &Input::default());
+	}
+
+	// Assert
+	assert_eq!(state.player.vehicle.resource_weight, 0);
+	assert_eq!(state.resources.len(), 1);
+	assert_eq!(state.throw_back_cargo(&init), None);
+}
+
+#[test]
+fn stronger_friction_slows_the_ship_faster() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.player.vehicle.velocity = vec2(3.0, 2.0);
+	let mut state_grippy = state.clone();
+	let mut init_grippy = init.clone();
+	init_grippy.physics.ground_friction *= 2.0;
+	init_grippy.physics.cross_friction *= 2.0;
+
+	// Act
+	for _ in 0..TICKS_PER_SECOND {
+		state.update(&init, &Input::default());
+		state_grippy.update(&init_grippy, &Input::default());
+	}
+
+	// Assert
+	let speed = state.player.vehicle.velocity.norm();
+	let speed_grippy = state_grippy.player.vehicle.velocity.norm();
+	assert!(speed_grippy < speed, "{speed_grippy} vs. {speed}");
+}
+
+#[test]
+fn shallow_water_slows_the_ship_faster() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.player.vehicle.velocity = vec2(3.0, 2.0);
+	let mut state_shallow = state.clone();
+	let mut init_shallow = init.clone();
+	for (_, elev) in init_shallow.terrain.iter_mut() {
+		*elev = TileType::ShallowWater.lowest();
+	}
+
+	// Act
+	for _ in 0..TICKS_PER_SECOND {
+		state.update(&init, &Input::default());
+		state_shallow.update(&init_shallow, &Input::default());
+	}
+
+	// Assert
+	let speed = state.player.vehicle.velocity.norm();
+	let speed_shallow = state_shallow.player.vehicle.velocity.norm();
+	assert!(speed_shallow < speed, "{speed_shallow} vs. {speed}");
+}
+
+#[test]
+fn ice_slows_the_ship_faster() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.player.vehicle.velocity = vec2(3.0, 2.0);
+	let mut state_icy = state.clone();
+	let mut init_icy = init.clone();
+	for tc in init.terrain.coords() {
+		init_icy.terrain.set_surface(tc, Surface::Ice);
+	}
+
+	// Act
+	for _ in 0..TICKS_PER_SECOND {
+		state.update(&init, &Input::default());
+		state_icy.update(&init_icy, &Input::default());
+	}
+
+	// Assert
+	let speed = state.player.vehicle.velocity.norm();
+	let speed_icy = state_icy.player.vehicle.velocity.norm();
+	assert!(speed_icy < speed, "{speed_icy} vs. {speed}");
+}
+
+#[test]
+fn grounding_damages_the_hull_and_slows_down_hard() {
+	// Arrange
+	let (mut init, mut state) = open_sea();
+	for (_, elev) in init.terrain.iter_mut() {
+		*elev = GROUNDING_ELEVATION;
+	}
+	state.player.vehicle.velocity = vec2(3.0, 2.0);
+	let initial_speed = state.player.vehicle.ground_speed();
+
+	// Act
+	let events: Vec<_> = (0..TICKS_PER_SECOND)
+		.flat_map(|_| state.update(&init, &Input::default()))
+		.collect();
+
+	// Assert
+	let vehicle = &state.player.vehicle;
+	assert!(vehicle.grounded);
+	assert!(vehicle.hull_integrity < 1.0);
+	assert!(vehicle.ground_speed() < 0.5 * initial_speed);
+	let groundings = events
+		.iter()
+		.filter(|ev| matches!(ev, Event::Grounding(_)))
+		.count();
+	assert_eq!(groundings, 1);
+}
+
+#[test]
+fn sailing_through_a_checkpoint_advances_the_race() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	let checkpoint = state.player.vehicle.pos + Distance(vec2(1.0, 0.0));
+	state.race = Some(Race::new(vec![checkpoint, Location::new(8.0, 8.0)], 1000));
+
+	// Act
+	let events = state.update(&init, &Input::default());
+
+	// Assert
+	assert!(events.contains(&Event::Checkpoint(0, checkpoint)));
+	let race = state.race.unwrap();
+	assert_eq!(race.next, 1);
+	assert_eq!(race.elapsed, 1);
+}
+
+#[test]
+fn auto_reefing_sets_full_sail_downwind() {
+	// Arrange
+	let (_, mut state) = open_sea();
+	let vehicle = &mut state.player.vehicle;
+	vehicle.sail.orientation_triangle = -FRAC_PI_2;
+
+	// Act
+	let reefing = vehicle.auto_reefing(Wind(vec2(5.0, 0.0)));
+
+	// Assert
+	assert_eq!(reefing, vehicle.sail.kind.max_reefing());
+}
+
+#[test]
+fn auto_reefing_strikes_the_sail_upwind() {
+	// Arrange
+	let (_, mut state) = open_sea();
+	let vehicle = &mut state.player.vehicle;
+	vehicle.sail.orientation_triangle = -FRAC_PI_2;
+
+	// Act
+	let reefing = vehicle.auto_reefing(Wind(vec2(-5.0, 0.0)));
+
+	// Assert
+	assert_eq!(reefing, Reefing(0));
+}
+
+#[test]
+fn full_sail_tears_in_a_strong_wind() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.player.vehicle.anchored = true;
+	let full_reefing = state.player.vehicle.sail.kind.max_reefing();
+	let second = u64::from(TICKS_PER_SECOND);
+
+	// Act
+	sail_straight(&init, &mut state, full_reefing, second);
+	let condition = state.player.vehicle.sail.condition;
+	sail_straight(&init, &mut state, full_reefing, 60 * second);
+
+	// Assert
+	assert!(condition < 1.0);
+	assert!(condition > SAIL_MIN_CONDITION);
+	assert_eq!(state.player.vehicle.sail.condition, SAIL_MIN_CONDITION);
+}
+
+#[test]
+fn struck_sail_withstands_a_strong_wind() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.player.vehicle.anchored = true;
+
+	// Act
+	sail_straight(
+		&init,
+		&mut state,
+		Reefing(0),
+		10 * u64::from(TICKS_PER_SECOND),
+	);
+
+	// Assert
+	assert_eq!(state.player.vehicle.sail.condition, 1.0);
+}
+
+#[test]
+fn damaged_hull_slows_down() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.player.vehicle.velocity = vec2(3.0, 0.0);
+	let mut state_damaged = state.clone();
+	state_damaged.player.vehicle.hull_integrity = 0.2;
+
+	// Act
+	for _ in 0..TICKS_PER_SECOND {
+		state.update(&init, &Input::default());
+		state_damaged.update(&init, &Input::default());
+	}
+
+	// Assert
+	let speed = state.player.vehicle.ground_speed();
+	let speed_damaged = state_damaged.player.vehicle.ground_speed();
+	assert!(speed_damaged < speed, "{speed_damaged} vs. {speed}");
+}
+
+#[test]
+fn damaged_hull_is_repaired_at_a_harbor() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state
+		.harbors
+		.push(Harbor::new(Location::new(36.0, 32.0), 0.0));
+	state.player.vehicle.hull_integrity = 0.5;
+	state.player.money = 10_000;
+
+	// Act
+	let mut trading = state.get_trading(&init).unwrap();
+	let price = trading.get_price_of_hull_repair().unwrap();
+	let res = trading.repair_hull();
+	let again = trading.repair_hull();
+
+	// Assert
+	assert!(res.is_ok());
+	assert!(matches!(again, Err(UpgradeError::Intact)));
+	assert!(price > 0);
+	assert_eq!(state.player.money, 10_000 - price);
+	assert_eq!(state.player.vehicle.hull_integrity, 1.0);
+}
+
+#[test]
+fn torn_sail_is_repaired_at_a_harbor() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state
+		.harbors
+		.push(Harbor::new(Location::new(36.0, 32.0), 0.0));
+	state.player.vehicle.sail.condition = 0.5;
+	state.player.money = 10_000;
+	let torn_area = state.player.vehicle.sail.sail_area();
+
+	// Act
+	let mut trading = state.get_trading(&init).unwrap();
+	let price = trading.get_price_of_sail_repair().unwrap();
+	let res = trading.repair_sail();
+	let again = trading.repair_sail();
+
+	// Assert
+	assert!(res.is_ok());
+	assert!(matches!(again, Err(UpgradeError::Intact)));
+	assert!(price > 0);
+	assert_eq!(state.player.money, 10_000 - price);
+	assert_eq!(state.player.vehicle.sail.condition, 1.0);
+	assert!(state.player.vehicle.sail.sail_area() > torn_area);
+}
+
+#[test]
+fn upgraded_sail_is_intact() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state
+		.harbors
+		.push(Harbor::new(Location::new(36.0, 32.0), 0.0));
+	state.player.vehicle.sail.condition = 0.5;
+	state.player.money = 10_000;
+
+	// Act
+	let mut trading = state.get_trading(&init).unwrap();
+	let res = trading.upgrade_sail();
+
+	// Assert
+	assert!(res.is_ok());
+	assert_eq!(state.player.vehicle.sail.kind, SailKind::Bermuda);
+	assert_eq!(state.player.vehicle.sail.condition, 1.0);
+}
+
+#[test]
+fn auto_reefing_spares_the_sail_in_a_strong_wind() {
+	// Arrange
+	let (_, mut state) = open_sea();
+	let vehicle = &mut state.player.vehicle;
+	vehicle.sail.orientation_triangle = -FRAC_PI_2;
+	let wind = Wind(vec2(MAX_WIND_SPEED, 0.0));
+
+	// Act
+	let reefing = vehicle.auto_reefing(wind);
+
+	// Assert
+	assert!(reefing < vehicle.sail.kind.max_reefing());
+	assert!(reefing > Reefing(0));
+	let sail = Sail {
+		reefing,
+		..vehicle.sail
+	};
+	assert!(sail.strain(vehicle.apparent_wind(wind)) <= SAIL_STRAIN_LIMIT);
+}
+
+#[test]
+fn docking_depends_on_the_harbor_size() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	let mut hub = Harbor::new(Location::new(42.0, 32.0), 0.0);
+	hub.effect_size = 12.0;
+	hub.docking_speed = 2.0;
+	let jetty = Harbor::new(Location::new(32.0, 42.0), 0.0);
+	state.player.vehicle.velocity = vec2(0.0, 1.5);
+	let mut at_jetty = state.clone();
+	state.harbors.push(hub);
+	at_jetty.harbors.push(jetty);
+
+	// Act
+	state.update(&init, &Input::default());
+	at_jetty.update(&init, &Input::default());
+
+	// Assert
+	// The hub reaches out far enough, and docks even a faster ship
+	assert_eq!(state.player.vehicle.velocity, vec2(0.0, 0.0));
+	assert!(state.get_trading(&init).is_some());
+	// The jetty is out of reach
+	assert!(at_jetty.player.vehicle.velocity.norm() > 0.0);
+	assert!(at_jetty.get_trading(&init).is_none());
+}
+
+#[test]
+fn pressing_against_land_collides_once() {
+	// Arrange
+	let (mut init, mut state) = open_sea();
+	// A wall from 36 m onwards along x
+	for y in 0..init.terrain.edge_length {
+		*init.terrain.get_mut(TileCoord::new(9, y)) = Elevation(1);
+	}
+	state.player.vehicle.pos = Location::new(35.5, 20.0);
+	state.player.vehicle.heading = 0.0;
+	// Keeps pushing the ship into the wall
+	let press = |state: &mut WorldState, ticks: u16| {
+		(0..ticks)
+			.flat_map(|_| {
+				state.player.vehicle.velocity = vec2(3.0, 0.0);
+				state.update(&init, &Input::default())
+			})
+			.filter(|ev| matches!(ev, Event::TileCollision(_, _)))
+			.count()
+	};
+
+	// Act
+	let pressed = press(&mut state, 2 * TICKS_PER_SECOND);
+	// Get clear of the wall for a while, and hit it again
+	state.player.vehicle.pos = Location::new(30.0, 20.0);
+	for _ in 0..COLLISION_COOLDOWN_TICKS {
+		state.player.vehicle.velocity = vec2(0.0, 0.0);
+		state.update(&init, &Input::default());
+	}
+	state.player.vehicle.pos = Location::new(35.5, 20.0);
+	let again = press(&mut state, TICKS_PER_SECOND);
+
+	// Assert
+	assert_eq!(pressed, 1);
+	assert_eq!(again, 1);
+	assert!(state.player.vehicle.pos.0.x < 36.0);
+}
+
+#[test]
+fn extreme_motion_keeps_the_ship_finite() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	let mut nan = state.clone();
+	// Rushing through the water, thus facing an extreme apparent wind
+	state.player.vehicle.velocity = vec2(1e30, -1e30);
+	nan.player.vehicle.velocity = vec2(f32::NAN, 0.0);
+	let full_reefing = state.player.vehicle.sail.kind.max_reefing();
+	let second = u64::from(TICKS_PER_SECOND);
+
+	// Act
+	sail_straight(&init, &mut state, full_reefing, second);
+	sail_straight(&init, &mut nan, full_reefing, second);
+
+	// Assert
+	for vehicle in [&state.player.vehicle, &nan.player.vehicle] {
+		assert!(
+			vehicle.velocity.iter().all(|c| c.is_finite()),
+			"{vehicle:?}"
+		);
+		assert!(vehicle.pos.0.iter().all(|c| c.is_finite()), "{vehicle:?}");
+		assert!(vehicle.ground_speed() <= VEHICLE_MAX_SPEED);
+	}
+}
+
+#[test]
+fn magnet_pulls_nearby_fish_gently() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.player.vehicle.magnet = Magnet::Lodestone;
+	state.player.vehicle.anchored = true;
+	let ship = state.player.vehicle.pos;
+	let reach = Magnet::Lodestone.radius();
+	state
+		.resources
+		.push(resource_at(ship + Distance::new(reach - 2.0, 0.0)));
+	state
+		.resources
+		.push(resource_at(ship + Distance::new(0.0, reach + 2.0)));
+	let origin_dist = |state: &WorldState, idx: usize| {
+		init.terrain
+			.torus_distance(state.resources[idx].origin, state.player.vehicle.pos)
+			.magnitude()
+	};
+	let near_before = origin_dist(&state, 0);
+	let far_before = state.resources[1].origin;
+	let input = Input {
+		reefing: Reefing(0),
+		..Default::default()
+	};
+
+	// Act
+	for _ in 0..TICKS_PER_SECOND {
+		state.update(&init, &input);
+	}
+
+	// Assert
+	let near_after = origin_dist(&state, 0);
+	assert_eq!(state.resources.len(), 2, "the magnet must not collect");
+	assert!(near_after < near_before - MAGNET_PULL_SPEED * 0.5);
+	assert!(near_after > near_before - MAGNET_PULL_SPEED * 1.5);
+	assert_eq!(state.resources[1].origin, far_before);
+}
+
+#[test]
+fn magnet_is_bought_at_a_harbor() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state
+		.harbors
+		.push(Harbor::new(Location::new(36.0, 32.0), 0.0));
+	state.player.money = Magnet::Lodestone.value();
+
+	// Act
+	let mut trading = state.get_trading(&init).unwrap();
+	let price = trading.get_price_of_magnet_upgrade();
+	let res = trading.upgrade_magnet();
+	let again = trading.upgrade_magnet();
+
+	// Assert
+	assert_eq!(price, Some(Magnet::Lodestone.value()));
+	assert!(res.is_ok());
+	assert!(matches!(again, Err(UpgradeError::InsufficientFunds)));
+	assert_eq!(state.player.money, 0);
+	assert_eq!(state.player.vehicle.magnet, Magnet::Lodestone);
+}
+
+#[test]
+fn catch_spoils_but_keeps_a_share_of_its_value() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state.player.vehicle.anchored = true;
+	for _ in 0..10 {
+		state
+			.player
+			.vehicle
+			.load(&init.terrain_setting.resources, ResourcePackContent::Fish0);
+	}
+	let fresh = state.player.vehicle.current_value();
+	let floor = (state.player.vehicle.resource_value as f32 * FISH_MIN_FRESHNESS).floor() as u64;
+	let wait = |state: &mut WorldState, seconds: u64| {
+		for _ in 0..seconds * u64::from(TICKS_PER_SECOND) {
+			state.update(&init, &Input::default());
+		}
+		state.player.vehicle.current_value()
+	};
+
+	// Act
+	let soon = wait(&mut state, 10);
+	let later = wait(&mut state, 10);
+	let rotten = wait(&mut state, 1000);
+
+	// Assert
+	assert_eq!(fresh, state.player.vehicle.resource_value);
+	assert!(soon < fresh, "{soon} < {fresh}");
+	assert!(later < soon, "{later} < {soon}");
+	assert_eq!(rotten, floor);
+	assert_eq!(state.player.vehicle.freshness, FISH_MIN_FRESHNESS);
+}
+
+#[test]
+fn fresh_catch_freshens_up_the_hold() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	let table = &init.terrain_setting.resources;
+	let vehicle = &mut state.player.vehicle;
+	vehicle.load(table, ResourcePackContent::Fish0);
+	vehicle.freshness = FISH_MIN_FRESHNESS;
+
+	// Act
+	vehicle.load(table, ResourcePackContent::Fish0);
+
+	// Assert
+	let expected = (FISH_MIN_FRESHNESS + 1.0) / 2.0;
+	assert!((vehicle.freshness - expected).abs() < 1e-6);
+}
+
+#[test]
+fn spoiled_catch_sells_for_less() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state
+		.harbors
+		.push(Harbor::new(Location::new(36.0, 32.0), 0.0));
+	let vehicle = &mut state.player.vehicle;
+	for _ in 0..10 {
+		vehicle.load(&init.terrain_setting.resources, ResourcePackContent::Fish0);
+	}
+	vehicle.freshness = FISH_MIN_FRESHNESS;
+	let spoiled = vehicle.current_value();
+
+	// Act
+	let mut trading = state.get_trading(&init).unwrap();
+	let price = trading.get_price_for_fish();
+	let amount = trading.players_fish_amount();
+	trading.sell_fish(amount);
+
+	// Assert
+	assert_eq!(state.player.money, spoiled * price);
+}
+
+#[test]
+fn selling_in_steps_earns_the_shown_value() {
+	// Arrange
+	let (init, mut state) = open_sea();
+	state
+		.harbors
+		.push(Harbor::new(Location::new(36.0, 32.0), 0.0));
+	let vehicle = &mut state.player.vehicle;
+	for _ in 0..7 {
+		vehicle.load(&init.terrain_setting.resources, ResourcePackContent::Fish0);
+		// Packs worth only a little
+		vehicle.load(&init.terrain_setting.resources, ResourcePackContent::Shoe0);
+	}
+	vehicle.freshness = 0.9;
+	let shown = vehicle.current_value();
+
+	// Act
+	let mut trading = state.get_trading(&init).unwrap();
+	let price = trading.get_price_for_fish();
+	while trading.players_fish_amount() > 0 {
+		trading.sell_fish(10);
+	}
+
+	// Assert
+	assert_eq!(state.player.money, shown * price);
+}
@@ -1,14 +1,22 @@
+use std::collections::VecDeque;
+
 use enum_map::Enum;
+use enum_map::EnumMap;
 use nalgebra_glm::Vec2;
 use rand::Rng;
 use serde::Deserialize;
 use serde::Serialize;
+use strum::IntoEnumIterator;
 
 use crate::units::Distance;
 use crate::units::Elevation;
 use crate::units::Location;
+use crate::units::TileType;
 use crate::TILE_SIZE;
 
+#[cfg(test)]
+mod test;
+
 
 
 /// The coordinates of a tile of the map, given by its tile axial indices
@@ -210,6 +218,78 @@ fn coord(edge_len: u16, index: usize) -> TileCoord {
 	TileCoord::new(x, y)
 }
 
+/// Gives the shortest signed distance along a single axis of a torus of the given `size`.
+///
+/// The `distance` is expected to be the difference of two coordinates
+/// on the torus, i.e. within `(-size, size)`.
+///
+/// If both ways around are equally long (i.e. the coordinates are exactly
+/// half the size apart), the original direction is kept, so that the
+/// result is consistent for both axes.
+fn torus_axis_distance(distance: f32, size: f32) -> f32 {
+	let half_size = size / 2.;
+
+	if distance > half_size {
+		distance - size
+	} else if distance < -half_size {
+		distance + size
+	} else {
+		distance
+	}
+}
+
+/// The colors of the tile types, e.g. for maps
+///
+/// By default, every tile type has its [TileType::color].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize)]
+pub struct TerrainPalette(EnumMap<TileType, [u8; 4]>);
+
+impl TerrainPalette {
+	/// Gives the RGBA color of the tile type
+	pub fn color(&self, tile: TileType) -> [u8; 4] {
+		self.0[tile]
+	}
+
+	/// Overrides the RGBA color of the tile type
+	pub fn set_color(&mut self, tile: TileType, color: [u8; 4]) {
+		self.0[tile] = color;
+	}
+}
+
+impl Default for TerrainPalette {
+	fn default() -> Self {
+		Self(EnumMap::from_fn(TileType::color))
+	}
+}
+
+/// The kind of surface of a tile, laid over its elevation
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize)]
+pub enum Surface {
+	/// The plain tile, just as its elevation makes it
+	Open,
+	/// Frozen water, still passable, but dragging much more
+	Ice,
+}
+// TODO: use the `#[default]` attribute one day instead
+impl Default for Surface {
+	fn default() -> Self {
+		Self::Open
+	}
+}
+impl Surface {
+	/// The factor of the friction of a ship on this surface
+	///
+	/// It applies on top of the [TileType::drag_factor] of the tile.
+	pub const fn drag_factor(self) -> f32 {
+		match self {
+			Self::Open => 1.0,
+			Self::Ice => crate::ICE_DRAG_FACTOR,
+		}
+	}
+}
+
 /// The terrain of the world.
 ///
 /// The terrain is a square with `edge_length` tiles along each axis.
@@ -229,6 +309,13 @@ pub struct Terrain {
 	/// Only use this to iterate over this if you need just the terrain types.
 	/// Prefer using [get](Self::get) and [get_mut](Self::get_mut)
 	pub playground: Vec<Elevation>,
+
+	/// The surface of the tiles, laid over the `playground`
+	///
+	/// This `Vec` is either empty, if all tiles are [Surface::Open], or it
+	/// has exactly as many elements as the `playground`. Prefer using
+	/// [surface](Self::surface) and [set_surface](Self::set_surface).
+	pub surface: Vec<Surface>,
 }
 impl Terrain {
 	/// Creates a new "flat" terrain with given edge length in tiles
@@ -239,6 +326,7 @@ impl Terrain {
 		Self {
 			edge_length,
 			playground,
+			surface: Vec::new(),
 		}
 	}
 
@@ -297,6 +385,68 @@ impl Terrain {
 		&self.playground[idx]
 	}
 
+	/// Gets the elevation of the tile at the given location
+	///
+	/// The location is mapped onto the torus first, so locations on or beyond
+	/// the map edge get the elevation of the wrapped around tile.
+	pub fn elevation_at(&self, loc: Location) -> Elevation {
+		let tc = self
+			.map_loc_on_torus(loc)
+			.try_into()
+			.expect("Location on the torus is out of bounds");
+		*self.get(tc)
+	}
+
+	/// Gets the surface of the tile at given coordinate
+	pub fn surface(&self, tc: TileCoord) -> Surface {
+		self.surface
+			.get(self.index(tc))
+			.copied()
+			.unwrap_or_default()
+	}
+
+	/// Sets the surface of the tile at given coordinate
+	pub fn set_surface(&mut self, tc: TileCoord, surface: Surface) {
+		if self.surface.is_empty() {
+			if surface == Surface::Open {
+				return;
+			}
+			self.surface = vec![Surface::Open; self.playground.len()];
+		}
+
+		let idx = self.index(tc);
+		self.surface[idx] = surface;
+	}
+
+	/// Rasterizes the tile types into an RGBA image of the given size
+	///
+	/// The pixels are given row by row, starting at the top left, each as four
+	/// bytes colored by [TileType::color]. Every pixel gets the color of the
+	/// tile below its center.
+	pub fn to_rgba(&self, width: u32, height: u32) -> Vec<u8> {
+		self.to_rgba_with(width, height, &TerrainPalette::default())
+	}
+
+	/// Rasterizes the tile types like [Self::to_rgba], but colored by the `palette`
+	pub fn to_rgba_with(&self, width: u32, height: u32, palette: &TerrainPalette) -> Vec<u8> {
+		let edge_length = u64::from(self.edge_length);
+		let tile_of = |pixel: u32, size: u32| {
+			// Rounding down gives the tile below the pixel center
+			let tile = (2 * u64::from(pixel) + 1) * edge_length / (2 * u64::from(size));
+			tile as u16
+		};
+
+		let mut rgba = Vec::with_capacity(width as usize * height as usize * 4);
+		for y in 0..height {
+			let tile_y = tile_of(y, height);
+			for x in 0..width {
+				let tc = TileCoord::new(tile_of(x, width), tile_y);
+				rgba.extend(palette.color(self.get(tc).classify()));
+			}
+		}
+		rgba
+	}
+
 	/// Gets mutably the tile type at given coordinate
 	pub fn get_mut(&mut self, tc: TileCoord) -> &mut Elevation {
 		let idx = self.index(tc);
@@ -320,6 +470,7 @@ impl Terrain {
 		Self {
 			edge_length,
 			playground: vec,
+			surface: Vec::new(),
 		}
 	}
 
@@ -365,6 +516,70 @@ impl Terrain {
 		))
 	}
 
+	/// Groups all passable tiles into connected regions of water
+	///
+	/// Tiles are connected to their four direct neighbors, including those
+	/// across the map edge. The regions are ordered by their first tile, going
+	/// row by row.
+	pub fn water_regions(&self) -> Vec<Vec<TileCoord>> {
+		let mut visited = vec![false; self.playground.len()];
+		let mut regions = Vec::new();
+
+		for (start, elevation) in self.iter() {
+			if visited[self.index(start)] || !elevation.is_passable() {
+				continue;
+			}
+
+			// Flood fill the region of the start tile
+			let mut region = Vec::new();
+			let mut pending = vec![start];
+			visited[self.index(start)] = true;
+			while let Some(tc) = pending.pop() {
+				region.push(tc);
+
+				for dir in TileDirection::iter() {
+					let neighbor = self.tile_in_direction(dir, tc);
+					let idx = self.index(neighbor);
+					if !visited[idx] && self.playground[idx].is_passable() {
+						visited[idx] = true;
+						pending.push(neighbor);
+					}
+				}
+			}
+			regions.push(region);
+		}
+
+		regions
+	}
+
+	/// Finds the passable tile closest to the given tile, if there is any
+	///
+	/// The distance is counted in steps to the four direct neighbors, thus a
+	/// passable tile is closest to itself.
+	pub fn nearest_passable(&self, start: TileCoord) -> Option<TileCoord> {
+		let mut visited = vec![false; self.playground.len()];
+		let mut pending = VecDeque::from([start]);
+		visited[self.index(start)] = true;
+
+		// Breadth first, so the first passable tile is the nearest one
+		while let Some(tc) = pending.pop_front() {
+			if self.get(tc).is_passable() {
+				return Some(tc);
+			}
+
+			for dir in TileDirection::iter() {
+				let neighbor = self.tile_in_direction(dir, tc);
+				let idx = self.index(neighbor);
+				if !visited[idx] {
+					visited[idx] = true;
+					pending.push_back(neighbor);
+				}
+			}
+		}
+
+		None
+	}
+
 	/// Returns an radom location within the map that is on a passable tile
 	pub fn random_passable_location<R: Rng>(&self, mut rng: R) -> Location {
 		// Just use rejection sampling
@@ -405,19 +620,66 @@ impl Terrain {
 
 		let mut distance = to - from;
 
-		let half_size = self.map_size() / 2.;
-		if distance.0.x.abs() > half_size {
-			let s = distance.0.x.signum();
-			distance.0.x = (self.map_size() - distance.0.x.abs()) * s * -1.;
-		}
-		if distance.0.y.abs() > half_size {
-			let s = distance.0.y.signum();
-			distance.0.y = (self.map_size() - distance.0.y.abs()) * s * -1.;
-		}
+		distance.0.x = torus_axis_distance(distance.0.x, self.map_size());
+		distance.0.y = torus_axis_distance(distance.0.y, self.map_size());
 
 		distance
 	}
 
+	/// Follows the straight path from `start` by `distance` and returns the first impassable tile on it.
+	///
+	/// The path is sampled in steps of at most a quarter tile, so that no
+	/// tile is skipped, even for long paths. The returned pair consists of
+	/// the last tile before the obstacle and the impassable tile itself.
+	///
+	/// Returns `None` if the entire path is passable.
+	pub fn first_impassable_on_path(
+		&self,
+		start: Location,
+		distance: Distance,
+	) -> Option<(TileCoord, TileCoord)> {
+		const MAX_STEP: f32 = TILE_SIZE as f32 / 4.;
+
+		let steps = (distance.magnitude() / MAX_STEP).ceil().max(1.0) as u32;
+
+		let mut last_tile = TileCoord::try_from(self.map_loc_on_torus(start)).ok()?;
+		for i in 1..=steps {
+			let loc = self.map_loc_on_torus(start + distance * (i as f32 / steps as f32));
+			let tile = TileCoord::try_from(loc).ok()?;
+
+			if tile != last_tile {
+				if Some(true) != self.try_get(tile).map(|t| t.is_passable()) {
+					return Some((last_tile, tile));
+				}
+				last_tile = tile;
+			}
+		}
+
+		None
+	}
+
+	/// Returns the shortest distance from `point` to the line segment from `start` to `end` on a torus.
+	///
+	/// The segment is assumed to be the shortest path between `start` and `end`.
+	pub fn torus_distance_to_segment(
+		&self,
+		start: Location,
+		end: Location,
+		point: Location,
+	) -> f32 {
+		let segment = self.torus_distance(start, end).0;
+		let to_point = self.torus_distance(start, point).0;
+
+		let segment_len_sq = segment.norm_squared();
+		let t = if segment_len_sq > 0.0 {
+			(to_point.dot(&segment) / segment_len_sq).clamp(0.0, 1.0)
+		} else {
+			0.0
+		};
+
+		(to_point - segment * t).norm()
+	}
+
 	/// Returns wether `x` lies between `min` and `max` on a Torus world.
 	///
 	/// This check is a conventional AABB check if `min` <= `max` (for each
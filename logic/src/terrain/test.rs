@@ -0,0 +1,362 @@
+use proptest::prelude::*;
+
+use super::Surface;
+use super::Terrain;
+use super::TerrainPalette;
+use super::TileCoord;
+use crate::units::Distance;
+use crate::units::Elevation;
+use crate::units::Location;
+use crate::units::TileType;
+
+/// A terrain of 32 m edge length
+fn terrain() -> Terrain {
+	Terrain::new(8)
+}
+
+const fn l(x: f32, y: f32) -> Location {
+	Location::new(x, y)
+}
+
+fn assert_distance(actual: Distance, expected: Distance) {
+	assert!(
+		(actual - expected).magnitude() < f32::EPSILON,
+		"expected {expected:?}, got {actual:?}"
+	);
+}
+
+#[test]
+fn torus_distance_zero() {
+	// Arrange
+	let terrain = terrain();
+
+	// Act
+	let actual = terrain.torus_distance(l(5.0, 7.0), l(5.0, 7.0));
+
+	// Assert
+	assert_distance(actual, Distance::new(0.0, 0.0));
+}
+
+#[test]
+fn torus_distance_zero_across_edge() {
+	// Arrange
+	let terrain = terrain();
+
+	// Act
+	let actual = terrain.torus_distance(l(0.0, 0.0), l(32.0, 32.0));
+
+	// Assert
+	assert_distance(actual, Distance::new(0.0, 0.0));
+}
+
+#[test]
+fn torus_distance_half_x() {
+	// Arrange
+	let terrain = terrain();
+
+	// Act
+	let forward = terrain.torus_distance(l(0.0, 0.0), l(16.0, 0.0));
+	let backward = terrain.torus_distance(l(16.0, 0.0), l(0.0, 0.0));
+
+	// Assert
+	assert_distance(forward, Distance::new(16.0, 0.0));
+	assert_distance(backward, Distance::new(-16.0, 0.0));
+}
+
+#[test]
+fn torus_distance_half_y() {
+	// Arrange
+	let terrain = terrain();
+
+	// Act
+	let forward = terrain.torus_distance(l(0.0, 0.0), l(0.0, 16.0));
+	let backward = terrain.torus_distance(l(0.0, 16.0), l(0.0, 0.0));
+
+	// Assert
+	assert_distance(forward, Distance::new(0.0, 16.0));
+	assert_distance(backward, Distance::new(0.0, -16.0));
+}
+
+#[test]
+fn torus_distance_half_both_axes() {
+	// Arrange
+	let terrain = terrain();
+
+	// Act
+	let actual = terrain.torus_distance(l(4.0, 4.0), l(20.0, 20.0));
+
+	// Assert
+	assert_distance(actual, Distance::new(16.0, 16.0));
+}
+
+#[test]
+fn torus_distance_beyond_half_wraps() {
+	// Arrange
+	let terrain = terrain();
+
+	// Act
+	let actual = terrain.torus_distance(l(0.0, 0.0), l(17.0, 31.0));
+
+	// Assert
+	assert_distance(actual, Distance::new(-15.0, -1.0));
+}
+
+
+/// A terrain all land, except for the given water tiles
+fn island_with_water(edge_length: u16, water: &[(u16, u16)]) -> Terrain {
+	let mut terrain = Terrain::new(edge_length);
+	for (_, elevation) in terrain.iter_mut() {
+		*elevation = Elevation::HIGHEST;
+	}
+	for &tc in water {
+		*terrain.get_mut(tc.into()) = Elevation::DEEPEST;
+	}
+	terrain
+}
+
+#[test]
+fn two_lakes_are_two_water_regions() {
+	// Arrange
+	let terrain = island_with_water(6, &[(1, 1), (2, 1), (2, 2), (4, 4)]);
+
+	// Act
+	let mut regions = terrain.water_regions();
+
+	// Assert
+	assert_eq!(regions.len(), 2);
+	regions[0].sort_by_key(|tc| (tc.y, tc.x));
+	assert_eq!(
+		regions[0],
+		[
+			TileCoord::new(1, 1),
+			TileCoord::new(2, 1),
+			TileCoord::new(2, 2)
+		]
+	);
+	assert_eq!(regions[1], [TileCoord::new(4, 4)]);
+}
+
+#[test]
+fn water_regions_connect_across_the_edge() {
+	// Arrange
+	let terrain = island_with_water(6, &[(0, 2), (5, 2), (3, 0), (3, 5)]);
+
+	// Act
+	let regions = terrain.water_regions();
+
+	// Assert
+	assert_eq!(regions.len(), 2);
+	assert!(regions.iter().all(|region| region.len() == 2));
+}
+
+#[test]
+fn elevation_at_tile_boundaries() {
+	// Arrange
+	let mut terrain = terrain();
+	*terrain.get_mut(TileCoord::new(0, 0)) = Elevation::DEEPEST;
+	*terrain.get_mut(TileCoord::new(1, 0)) = Elevation::COAST;
+
+	// Act & Assert
+	assert_eq!(terrain.elevation_at(l(0.0, 0.0)), Elevation::DEEPEST);
+	assert_eq!(terrain.elevation_at(l(4.0, 0.0)), Elevation::COAST);
+	assert_eq!(terrain.elevation_at(l(MAP_SIZE, 0.0)), Elevation::DEEPEST);
+	assert_eq!(
+		terrain.elevation_at(l(-4.0, MAP_SIZE)),
+		Elevation::default()
+	);
+	assert_eq!(
+		terrain.elevation_at(l(MAP_SIZE + 4.0, 0.0)),
+		Elevation::COAST
+	);
+}
+
+#[test]
+fn to_rgba_has_four_bytes_per_pixel() {
+	// Arrange
+	let terrain = terrain();
+
+	// Act & Assert
+	assert_eq!(terrain.to_rgba(100, 30).len(), 100 * 30 * 4);
+	assert_eq!(terrain.to_rgba(3, 5).len(), 3 * 5 * 4);
+	assert!(terrain.to_rgba(0, 10).is_empty());
+}
+
+#[test]
+fn to_rgba_colors_the_tile_below_each_pixel() {
+	// Arrange
+	let mut terrain = island_with_water(8, &[]);
+	*terrain.get_mut(TileCoord::new(2, 1)) = Elevation::DEEPEST;
+
+	// Act
+	let rgba = terrain.to_rgba(16, 16);
+
+	// Assert
+	let pixel = |x: usize, y: usize| &rgba[(y * 16 + x) * 4..][..4];
+	let water = TileType::DeepWater.color();
+	let grass = TileType::Grass.color();
+	for (x, y) in [(4, 2), (5, 2), (4, 3), (5, 3)] {
+		assert_eq!(pixel(x, y), water);
+	}
+	for (x, y) in [(0, 0), (3, 2), (6, 2), (4, 1), (4, 4), (15, 15)] {
+		assert_eq!(pixel(x, y), grass);
+	}
+}
+
+#[test]
+fn to_rgba_with_uses_the_palette() {
+	// Arrange
+	let terrain = island_with_water(4, &[(1, 1)]);
+	let mut palette = TerrainPalette::default();
+	palette.set_color(TileType::DeepWater, [1, 2, 3, 4]);
+
+	// Act
+	let rgba = terrain.to_rgba_with(4, 4, &palette);
+
+	// Assert
+	assert_eq!(&rgba[(4 + 1) * 4..][..4], [1, 2, 3, 4]);
+	assert_eq!(&rgba[..4], TileType::Grass.color());
+}
+
+#[test]
+fn nearest_passable_tile() {
+	// Arrange
+	let terrain = island_with_water(6, &[(1, 1), (4, 1)]);
+
+	// Act & Assert
+	assert_eq!(
+		terrain.nearest_passable(TileCoord::new(1, 1)),
+		Some(TileCoord::new(1, 1))
+	);
+	assert_eq!(
+		terrain.nearest_passable(TileCoord::new(1, 3)),
+		Some(TileCoord::new(1, 1))
+	);
+	assert_eq!(
+		terrain.nearest_passable(TileCoord::new(5, 1)),
+		Some(TileCoord::new(4, 1))
+	);
+	// Across the map edge
+	assert_eq!(
+		island_with_water(6, &[(5, 3)]).nearest_passable(TileCoord::new(0, 3)),
+		Some(TileCoord::new(5, 3))
+	);
+	assert_eq!(
+		island_with_water(4, &[]).nearest_passable(TileCoord::new(0, 0)),
+		None
+	);
+}
+
+
+/// The edge length in meter of the terrain from `terrain()`
+const MAP_SIZE: f32 = 32.0;
+
+/// Arbitrary finite coordinates, with emphasis on the interesting ranges
+fn coordinate() -> impl Strategy<Value = f32> {
+	use prop::num::f32::NEGATIVE;
+	use prop::num::f32::NORMAL;
+	use prop::num::f32::POSITIVE;
+	use prop::num::f32::SUBNORMAL;
+	use prop::num::f32::ZERO;
+
+	prop_oneof![
+		// Anything finite, including huge and tiny values
+		POSITIVE | NEGATIVE | NORMAL | SUBNORMAL | ZERO,
+		// Values within a few maps
+		-4.0 * MAP_SIZE..4.0 * MAP_SIZE,
+		// Values just around the map boundaries
+		(-4_i32..=4, -1e-3_f32..1e-3).prop_map(|(k, e)| k as f32 * MAP_SIZE + e),
+	]
+}
+
+fn location() -> impl Strategy<Value = Location> {
+	(coordinate(), coordinate()).prop_map(|(x, y)| l(x, y))
+}
+
+proptest! {
+	#[test]
+	fn map_loc_on_torus_is_in_bounds(loc in location()) {
+		// Arrange
+		let terrain = terrain();
+
+		// Act
+		let mapped = terrain.map_loc_on_torus(loc);
+
+		// Assert
+		prop_assert!((0.0..MAP_SIZE).contains(&mapped.0.x), "{loc:?} mapped to {mapped:?}");
+		prop_assert!((0.0..MAP_SIZE).contains(&mapped.0.y), "{loc:?} mapped to {mapped:?}");
+	}
+
+	#[test]
+	fn map_loc_on_torus_is_idempotent(loc in location()) {
+		// Arrange
+		let terrain = terrain();
+
+		// Act
+		let once = terrain.map_loc_on_torus(loc);
+		let twice = terrain.map_loc_on_torus(once);
+
+		// Assert
+		prop_assert_eq!(once, twice);
+	}
+}
+
+proptest! {
+	#[test]
+	fn torus_distance_is_symmetric(a in location(), b in location()) {
+		// Arrange
+		let terrain = terrain();
+
+		// Act
+		let forward = terrain.torus_distance(a, b);
+		let backward = terrain.torus_distance(b, a);
+
+		// Assert
+		prop_assert_eq!(forward.magnitude(), backward.magnitude());
+	}
+
+	#[test]
+	fn torus_distance_is_at_most_half_the_diagonal(a in location(), b in location()) {
+		// Arrange
+		let terrain = terrain();
+
+		// Act
+		let distance = terrain.torus_distance(a, b);
+
+		// Assert
+		let half_diagonal = MAP_SIZE * std::f32::consts::SQRT_2 / 2.;
+		prop_assert!(distance.magnitude() <= half_diagonal + 1e-4, "{distance:?}");
+	}
+
+	#[test]
+	fn torus_distance_leads_to_the_target(a in location(), b in location()) {
+		// Arrange
+		let terrain = terrain();
+		// Start from the mapped location, huge coordinates would swallow the distance
+		let start = terrain.map_loc_on_torus(a);
+
+		// Act
+		let arrival = start + terrain.torus_distance(start, b);
+
+		// Assert
+		let miss = terrain.torus_distance(arrival, b);
+		prop_assert!(miss.magnitude() < 1e-4, "{arrival:?} misses {b:?} by {miss:?}");
+	}
+}
+
+#[test]
+fn surface_overlay_is_only_allocated_when_needed() {
+	// Arrange
+	let mut terrain = terrain();
+	let tile = TileCoord::new(2, 5);
+
+	// Act
+	terrain.set_surface(tile, Surface::Open);
+	let open_len = terrain.surface.len();
+	terrain.set_surface(tile, Surface::Ice);
+
+	// Assert
+	assert_eq!(open_len, 0);
+	assert_eq!(terrain.surface.len(), terrain.playground.len());
+	assert_eq!(terrain.surface(tile), Surface::Ice);
+	assert_eq!(terrain.surface(TileCoord::new(5, 2)), Surface::Open);
+}
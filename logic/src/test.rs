@@ -0,0 +1,30 @@
+use rand::Rng;
+
+use super::seeded_rng;
+
+/// The first few outputs of the given generator
+fn first_outputs(mut rng: impl Rng) -> [u64; 4] {
+	[rng.gen(), rng.gen(), rng.gen(), rng.gen()]
+}
+
+#[test]
+fn seeded_rng_is_deterministic() {
+	// Act
+	let first = first_outputs(seeded_rng(42, 7));
+	let second = first_outputs(seeded_rng(42, 7));
+
+	// Assert
+	assert_eq!(first, second);
+}
+
+#[test]
+fn seeded_rng_differs_by_seed_and_stream() {
+	// Act
+	let base = first_outputs(seeded_rng(42, 7));
+	let other_seed = first_outputs(seeded_rng(43, 7));
+	let other_stream = first_outputs(seeded_rng(42, 8));
+
+	// Assert
+	assert_ne!(base, other_seed);
+	assert_ne!(base, other_stream);
+}
@@ -0,0 +1,287 @@
+//! Recorded paths of ships, e.g. to race against a previous run
+//!
+//! For sharing with other players, a run is exported as a ghost file, which
+//! consists of [GHOST_MAGIC], followed by the format version as little-endian
+//! `u16`, followed by the `bincode` encoded [Ghost]. Its header is the
+//! [RecordedWorld] and the [Score] of the run, followed by the samples.
+//!
+
+use std::fmt;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::state::normalize_angle_rel;
+use crate::state::Vehicle;
+use crate::terrain::Terrain;
+use crate::units::Location;
+use crate::units::Tick;
+
+#[cfg(test)]
+mod test;
+
+
+
+/// The first bytes of every ghost file
+pub const GHOST_MAGIC: [u8; 4] = *b"PoFG";
+
+/// The version of the ghost file format written by this build
+///
+/// Increment it with every change of the layout of [Ghost].
+pub const GHOST_VERSION: u16 = 2;
+
+
+/// Represents the reason for the failure of importing a ghost file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GhostError {
+	/// The file does not start with [GHOST_MAGIC]
+	NotAGhost,
+	/// The file was exported in another format version than [GHOST_VERSION]
+	UnsupportedVersion(u16),
+	/// The content could not be decoded
+	Corrupted(String),
+	/// The ghost was recorded in another world than the given one
+	OtherWorld(RecordedWorld),
+}
+impl fmt::Display for GhostError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NotAGhost => write!(f, "Not a ghost file"),
+			Self::UnsupportedVersion(version) => {
+				write!(
+					f,
+					"The ghost file has the version {version}, but only version {GHOST_VERSION} \
+					 is supported"
+				)
+			},
+			Self::Corrupted(err) => write!(f, "The ghost file is corrupted: {err}"),
+			Self::OtherWorld(world) => {
+				write!(
+					f,
+					"The ghost has been recorded in another world, use the seed {}, the map size \
+					 {}{}",
+					world.seed,
+					world.edge_length,
+					if world.icy { ", and an icy world" } else { "" }
+				)
+			},
+		}
+	}
+}
+impl std::error::Error for GhostError {}
+
+
+/// The parameters of the world, in which a trajectory was recorded
+///
+/// Only in the very same world, a ghost ship following the trajectory makes
+/// any sense.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[derive(Serialize, Deserialize)]
+pub struct RecordedWorld {
+	/// The seed of the world
+	///
+	/// Stored as string, because not every file format supports the full `u64` range.
+	#[serde(with = "seed_string")]
+	pub seed: u64,
+	/// The edge length of the map in tiles, see `Setting::edge_length`
+	pub edge_length: u16,
+	/// Whether the world was generated wintry, with ice floes
+	pub icy: bool,
+}
+
+
+/// How well a shared run went
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize)]
+pub enum Score {
+	/// A race, finished after the given number of ticks
+	Race(u64),
+	/// A voyage outside of races, ending with the given money
+	Voyage(u64),
+}
+
+impl Score {
+	/// Whether this score beats the `other` one
+	///
+	/// The faster race and the richer voyage wins. Races and voyages do not
+	/// compare, so either beats the other kind.
+	pub fn beats(self, other: Self) -> bool {
+		match (self, other) {
+			(Self::Race(ticks), Self::Race(other)) => ticks < other,
+			(Self::Voyage(money), Self::Voyage(other)) => money > other,
+			_ => true,
+		}
+	}
+}
+
+
+/// The state of a ship at one tick of a trajectory
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[derive(Serialize, Deserialize)]
+pub struct TrajectorySample {
+	/// The position of the ship
+	pub pos: Location,
+	/// The heading of the ship in radians
+	pub heading: f32,
+}
+
+impl TrajectorySample {
+	pub fn of(vehicle: &Vehicle) -> Self {
+		Self {
+			pos: vehicle.pos,
+			heading: vehicle.heading,
+		}
+	}
+}
+
+/// The path of a ship, sampled at every tick
+#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Serialize, Deserialize)]
+pub struct Trajectory {
+	/// The tick of the first sample
+	pub start: Tick,
+	/// The world in which the trajectory was recorded
+	pub world: RecordedWorld,
+	/// The samples, one per tick
+	pub samples: Vec<TrajectorySample>,
+}
+
+impl Trajectory {
+	/// Creates an empty trajectory in the given `world` beginning at the tick `start`
+	pub fn new(world: RecordedWorld, start: Tick) -> Self {
+		Self {
+			start,
+			world,
+			samples: Vec::new(),
+		}
+	}
+
+	/// Records the state of the `vehicle` at the given `tick`
+	///
+	/// Missing ticks, e.g. when recording resumes after a pause, are filled
+	/// with the last known state. Ticks already recorded are ignored.
+	pub fn record(&mut self, tick: Tick, vehicle: &Vehicle) {
+		let idx = match tick.0.checked_sub(self.start.0) {
+			Some(idx) => idx as usize,
+			None => return,
+		};
+		if idx < self.samples.len() {
+			return;
+		}
+
+		let sample = TrajectorySample::of(vehicle);
+		let filler = self.samples.last().copied().unwrap_or(sample);
+		self.samples.resize(idx, filler);
+		self.samples.push(sample);
+	}
+
+	/// Returns the sample at the given `tick`, if recorded
+	pub fn at(&self, tick: Tick) -> Option<&TrajectorySample> {
+		let idx = tick.0.checked_sub(self.start.0)?;
+		self.samples.get(usize::try_from(idx).ok()?)
+	}
+
+	/// Returns the sample between the given `tick` and its predecessor
+	///
+	/// `alpha` is the progress from the previous tick (`0`) to the given tick (`1`).
+	pub fn interpolated(
+		&self,
+		terrain: &Terrain,
+		tick: Tick,
+		alpha: f32,
+	) -> Option<TrajectorySample> {
+		let current = self.at(tick)?;
+		let prev = tick
+			.0
+			.checked_sub(1)
+			.and_then(|t| self.at(Tick(t)))
+			.unwrap_or(current);
+
+		let step = terrain.torus_distance(prev.pos, current.pos);
+		Some(TrajectorySample {
+			pos: terrain.map_loc_on_torus(prev.pos + step * alpha),
+			heading: prev.heading + normalize_angle_rel(current.heading - prev.heading) * alpha,
+		})
+	}
+
+	/// Returns the tick after the last sample
+	pub fn end(&self) -> Tick {
+		Tick(self.start.0 + self.samples.len() as u64)
+	}
+
+	/// Drops all samples from the tick `end` on, e.g. after finishing a race
+	pub fn truncate(&mut self, end: Tick) {
+		let len = end.0.saturating_sub(self.start.0);
+		self.samples
+			.truncate(usize::try_from(len).unwrap_or(usize::MAX));
+	}
+
+	/// Checks that the trajectory has been recorded in the given `world`
+	pub fn check_world(&self, world: &RecordedWorld) -> Result<(), GhostError> {
+		if self.world == *world {
+			Ok(())
+		} else {
+			Err(GhostError::OtherWorld(self.world))
+		}
+	}
+}
+
+/// A run shared with other players, see the module docs for the file format
+#[derive(Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize)]
+pub struct Ghost {
+	/// How well the run went, to keep only the best one
+	pub score: Score,
+	/// The recorded run
+	pub trajectory: Trajectory,
+}
+
+impl Ghost {
+	/// Encodes the ghost as a ghost file of the current [GHOST_VERSION]
+	pub fn export(&self) -> Vec<u8> {
+		let mut bytes = Vec::from(GHOST_MAGIC);
+		bytes.extend_from_slice(&GHOST_VERSION.to_le_bytes());
+		bincode::serialize_into(&mut bytes, self).expect("Failed to encode the ghost");
+		bytes
+	}
+
+	/// Decodes a ghost from a ghost file, as written by [Self::export]
+	///
+	/// The ghost has to be recorded in the given `world`.
+	pub fn import(bytes: &[u8], world: &RecordedWorld) -> Result<Self, GhostError> {
+		let content = bytes
+			.strip_prefix(&GHOST_MAGIC)
+			.ok_or(GhostError::NotAGhost)?;
+		let (version, content) = match content {
+			[lo, hi, content @ ..] => (u16::from_le_bytes([*lo, *hi]), content),
+			_ => return Err(GhostError::NotAGhost),
+		};
+		if version != GHOST_VERSION {
+			return Err(GhostError::UnsupportedVersion(version));
+		}
+
+		let ghost: Self =
+			bincode::deserialize(content).map_err(|err| GhostError::Corrupted(err.to_string()))?;
+		ghost.trajectory.check_world(world)?;
+
+		Ok(ghost)
+	}
+}
+
+/// Serializes the seed as decimal string
+mod seed_string {
+	use serde::de::Error;
+	use serde::Deserialize;
+	use serde::Deserializer;
+	use serde::Serializer;
+
+	pub fn serialize<S: Serializer>(seed: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(seed)
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+		String::deserialize(deserializer)?
+			.parse()
+			.map_err(D::Error::custom)
+	}
+}
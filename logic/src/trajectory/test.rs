@@ -0,0 +1,175 @@
+use super::Ghost;
+use super::GhostError;
+use super::RecordedWorld;
+use super::Score;
+use super::Trajectory;
+use super::GHOST_MAGIC;
+use super::GHOST_VERSION;
+use crate::state::Vehicle;
+use crate::units::Location;
+use crate::units::Tick;
+
+fn world(seed: u64) -> RecordedWorld {
+	RecordedWorld {
+		seed,
+		edge_length: 32,
+		icy: false,
+	}
+}
+
+fn vehicle_at(x: f32) -> Vehicle {
+	let mut vehicle = Vehicle::default();
+	vehicle.pos = Location::new(x, 0.0);
+	vehicle
+}
+
+#[test]
+fn samples_are_found_by_tick() {
+	// Arrange
+	let mut trajectory = Trajectory::new(world(42), Tick(10));
+
+	// Act
+	for t in 10..20 {
+		trajectory.record(Tick(t), &vehicle_at(t as f32));
+	}
+
+	// Assert
+	assert_eq!(trajectory.end(), Tick(20));
+	assert_eq!(trajectory.at(Tick(9)), None);
+	assert_eq!(
+		trajectory.at(Tick(12)).unwrap().pos,
+		Location::new(12.0, 0.0)
+	);
+	assert_eq!(trajectory.at(Tick(20)), None);
+}
+
+#[test]
+fn gaps_are_filled_with_the_previous_sample() {
+	// Arrange
+	let mut trajectory = Trajectory::new(world(42), Tick(0));
+
+	// Act
+	trajectory.record(Tick(0), &vehicle_at(1.0));
+	trajectory.record(Tick(3), &vehicle_at(2.0));
+	// Already recorded
+	trajectory.record(Tick(1), &vehicle_at(3.0));
+
+	// Assert
+	assert_eq!(trajectory.samples.len(), 4);
+	assert_eq!(trajectory.at(Tick(2)).unwrap().pos, Location::new(1.0, 0.0));
+	assert_eq!(trajectory.at(Tick(3)).unwrap().pos, Location::new(2.0, 0.0));
+}
+
+#[test]
+fn truncating_drops_the_later_samples() {
+	// Arrange
+	let mut trajectory = Trajectory::new(world(42), Tick(10));
+	for t in 10..20 {
+		trajectory.record(Tick(t), &vehicle_at(t as f32));
+	}
+
+	// Act
+	trajectory.truncate(Tick(15));
+
+	// Assert
+	assert_eq!(trajectory.end(), Tick(15));
+	assert_eq!(
+		trajectory.at(Tick(14)).unwrap().pos,
+		Location::new(14.0, 0.0)
+	);
+}
+
+#[test]
+fn ghost_file_round_trips() {
+	// Arrange
+	// A seed beyond the range of some file formats
+	let mut trajectory = Trajectory::new(world(u64::MAX), Tick(3));
+	for t in 3..8 {
+		let mut vehicle = vehicle_at(t as f32);
+		vehicle.heading = t as f32 * 0.1;
+		trajectory.record(Tick(t), &vehicle);
+	}
+	let ghost = Ghost {
+		score: Score::Race(5),
+		trajectory,
+	};
+
+	// Act
+	let bytes = ghost.export();
+	let imported = Ghost::import(&bytes, &world(u64::MAX));
+
+	// Assert
+	assert!(bytes.starts_with(&GHOST_MAGIC));
+	assert_eq!(imported, Ok(ghost));
+}
+
+#[test]
+fn ghost_files_of_other_worlds_are_rejected() {
+	// Arrange
+	let recorded = world(42);
+	let ghost = Ghost {
+		score: Score::Voyage(100),
+		trajectory: Trajectory::new(recorded, Tick(0)),
+	};
+	let bytes = ghost.export();
+	let other_seed = world(43);
+	let other_size = RecordedWorld {
+		edge_length: 64,
+		..recorded
+	};
+	let icy = RecordedWorld {
+		icy: true,
+		..recorded
+	};
+
+	// Act
+	let imported = [other_seed, other_size, icy].map(|other| Ghost::import(&bytes, &other));
+
+	// Assert
+	for imported in imported {
+		assert_eq!(imported, Err(GhostError::OtherWorld(recorded)));
+	}
+}
+
+#[test]
+fn foreign_files_are_not_ghosts() {
+	// Arrange
+	let save_file = b"PoFS\x09\x00whatever";
+
+	// Act
+	let imported = Ghost::import(save_file, &world(42));
+
+	// Assert
+	assert_eq!(imported, Err(GhostError::NotAGhost));
+}
+
+#[test]
+fn ghost_files_of_other_versions_are_rejected() {
+	// Arrange
+	let ghost = Ghost {
+		score: Score::Race(1),
+		trajectory: Trajectory::new(world(42), Tick(0)),
+	};
+	let mut bytes = ghost.export();
+	let older = GHOST_VERSION - 1;
+	bytes[GHOST_MAGIC.len()..][..2].copy_from_slice(&older.to_le_bytes());
+
+	// Act
+	let imported = Ghost::import(&bytes, &world(42));
+
+	// Assert
+	assert_eq!(imported, Err(GhostError::UnsupportedVersion(older)));
+}
+
+#[test]
+fn faster_races_and_richer_voyages_score_better() {
+	// Act & Assert
+	assert!(Score::Race(100).beats(Score::Race(200)));
+	assert!(!Score::Race(200).beats(Score::Race(100)));
+	assert!(!Score::Race(100).beats(Score::Race(100)));
+	assert!(Score::Voyage(200).beats(Score::Voyage(100)));
+	assert!(!Score::Voyage(100).beats(Score::Voyage(200)));
+	assert!(!Score::Voyage(100).beats(Score::Voyage(100)));
+	assert!(Score::Voyage(0).beats(Score::Race(100)));
+	assert!(Score::Race(100).beats(Score::Voyage(0)));
+}
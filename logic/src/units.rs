@@ -1,3 +1,5 @@
+use std::f32::consts::PI;
+use std::f32::consts::TAU;
 use std::ops::Add;
 use std::ops::AddAssign;
 use std::ops::Div;
@@ -17,6 +19,9 @@ use rand_distr::uniform::UniformSampler;
 use serde::Deserialize;
 use serde::Serialize;
 
+#[cfg(test)]
+mod test;
+
 
 /// An arbitrary distance on the map given in meters
 #[derive(Debug, Copy, Clone, PartialEq, Default)]
@@ -95,6 +100,30 @@ impl DivAssign<f32> for Distance {
 	}
 }
 
+/// The upper wind speed limits in m/s of the Beaufort forces 0 to 11
+///
+/// Any faster wind is of force 12.
+const BEAUFORT_LIMITS: [f32; 12] = [
+	0.5, 1.6, 3.4, 5.5, 8.0, 10.8, 13.9, 17.2, 20.8, 24.5, 28.5, 32.7,
+];
+
+/// The names of the Beaufort forces 0 to 12
+const BEAUFORT_DESCRIPTIONS: [&str; 13] = [
+	"Calm",
+	"Light air",
+	"Light breeze",
+	"Gentle breeze",
+	"Moderate breeze",
+	"Fresh breeze",
+	"Strong breeze",
+	"Near gale",
+	"Gale",
+	"Strong gale",
+	"Storm",
+	"Violent storm",
+	"Hurricane",
+];
+
 /// Represents wind conditions
 #[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[derive(Serialize, Deserialize)]
@@ -111,6 +140,39 @@ impl Wind {
 	pub fn from_polar(angle: f32, magnitude: f32) -> Self {
 		Self(Vec2::new(angle.cos(), angle.sin()) * magnitude)
 	}
+
+	/// The force of this wind on the Beaufort scale, from `0` to `12`
+	pub fn beaufort(self) -> u8 {
+		let magnitude = self.magnitude();
+		BEAUFORT_LIMITS
+			.iter()
+			.position(|&limit| magnitude < limit)
+			.unwrap_or(BEAUFORT_LIMITS.len()) as u8
+	}
+
+	/// The name of this wind on the Beaufort scale, e.g. "Gentle breeze"
+	pub fn description(self) -> &'static str {
+		BEAUFORT_DESCRIPTIONS[usize::from(self.beaufort())]
+	}
+
+	/// Interpolates between two winds in polar coordinates
+	///
+	/// The direction turns along the shortest arc, while the magnitude is
+	/// interpolated linearly. Thus, unlike a linear interpolation of the
+	/// vectors, the wind does not calm down when turning to the opposite
+	/// direction.
+	pub fn lerp_polar(a: Self, b: Self, t: f32) -> Self {
+		// The angle difference in range [-π,π)
+		let mut diff = (b.angle() - a.angle()).rem_euclid(TAU);
+		if diff > PI {
+			diff -= TAU;
+		}
+
+		let angle = a.angle() + diff * t;
+		let magnitude = a.magnitude() + (b.magnitude() - a.magnitude()) * t;
+
+		Self::from_polar(angle, magnitude)
+	}
 }
 
 /// An arbitrary location on the map given in meters
@@ -329,6 +391,29 @@ impl TileType {
 			Self::Grass => Elevation::HIGHEST,
 		}
 	}
+
+	/// The factor of the friction of a ship above this kind of tile
+	///
+	/// Land is impassable anyway, thus it adds no drag.
+	pub const fn drag_factor(self) -> f32 {
+		match self {
+			Self::DeepWater => crate::DEEP_WATER_DRAG_FACTOR,
+			Self::ShallowWater => crate::SHALLOW_WATER_DRAG_FACTOR,
+			Self::Beach | Self::Grass => 1.0,
+		}
+	}
+
+	/// The default color of this kind of tile on maps as RGBA, matching the sprites
+	///
+	/// See [TerrainPalette](crate::terrain::TerrainPalette) to override it.
+	pub const fn color(self) -> [u8; 4] {
+		match self {
+			Self::DeepWater => [0x1d, 0x4e, 0x89, 0xff],
+			Self::ShallowWater => [0x3d, 0x9b, 0xc4, 0xff],
+			Self::Beach => [0xe8, 0xd5, 0x9e, 0xff],
+			Self::Grass => [0x5a, 0x9e, 0x4b, 0xff],
+		}
+	}
 }
 
 
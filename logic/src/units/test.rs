@@ -0,0 +1,96 @@
+use std::f32::consts::PI;
+
+use super::Wind;
+
+fn assert_wind(actual: Wind, expected: Wind) {
+	assert!(
+		(actual.0 - expected.0).magnitude() < 1e-4,
+		"expected {expected:?}, got {actual:?}"
+	);
+}
+
+#[test]
+fn lerp_polar_keeps_the_endpoints() {
+	// Arrange
+	let a = Wind::from_polar(0.3, 5.0);
+	let b = Wind::from_polar(2.0, 10.0);
+
+	// Act
+	let start = Wind::lerp_polar(a, b, 0.0);
+	let end = Wind::lerp_polar(a, b, 1.0);
+
+	// Assert
+	assert_wind(start, a);
+	assert_wind(end, b);
+}
+
+#[test]
+fn lerp_polar_does_not_calm_between_opposite_winds() {
+	// Arrange
+	let a = Wind::from_polar(0.0, 10.0);
+	let b = Wind::from_polar(PI, 10.0);
+
+	// Act
+	let middle = Wind::lerp_polar(a, b, 0.5);
+
+	// Assert
+	assert!((middle.magnitude() - 10.0).abs() < 1e-4, "{middle:?}");
+}
+
+#[test]
+fn lerp_polar_turns_along_the_shortest_arc() {
+	// Arrange
+	let a = Wind::from_polar(PI - 0.2, 10.0);
+	let b = Wind::from_polar(-PI + 0.2, 10.0);
+
+	// Act
+	let middle = Wind::lerp_polar(a, b, 0.5);
+
+	// Assert
+	// Turning across the negative x axis, rather than the positive one
+	assert_wind(middle, Wind::from_polar(PI, 10.0));
+}
+
+#[test]
+fn lerp_polar_interpolates_the_magnitude() {
+	// Arrange
+	let a = Wind::from_polar(1.0, 4.0);
+	let b = Wind::from_polar(1.0, 12.0);
+
+	// Act
+	let quarter = Wind::lerp_polar(a, b, 0.25);
+
+	// Assert
+	assert_wind(quarter, Wind::from_polar(1.0, 6.0));
+}
+
+#[test]
+fn beaufort_classifies_the_wind_speed() {
+	// Arrange
+	let table = [
+		(0.0, 0, "Calm"),
+		(0.49, 0, "Calm"),
+		(0.5, 1, "Light air"),
+		(3.0, 2, "Light breeze"),
+		(5.0, 3, "Gentle breeze"),
+		(7.9, 4, "Moderate breeze"),
+		(10.0, 5, "Fresh breeze"),
+		(13.0, 6, "Strong breeze"),
+		(15.0, 7, "Near gale"),
+		(20.0, 8, "Gale"),
+		(24.0, 9, "Strong gale"),
+		(28.0, 10, "Storm"),
+		(32.0, 11, "Violent storm"),
+		(32.7, 12, "Hurricane"),
+		(100.0, 12, "Hurricane"),
+	];
+
+	for (speed, force, name) in table {
+		// Act
+		let wind = Wind::from_polar(0.0, speed);
+
+		// Assert
+		assert_eq!(wind.beaufort(), force, "at {speed} m/s");
+		assert_eq!(wind.description(), name, "at {speed} m/s");
+	}
+}
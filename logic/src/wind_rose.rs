@@ -0,0 +1,99 @@
+//! Statistics of the wind over time, e.g. to find the prevailing wind of a world
+//!
+
+use std::f32::consts::TAU;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::state::WorldState;
+use crate::units::Tick;
+use crate::units::Wind;
+use crate::WorldInit;
+use crate::MAX_WIND_SPEED;
+
+#[cfg(test)]
+mod test;
+
+
+
+/// The number of direction sectors of a wind rose
+pub const WIND_ROSE_SECTORS: usize = 16;
+
+/// The number of wind speed bins per sector of a wind rose
+///
+/// The bins evenly divide the wind speeds up to [MAX_WIND_SPEED].
+pub const WIND_ROSE_SPEED_BINS: usize = 5;
+
+/// A histogram of the wind over a period of time
+///
+/// The directions are the directions the wind is blowing to, as given by
+/// [Wind::angle]. The first sector is centered around the angle zero, the
+/// following sectors continue counterclockwise.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Serialize, Deserialize)]
+pub struct WindRose {
+	/// The number of samples per direction sector and wind speed bin
+	pub counts: [[u64; WIND_ROSE_SPEED_BINS]; WIND_ROSE_SECTORS],
+}
+
+impl WindRose {
+	/// Returns the direction sector of the given `wind`
+	pub fn sector_of(wind: Wind) -> usize {
+		let sector_width = TAU / WIND_ROSE_SECTORS as f32;
+		let rel = (wind.angle() + sector_width * 0.5).rem_euclid(TAU);
+
+		((rel / sector_width) as usize).min(WIND_ROSE_SECTORS - 1)
+	}
+
+	/// Returns the wind speed bin of the given `wind`
+	pub fn speed_bin_of(wind: Wind) -> usize {
+		let bin_width = MAX_WIND_SPEED / WIND_ROSE_SPEED_BINS as f32;
+
+		((wind.magnitude() / bin_width) as usize).min(WIND_ROSE_SPEED_BINS - 1)
+	}
+
+	/// Returns the center angle of the given direction `sector`
+	pub fn sector_angle(sector: usize) -> f32 {
+		sector as f32 * TAU / WIND_ROSE_SECTORS as f32
+	}
+
+	/// Adds a sample of the `wind`
+	pub fn add(&mut self, wind: Wind) {
+		self.counts[Self::sector_of(wind)][Self::speed_bin_of(wind)] += 1;
+	}
+
+	/// Returns the total number of samples
+	pub fn samples(&self) -> u64 {
+		self.counts.iter().flatten().sum()
+	}
+
+	/// Returns the number of samples per direction sector, regardless of the speed
+	pub fn direction_counts(&self) -> [u64; WIND_ROSE_SECTORS] {
+		self.counts.map(|bins| bins.iter().sum())
+	}
+
+	/// Returns the direction sector with the most samples, if any
+	pub fn prevailing_sector(&self) -> Option<usize> {
+		let counts = self.direction_counts();
+		counts
+			.iter()
+			.enumerate()
+			.filter(|(_, count)| **count > 0)
+			// The first one wins a tie
+			.max_by_key(|(idx, count)| (**count, std::cmp::Reverse(*idx)))
+			.map(|(idx, _)| idx)
+	}
+}
+
+/// Samples the wind of the given world in each of the first `ticks` ticks
+///
+/// Just like the wind itself, the result is fully determined by the seed
+/// and the debugging configuration of the world.
+pub fn wind_statistics(init: &WorldInit, ticks: u64) -> WindRose {
+	let mut rose = WindRose::default();
+	for tick in 0..ticks {
+		rose.add(WorldState::wind_at(init, Tick(tick)));
+	}
+	rose
+}
@@ -0,0 +1,83 @@
+use super::wind_statistics;
+use super::WindRose;
+use super::WIND_ROSE_SECTORS;
+use super::WIND_ROSE_SPEED_BINS;
+use crate::generator::Setting;
+use crate::terrain::Terrain;
+use crate::units::Wind;
+use crate::DebuggingConf;
+use crate::WorldInit;
+use crate::MAX_WIND_SPEED;
+use crate::TICKS_PER_SECOND;
+use crate::WIND_CHANGE_INTERVAL;
+
+fn world_init(seed: u64, dbg: DebuggingConf) -> WorldInit {
+	let edge_length = 4;
+	WorldInit {
+		terrain_setting: Setting {
+			edge_length,
+			resource_density: 0.0,
+			resources: Default::default(),
+		},
+		terrain: Terrain::new(edge_length),
+		seed,
+		dbg,
+		physics: Default::default(),
+		animation_lod: None,
+	}
+}
+
+#[test]
+fn fixed_wind_yields_a_single_direction() {
+	// Arrange
+	let init = world_init(
+		42,
+		DebuggingConf {
+			fixed_wind_direction: Some(1.0),
+			..Default::default()
+		},
+	);
+
+	// Act
+	let rose = wind_statistics(&init, 1000);
+
+	// Assert
+	let sector = WindRose::sector_of(Wind::from_polar(1.0, MAX_WIND_SPEED));
+	assert_eq!(rose.samples(), 1000);
+	assert_eq!(rose.prevailing_sector(), Some(sector));
+	assert_eq!(rose.direction_counts()[sector], 1000);
+	assert_eq!(rose.counts[sector][WIND_ROSE_SPEED_BINS - 1], 1000);
+}
+
+#[test]
+fn wind_statistics_are_deterministic() {
+	// Arrange
+	let init = world_init(42, DebuggingConf::default());
+	let ticks = 20 * u64::from(TICKS_PER_SECOND) * u64::from(WIND_CHANGE_INTERVAL);
+
+	// Act
+	let first = wind_statistics(&init, ticks);
+	let second = wind_statistics(&init, ticks);
+
+	// Assert
+	assert_eq!(first, second);
+	assert_eq!(first.samples(), ticks);
+	assert!(first.direction_counts().iter().filter(|&&c| c > 0).count() > 1);
+}
+
+#[test]
+fn sectors_are_centered_around_their_angle() {
+	for sector in 0..WIND_ROSE_SECTORS {
+		// Arrange
+		let angle = WindRose::sector_angle(sector);
+		let half_width = std::f32::consts::PI / WIND_ROSE_SECTORS as f32;
+
+		// Act
+		let below = WindRose::sector_of(Wind::from_polar(angle - half_width * 0.9, 1.0));
+		let above = WindRose::sector_of(Wind::from_polar(angle + half_width * 0.9, 1.0));
+
+		// Assert
+		assert_eq!(below, sector);
+		assert_eq!(above, sector);
+	}
+}